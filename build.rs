@@ -0,0 +1,413 @@
+//! Checks, at compile time, that each ShaderSignature matches its GLSL.
+//!
+//! The `name_glsl` fields in ‘src/renderer_webgl/signature/’ are copied by hand
+//! from the `.vert` and `.frag` files. Without this check, a typo only shows
+//! up at runtime, as R33512 or R11006.
+//!
+//! Naga’s GLSL front-end only accepts GLSL 440+ and GLSL ES 300+, but our
+//! shaders are `#version 100`. So this script scans the top-level `attribute`
+//! and `uniform` declarations itself. That is all GLSL ES 1.0 allows for them.
+//!
+//! A declaration inside `#ifdef FEATURE` only exists in the variants built
+//! with that ShaderFeatures flag. The signature’s `feature_gated` list must
+//! name it, so that `ShaderSignature::for_features()` leaves it out of the rest.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+const SHADER_DIR: &str = "src/renderer_webgl/shader";
+const SIGNATURE_DIR: &str = "src/renderer_webgl/signature";
+const SHADER_PROGRAM_NAME_RS: &str =
+    "src/renderer_webgl/shader/shader_program/shader_program_name.rs";
+
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", SHADER_DIR);
+    println!("cargo:rerun-if-changed={}", SIGNATURE_DIR);
+
+    let mut problems = vec![];
+    for program in read_programs() {
+        problems.extend(check_program(&program));
+    }
+
+    if ! problems.is_empty() {
+        eprintln!("{} shader signature mismatch(es):", problems.len());
+        for problem in problems.iter() { eprintln!("  {}", problem) }
+        process::exit(1);
+    }
+}
+
+
+
+
+// PROGRAMS
+
+/// One `ShaderProgramName`, with the GLSL files and signature it is built from.
+struct Program {
+    name: String,
+    vert_file: String,
+    frag_file: String,
+    signature_file: String,
+}
+
+/// Reads the `default_sources()` and `signature_name()` matches from
+/// ‘shader_program_name.rs’, so that this script needs no list of its own.
+fn read_programs() -> Vec<Program> {
+    let rust = read(SHADER_PROGRAM_NAME_RS);
+    let mut sources: BTreeMap<String, (String, String)> = BTreeMap::new();
+    let mut signatures: BTreeMap<String, String> = BTreeMap::new();
+
+    for line in rust.lines() {
+        let line = line.trim();
+        let (name, value) = match line.strip_prefix("ShaderProgramName::")
+            .and_then(|rest| rest.split_once("=>")) {
+            Some((name, value)) => (name.trim().to_string(), value.trim()),
+            None => continue,
+        };
+
+        // Eg `ShaderProgramName::BlueBox => (box_vert(), blue_frag()),`
+        if let Some(pair) = value.strip_prefix('(') {
+            let files: Vec<String> = pair.split(',')
+                .filter_map(|part| {
+                    let function = part.trim().trim_end_matches(')').trim_end_matches('(');
+                    let (stem, ext) = function.rsplit_once('_')?;
+                    Some(format!("{}.{}", stem, ext))
+                })
+                .collect();
+            sources.insert(name, (files[0].clone(), files[1].clone()));
+
+        // Eg `ShaderProgramName::BlueBox => ShaderSignatureName::BlueRedBox,`
+        } else if let Some(signature_name) = value.strip_prefix("ShaderSignatureName::") {
+            let signature_name = signature_name.trim_end_matches(',');
+            signatures.insert(name, format!("shader_signature_{}.rs", snake_case(signature_name)));
+        }
+    }
+
+    sources.into_iter()
+        .map(|(name, (vert_file, frag_file))| Program {
+            signature_file: signatures.get(&name).cloned()
+                .unwrap_or_else(|| panic!("no signature_name() for {}", name)),
+            name,
+            vert_file,
+            frag_file,
+        })
+        .collect()
+}
+
+/// Compares one program’s GLSL declarations with its ShaderSignature.
+fn check_program(
+    program: &Program,
+) -> Vec<String> {
+    let vert = resolve_includes(&read(&format!("{}/vertex_shader/{}", SHADER_DIR, program.vert_file)));
+    let frag = resolve_includes(&read(&format!("{}/fragment_shader/{}", SHADER_DIR, program.frag_file)));
+    let signature = read(&format!("{}/{}", SIGNATURE_DIR, program.signature_file));
+
+    let mut declared = declarations(&vert);
+    declared.extend(declarations(&frag));
+    let expected = signature_entries(&signature);
+    let gated = signature_feature_gated(&signature);
+
+    let mut problems = vec![];
+    let context = format!("{} ({}, {}, {})",
+        program.name, program.vert_file, program.frag_file, program.signature_file);
+
+    for entry in expected.iter() {
+        match declared.iter().find(|d| d.qualifier == entry.qualifier && d.name == entry.name) {
+            None => problems.push(format!("{}: {} `{}` is in the signature, but not the GLSL",
+                context, entry.qualifier, entry.name)),
+            Some(d) if d.glsl_type != entry.glsl_type => problems.push(format!(
+                "{}: {} `{}` is `{}` in the GLSL, but `{}` in the signature",
+                context, entry.qualifier, entry.name, d.glsl_type, entry.glsl_type)),
+            Some(_) => {},
+        }
+    }
+    for d in declared.iter() {
+        if ! expected.iter().any(|entry| entry.qualifier == d.qualifier && entry.name == d.name) {
+            problems.push(format!("{}: {} `{} {}` is in the GLSL, but not the signature",
+                context, d.qualifier, d.glsl_type, d.name));
+        }
+    }
+
+    // Each declaration inside an `#ifdef` must be in `feature_gated`, under the
+    // same feature, and each `feature_gated` entry must be inside its `#ifdef`.
+    for d in declared.iter() {
+        let listed = gated.iter()
+            .find(|(name, _)| *name == d.name)
+            .map(|(_, feature)| feature);
+        match (&d.feature, listed) {
+            (None, None) => {},
+            (Some(feature), Some(listed)) if feature == listed => {},
+            (Some(feature), _) if ! is_feature_name(feature) => problems.push(format!(
+                "{}: {} `{}` is declared under `#if {}`, but only `#ifdef FEATURE` can gate a declaration",
+                context, d.qualifier, d.name, feature)),
+            (Some(feature), None) => problems.push(format!(
+                "{}: {} `{}` only exists under `#ifdef {}`, but the signature’s `feature_gated` doesn’t list it",
+                context, d.qualifier, d.name, feature)),
+            (None, Some(listed)) => problems.push(format!(
+                "{}: {} `{}` is always declared, but the signature’s `feature_gated` ties it to {}",
+                context, d.qualifier, d.name, listed)),
+            (Some(feature), Some(listed)) => problems.push(format!(
+                "{}: {} `{}` is declared under `#ifdef {}`, but the signature’s `feature_gated` ties it to {}",
+                context, d.qualifier, d.name, feature, listed)),
+        }
+    }
+    for (name, feature) in gated.iter() {
+        if ! declared.iter().any(|d| d.name == *name) {
+            problems.push(format!("{}: `feature_gated` ties `{}` to {}, but the GLSL doesn’t declare it",
+                context, name, feature));
+        }
+    }
+    problems
+}
+
+
+
+
+// PARSING
+
+/// An `attribute` or `uniform`, from either a GLSL file or a signature file.
+struct Declaration {
+    /// "attribute" or "uniform".
+    qualifier: &'static str,
+    /// Eg "vec3".
+    glsl_type: String,
+    /// Eg "position".
+    name: String,
+    /// The `#ifdef` the declaration is inside, eg "INSTANCE_COLOR", or None if
+    /// it’s always declared. Signature entries use `feature_gated` instead.
+    feature: Option<String>,
+}
+
+/// Finds every `attribute` and `uniform` declaration in GLSL ES 1.0 source,
+/// noting which `#ifdef`, `#ifndef` or `#if` block, if any, each is inside.
+fn declarations(
+    glsl: &str,
+) -> Vec<Declaration> {
+    let mut found = vec![];
+    let mut conditions: Vec<String> = vec![];
+    let mut code = String::new();
+
+    for line in strip_comments(glsl).lines() {
+        let directive = match line.trim_start().strip_prefix('#') {
+            Some(directive) => directive.trim(),
+            None => {
+                code.push_str(line);
+                code.push('\n');
+                continue;
+            },
+        };
+
+        // Every line since the last directive shares the same conditions.
+        let feature = if conditions.is_empty() { None } else { Some(conditions.join(" && ")) };
+        found.extend(block_declarations(&code, feature));
+        code.clear();
+
+        let mut words = directive.split_whitespace();
+        match (words.next(), words.next()) {
+            (Some("ifdef"), Some(name)) => conditions.push(name.into()),
+            (Some("ifndef"), Some(name)) => conditions.push(format!("!{}", name)),
+            (Some("if"), _) => conditions.push(directive.into()),
+            (Some("elif"), _) | (Some("else"), _) => if let Some(condition) = conditions.last_mut() {
+                *condition = format!("!({})", condition);
+            },
+            (Some("endif"), _) => { conditions.pop(); },
+            _ => {},
+        }
+    }
+    let feature = if conditions.is_empty() { None } else { Some(conditions.join(" && ")) };
+    found.extend(block_declarations(&code, feature));
+    found
+}
+
+/// Finds every `attribute` and `uniform` declaration in GLSL which contains
+/// no preprocessor directives.
+fn block_declarations(
+    code: &str,
+    feature: Option<String>,
+) -> Vec<Declaration> {
+    let mut found = vec![];
+    for statement in code.split([';', '{', '}']) {
+        let mut tokens = statement.split_whitespace();
+        let qualifier = match tokens.next() {
+            Some("attribute") => "attribute",
+            Some("uniform") => "uniform",
+            _ => continue,
+        };
+        let mut tokens = tokens.skip_while(|t| *t == "lowp" || *t == "mediump" || *t == "highp");
+        let glsl_type = match tokens.next() { Some(t) => t.to_string(), None => continue };
+
+        // Eg `uniform mat4 u_a, u_b[2];`
+        let names: String = tokens.collect::<Vec<_>>().join(" ");
+        for name in names.split(',') {
+            let name = name.split('[').next().unwrap_or("").trim();
+            if name.is_empty() { continue }
+            found.push(Declaration {
+                qualifier,
+                glsl_type: glsl_type.clone(),
+                name: name.into(),
+                feature: feature.clone(),
+            });
+        }
+    }
+    found
+}
+
+/// Replaces each `#include "name"` line with ‘chunk/name’, as `SkCool::preprocess()`
+/// does at runtime. The `#ifdef` blocks are kept, for `declarations()`.
+fn resolve_includes(
+    glsl: &str,
+) -> String {
+    let mut included: Vec<String> = vec![];
+    let mut pending: Vec<String> = vec![glsl.into()];
+    let mut resolved = String::new();
+    while let Some(source) = pending.pop() {
+        for line in source.lines() {
+            match line.trim().strip_prefix("#include") {
+                Some(name) => {
+                    let name = name.trim().trim_matches('"').to_string();
+                    if included.contains(&name) { continue }
+                    pending.push(read(&format!("{}/chunk/{}", SHADER_DIR, name)));
+                    included.push(name);
+                },
+                None => {
+                    resolved.push_str(line);
+                    resolved.push('\n');
+                },
+            }
+        }
+    }
+    resolved
+}
+
+/// Finds every `AttributeSignature` and `UniformSignature` in a signature file,
+/// and converts its `kind` to a GLSL type.
+fn signature_entries(
+    rust: &str,
+) -> Vec<Declaration> {
+    let mut found = vec![];
+    let mut qualifier = None;
+    let mut glsl_type = None;
+
+    for line in rust.lines() {
+        let line = line.trim();
+        if line.starts_with("AttributeSignature {") {
+            qualifier = Some("attribute");
+        } else if line.starts_with("UniformSignature {") {
+            qualifier = Some("uniform");
+        } else if let Some(kind) = line.strip_prefix("kind: AttributeKind::")
+            .or_else(|| line.strip_prefix("kind: UniformKind::")) {
+            glsl_type = Some(kind_to_glsl_type(kind.trim_end_matches(',')));
+        } else if let Some(name) = line.strip_prefix("name_glsl: \"") {
+            let name = name.trim_end_matches(',').trim_end_matches('"');
+            if let (Some(qualifier), Some(glsl_type)) = (qualifier.take(), glsl_type.take()) {
+                found.push(Declaration { qualifier, glsl_type, name: name.into(), feature: None });
+            }
+        }
+    }
+    found
+}
+
+/// Finds every `("name_glsl", ShaderFeatures::FEATURE)` entry in a signature
+/// file’s `feature_gated` list, as (name_glsl, FEATURE) pairs.
+fn signature_feature_gated(
+    rust: &str,
+) -> Vec<(String, String)> {
+    let mut found = vec![];
+    let mut in_list = false;
+    for line in rust.lines() {
+        let line = line.trim();
+        if line.starts_with("feature_gated: vec![") {
+            in_list = true;
+        } else if in_list && line.starts_with(']') {
+            in_list = false;
+        } else if in_list {
+            let entry = line.trim_start_matches('(').trim_end_matches(',').trim_end_matches(')');
+            if let Some((name, feature)) = entry.split_once(',') {
+                found.push((
+                    name.trim().trim_matches('"').to_string(),
+                    feature.trim().trim_start_matches("ShaderFeatures::").to_string(),
+                ));
+            }
+        }
+    }
+    found
+}
+
+
+
+
+// HELPERS
+
+fn read(
+    path: &str,
+) -> String {
+    fs::read_to_string(Path::new(path))
+        .unwrap_or_else(|error| panic!("cannot read {}: {}", path, error))
+}
+
+/// Eg "Mat4" becomes "mat4". Both AttributeKind and UniformKind use these.
+/// Integer attributes reach the shader as floats, so "U8Vec3Norm" is "vec3".
+fn kind_to_glsl_type(
+    kind: &str,
+) -> String {
+    match kind {
+        "F32" => "float",
+        "Mat4" => "mat4",
+        "Sampler2D" => "sampler2D",
+        "Vec2" => "vec2",
+        "Vec3" | "I16Vec3" | "I16Vec3Norm" | "U8Vec3Norm" => "vec3",
+        "Vec4" | "U8Vec4Norm" => "vec4",
+        _ => kind,
+    }.into()
+}
+
+/// Returns `true` for a plain `#ifdef` condition, eg "INSTANCE_COLOR", rather
+/// than eg "!INSTANCE_COLOR" or "defined(A) && defined(B)".
+fn is_feature_name(
+    condition: &str,
+) -> bool {
+    ! condition.is_empty()
+        && condition.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Eg "BlueRedBox" becomes "blue_red_box".
+fn snake_case(
+    camel: &str,
+) -> String {
+    let mut snake = String::new();
+    for (i, c) in camel.chars().enumerate() {
+        if c.is_uppercase() && i > 0 { snake.push('_') }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// Removes `// ...` and `/* ... */` comments, keeping the newlines.
+fn strip_comments(
+    glsl: &str,
+) -> String {
+    let mut stripped = String::with_capacity(glsl.len());
+    let mut chars = glsl.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '/' && chars.peek() == Some(&'/') {
+            while let Some(&next) = chars.peek() {
+                if next == '\n' { break }
+                chars.next();
+            }
+        } else if c == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut previous = ' ';
+            for next in chars.by_ref() {
+                if next == '\n' { stripped.push('\n') }
+                if previous == '*' && next == '/' { break }
+                previous = next;
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
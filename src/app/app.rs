@@ -1,15 +1,24 @@
 extern crate console_error_panic_hook;
+use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::wasm_bindgen;
+use web_sys::HtmlImageElement;
 
-use crate::develop::{CameraPreset,GuidesPreset,Develop,LodPreset,log,WireframePreset};
-use crate::scene::{SceneContainer,SceneContainerName};
-use crate::renderer_webgl::{RendererWebGl,RkWarm};
-use super::Timer;
+use crate::error::ERROR as E;
+use crate::develop::{CameraPreset,DebugPreset,GuidesPreset,Develop,LightingPreset,LodPreset,log,WireframePreset};
+use crate::scene::{quad_view_labels_json,SceneContainer,SceneContainerName,TextureName};
+use crate::renderer_webgl::{PostChain,PostPass,RenderTarget,RendererError,RendererWebGl,RkCool,RkTepid,RkWarm,ShaderProgramName};
+use super::{Capture,CapturedFrame,StillImage,StillTile,Timer};
 
 /// Wraps state, and the public-facing ‘Phase N’ methods.
 #[wasm_bindgen]
 pub struct App {
+    /// Set while frames are being captured at a fixed frame rate.
+    capture: Option<Capture>,
     develop: Develop,
+    /// Every distinct RendererError the App has recovered from, in order.
+    errors: Vec<RendererError>,
+    /// How many of `errors` have already been sent in a Phase 9 report.
+    errors_reported: usize,
     renderer: RendererWebGl,
     scene_container: SceneContainer,
     timer: Timer,
@@ -24,45 +33,68 @@ impl App {
         canvas_id: String,
         scene_container_name: SceneContainerName,
         camera_preset: CameraPreset,
+        debug_preset: DebugPreset,
         guides_preset: GuidesPreset,
+        lighting_preset: LightingPreset,
         lod_preset: LodPreset,
         wireframe_preset: WireframePreset,
-    ) -> Self {
+    ) -> Result<App, JsValue> {
 
         // If a panic occurs, pass it to the browser’s `console.error()`.
         // github.com/rustwasm/console_error_panic_hook#usage
         console_error_panic_hook::set_once();
 
         log(&format!(
-            "Canvas: {} {} {} {:?} {:?} {:?} {:?} {:?}",
+            "Canvas: {} {} {} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
             canvas_extent_horizontal,
             canvas_extent_vertical,
             canvas_id,
             scene_container_name,
             camera_preset,
+            debug_preset,
             guides_preset,
+            lighting_preset,
             lod_preset,
             wireframe_preset,
         ));
 
-        // Instantiate the renderer.
+        // Instantiate the renderer. Without it the App is useless, so if it
+        // fails, throw a JavaScript exception.
         let mut renderer = RendererWebGl::new(
             canvas_extent_horizontal,
             canvas_extent_vertical,
             canvas_id,
-        );
-
-        Self {
+        ).map_err(|error| JsValue::from_str(&error.to_string()))?;
+
+        // If the Scene fails to initialise, fall back to an empty Scene, and
+        // report the error during the first Phase 9.
+        let mut errors = vec![];
+        let scene_container =
+            match SceneContainer::new(&mut renderer, scene_container_name) {
+                Ok(scene_container) => scene_container,
+                Err(error) => {
+                    errors.push(error);
+                    renderer.release_scene_resources(); // free any buffers the Scene already made
+                    SceneContainer::empty(&mut renderer)
+                },
+            };
+
+        Ok(Self {
+            capture: None,
             develop: Develop {
                 camera_preset,
+                debug_preset,
                 guides_preset,
+                lighting_preset,
                 lod_preset,
                 wireframe_preset,
             },
-            scene_container: SceneContainer::new(&mut renderer, scene_container_name),
-            renderer, // note, must be placed AFTER `...(&mut renderer, ...)`
+            errors,
+            errors_reported: 0,
+            renderer,
+            scene_container,
             timer: Timer::new(),
-        }
+        })
     }
 
 
@@ -82,6 +114,152 @@ impl App {
         self.scene_container.scene.get_presets()
     }
 
+    /// Returns JSON counts of the WebGL calls skipped since the App started,
+    /// because they would have changed nothing. Useful for benchmarking.
+    pub fn get_skipped_calls(&self) -> String {
+        let skipped_calls = self.renderer.skipped_calls.get();
+        format!(
+            r#"{{"use_program":{},"uniform":{},"pipeline_state":{},"bind_texture":{}}}"#,
+            skipped_calls.use_program,
+            skipped_calls.uniform,
+            skipped_calls.pipeline_state,
+            skipped_calls.bind_texture,
+        )
+    }
+
+    /// Returns a JSON array of the Quad View pane labels, in reading order.
+    /// index.html overlays them on the canvas, so they are on-screen only.
+    pub fn get_quad_view_labels(&self) -> String {
+        quad_view_labels_json()
+    }
+
+    /// Returns JSON describing each shader variant compiled so far, with its
+    /// compile time in milliseconds. Useful for spotting expensive permutations.
+    pub fn get_shader_variants(&self) -> String {
+        self.renderer.shader_variants.to_json()
+    }
+
+    /// Returns JSON listing every live buffer and program, and the total bytes
+    /// of VRAM the buffers take up. Useful for spotting leaks.
+    pub fn get_gpu_resources(&self) -> String {
+        self.renderer.resources.borrow().to_json()
+    }
+
+    /// Swaps new GLSL source into a shader, eg while live-editing a .vert or
+    /// .frag file. Returns `undefined` on success. Otherwise the old program
+    /// keeps running, and the error is returned, including any compile log.
+    pub fn reload_shader(
+        &mut self,
+        shader_program_name: ShaderProgramName,
+        vert_source: String,
+        frag_source: String,
+    ) -> Option<String> {
+        RkCool::reload_shader(
+            &mut self.renderer,
+            shader_program_name,
+            &vert_source,
+            &frag_source,
+        ).err().map(|error| error.to_string())
+    }
+
+    /// Renders the current Scene at any size, eg for a print-quality poster,
+    /// and returns it as PNG bytes. The projection is split into `tiles` by
+    /// `tiles` sub-frustums, each rendered offscreen and then stitched
+    /// together, so the image can be far bigger than the canvas. Time stands
+    /// still while the tiles render. Each tile runs through the post-processing
+    /// passes, rendered with a gutter so that they blend across tile edges.
+    /// Viewports and scissors are per tile, so Quad View fails unless `tiles`
+    /// is 1.
+    pub fn render_still(
+        &mut self,
+        width: u32,
+        height: u32,
+        tiles: u32,
+    ) -> Result<Vec<u8>, JsValue> {
+        if self.develop.camera_preset == CameraPreset::QuadView && tiles > 1 {
+            return Err(JsValue::from_str(&RendererError::new(E::R11907,
+                format!("Quad View in {} tiles per side — use 1", tiles)).to_string()));
+        }
+        let mut still_image = StillImage::new(width, height, tiles, self.renderer.aspect_ratio)
+            .map_err(|error| JsValue::from_str(&error.to_string()))?;
+        let timer = Timer { time_delta: 0., ..self.timer.clone() };
+        let gutter = self.renderer.post_chain.as_ref().map_or(0, PostChain::gutter_px);
+        let mut result = Ok(());
+        for tile in still_image.get_tiles(gutter) {
+            let frame = still_image.get_frame(&tile);
+            result = self.render_still_tile(&tile, frame, &timer)
+                .map(|pixels| still_image.paste(&tile, &pixels));
+            if result.is_err() { break }
+        }
+        let canvas_bound = RkWarm::bind_render_target(&self.renderer, None);
+        result.and(canvas_bound)
+            .map(|_| still_image.to_png())
+            .map_err(|error| JsValue::from_str(&error.to_string()))
+    }
+
+    /// Starts capturing frames for a video, at exactly `fps` frames per second
+    /// of song time, however long each tick really takes. The song restarts
+    /// at its first bar. After each tick, `take_captured_frame()` returns the
+    /// frame, rendered offscreen at `width` by `height`. Until it’s taken,
+    /// later ticks neither move the Timer on nor render.
+    /// Returns how many frames make up a whole 32-bar song.
+    pub fn start_capture(
+        &mut self,
+        fps: f32,
+        width: u32,
+        height: u32,
+    ) -> Result<u32, JsValue> {
+        self.stop_capture();
+        let capture = Capture::new(&self.renderer, fps, width, height)
+            .map_err(|error| JsValue::from_str(&error.to_string()))?;
+        self.capture = Some(capture);
+        self.timer = Timer::new();
+        Ok(Timer::frames_per_song(fps))
+    }
+
+    /// Goes back to rendering to the canvas in real time, and frees the
+    /// capture’s RenderTargets.
+    pub fn stop_capture(
+        &mut self,
+    ) {
+        if let Some(capture) = self.capture.take() { capture.delete(&self.renderer) }
+    }
+
+    /// The frame rendered by the most recent tick, or `undefined` if it’s
+    /// already been taken, or rendering it failed.
+    pub fn take_captured_frame(
+        &mut self,
+    ) -> Option<CapturedFrame> {
+        self.capture.as_mut().and_then(|capture| capture.frame.take())
+    }
+
+    /// Runs the rendered Scene through post-processing passes, eg
+    /// `"bloom, grade, fxaa"`, in that order. An empty string turns them off.
+    /// Returns `undefined` on success, otherwise the error.
+    pub fn set_post_passes(
+        &mut self,
+        post_passes: String,
+    ) -> Option<String> {
+        let renderer = &mut self.renderer;
+        PostPass::from_names(&post_passes)
+            .and_then(|post_passes| renderer.set_post_passes(post_passes))
+            .err().map(|error| error.to_string())
+    }
+
+    /// Replaces one of the Scene’s textures, eg `TextureName.Seed`, with an
+    /// image which has finished loading. The image is kept, so it survives the
+    /// WebGL context being lost. Returns `undefined` on success, otherwise the
+    /// error. Scenes without that texture ignore it.
+    pub fn set_scene_texture(
+        &mut self,
+        texture_name: TextureName,
+        image: HtmlImageElement,
+    ) -> Option<String> {
+        self.scene_container.scene.set_texture(&self.renderer, texture_name, &image)
+            .err().map(|error| error.to_string())
+    }
+
+
 
 
 
@@ -97,11 +275,13 @@ impl App {
         down_evt_x: f32,
         down_evt_y: f32,
         camera_preset: CameraPreset,
+        debug_preset: DebugPreset,
         guides_preset: GuidesPreset,
+        lighting_preset: LightingPreset,
         lod_preset: LodPreset,
         wireframe_preset: WireframePreset,
         parameter_values_raw: String,
-    ) {
+    ) -> String {
         self.update_state(
             time_in_ms,
             origin_x,
@@ -109,7 +289,9 @@ impl App {
             down_evt_x,
             down_evt_y,
             camera_preset,
+            debug_preset,
             guides_preset,
+            lighting_preset,
             lod_preset,
             wireframe_preset,
             parameter_values_raw,
@@ -136,14 +318,28 @@ impl App {
         down_evt_x: f32,
         down_evt_y: f32,
         camera_preset: CameraPreset,
+        debug_preset: DebugPreset,
         guides_preset: GuidesPreset,
+        lighting_preset: LightingPreset,
         lod_preset: LodPreset,
         wireframe_preset: WireframePreset,
         parameter_values_raw: String,
     ) {
         // Phase 1A: Update the App’s internal Timer.
         // Note that the App uses seconds, not milliseconds, internally.
-        self.timer.update(time_in_ms);
+        // While the WebGL context is lost, the Timer is paused. After it’s been
+        // restored, the Scene rebuilds its Shaders and buffers.
+        if self.renderer.context_watcher.take_restored() {
+            self.restore();
+        }
+        // While capturing, the Timer moves on by exactly one frame per tick,
+        // but waits while the previous frame has not been taken.
+        self.timer.paused = self.renderer.context_watcher.is_lost();
+        match &self.capture {
+            Some(capture) if capture.frame.is_some() => {},
+            Some(capture) => self.timer.update(capture.get_time_in_ms()),
+            None => self.timer.update(time_in_ms),
+        }
 
 
         // Phase 1B: Take note of any recent input events.
@@ -157,10 +353,18 @@ impl App {
             self.develop.camera_preset = camera_preset;
             // log(&format!("camera_preset: {:?}", self.develop.camera_preset));
         }
+        if self.develop.debug_preset != debug_preset {
+            self.develop.debug_preset = debug_preset;
+            // log(&format!("debug_preset: {:?}", self.develop.debug_preset));
+        }
         if self.develop.guides_preset != guides_preset {
             self.develop.guides_preset = guides_preset;
             // log(&format!("guides_preset: {:?}", self.develop.guides_preset));
         }
+        if self.develop.lighting_preset != lighting_preset {
+            self.develop.lighting_preset = lighting_preset;
+            // log(&format!("lighting_preset: {:?}", self.develop.lighting_preset));
+        }
         if self.develop.lod_preset != lod_preset {
             self.develop.lod_preset = lod_preset;
             // log(&format!("lod_preset: {:?}", self.develop.lod_preset));
@@ -248,8 +452,17 @@ impl App {
     pub fn render(
         &mut self,
     ) {
-        RkWarm::clear(&self.renderer);
-        self.scene_container.scene.render(&self.develop, &self.renderer, &self.timer);
+        if self.renderer.context_watcher.is_lost() { return }
+        if self.capture.is_some() { return self.render_capture() }
+        let (develop, renderer, timer) = (&self.develop, &self.renderer, &self.timer);
+        let scene = &mut self.scene_container.scene;
+        let post_chain = renderer.post_chain.as_ref();
+        let result = post_chain.map_or(Ok(()), |post_chain| post_chain.begin(renderer))
+            .and_then(|_| RkWarm::set_pipeline_state(renderer, &scene.get_pipeline_state()))
+            .and_then(|_| RkWarm::clear(renderer))
+            .and_then(|_| scene.render(develop, renderer, timer))
+            .and_then(|_| post_chain.map_or(Ok(()), |post_chain| post_chain.run(renderer)));
+        if let Err(error) = result { self.record_error(error) }
     }
 
 
@@ -257,9 +470,124 @@ impl App {
 
     // Phase 9: If certain state values have changed, inform the browser.
     // For example, the cursor may need to change to a pointer.
+    // Returns JSON, eg `{"errors":[]}`. Each error is only reported once.
     pub fn report(
-        &self,
+        &mut self,
+    ) -> String {
+        let errors: Vec<String> = self.errors[self.errors_reported..]
+            .iter()
+            .map(|error| error.to_json())
+            .collect();
+        self.errors_reported = self.errors.len();
+        format!(r#"{{"errors":[{}]}}"#, errors.join(","))
+    }
+
+}
+
+
+
+
+// HELPERS
+
+impl App {
+
+    /// Re-runs the Renderer’s ‘cold path’, and rebuilds the Scene, after the
+    /// WebGL context has been lost and then restored.
+    fn restore(
+        &mut self,
+    ) {
+        let result = match self.renderer.restore() {
+            Ok(()) => self.scene_container.scene.restore(&mut self.renderer),
+            Err(error) => Err(error),
+        };
+        if let Some(capture) = self.capture.as_mut() {
+            if let Err(error) = capture.restore(&self.renderer) {
+                self.record_error(error);
+                self.capture = None; // its old RenderTargets went with the context
+            }
+        }
+        if let Err(error) = result {
+            self.record_error(error);
+            self.renderer.release_scene_resources();
+            self.scene_container = SceneContainer::empty(&mut self.renderer);
+        }
+    }
+
+    /// Renders the next captured frame into the capture’s RenderTargets, runs
+    /// any post-processing passes, and keeps the frame as a one-tile still
+    /// image until `take_captured_frame()` is called.
+    fn render_capture(
+        &mut self,
+    ) {
+        let mut capture = match self.capture.take() { Some(capture) => capture, None => return };
+        if capture.frame.is_some() { self.capture = Some(capture); return }
+        let timer = self.timer.clone();
+        let result = StillImage::new(capture.width, capture.height, 1, self.renderer.aspect_ratio)
+            .and_then(|mut still_image| {
+                let tile = still_image.get_tiles(0)[0];
+                self.renderer.clip_tile.set(Some(tile.clip));
+                let pixels = self.render_offscreen(&capture.targets, still_image.get_frame(&tile), &timer);
+                self.renderer.clip_tile.set(None);
+                still_image.paste(&tile, &pixels?);
+                Ok(still_image)
+            });
+        let canvas_bound = RkWarm::bind_render_target(&self.renderer, None);
+        match result.and_then(|still_image| canvas_bound.map(|_| still_image)) {
+            Ok(still_image) => {
+                capture.frame = Some(CapturedFrame::new(capture.frame_index, still_image));
+                capture.frame_index += 1;
+            },
+            Err(error) => self.record_error(error),
+        }
+        self.capture = Some(capture);
+    }
+
+    /// Renders one tile of a still image, and its gutter, into its own pair of
+    /// RenderTargets, and reads back its pixels. The RenderTargets are freed
+    /// even if rendering fails.
+    fn render_still_tile(
+        &mut self,
+        tile: &StillTile,
+        frame: [f32; 4],
+        timer: &Timer,
+    ) -> Result<Vec<u8>, RendererError> {
+        let [width, height] = tile.get_render_size();
+        let targets = PostChain::create_targets(&self.renderer, width as i32, height as i32)?;
+        self.renderer.clip_tile.set(Some(tile.clip));
+        let result = self.render_offscreen(&targets, frame, timer);
+        self.renderer.clip_tile.set(None);
+        PostChain::delete_targets(&self.renderer, &targets);
+        result
+    }
+
+    /// Renders the Scene into `targets[0]`, runs it through any post-processing
+    /// passes, and reads back the finished pixels, bottom row first.
+    fn render_offscreen(
+        &mut self,
+        targets: &[RenderTarget; 2],
+        frame: [f32; 4],
+        timer: &Timer,
+    ) -> Result<Vec<u8>, RendererError> {
+        let (develop, renderer) = (&self.develop, &self.renderer);
+        let scene = &mut self.scene_container.scene;
+        let post_chain = renderer.post_chain.as_ref();
+        RkWarm::bind_render_target(renderer, Some(&targets[0]))
+            .and_then(|_| RkWarm::set_pipeline_state(renderer, &scene.get_pipeline_state()))
+            .and_then(|_| RkWarm::clear(renderer))
+            .and_then(|_| scene.render(develop, renderer, timer))
+            .and_then(|_| post_chain.map_or(Ok(()), |post_chain| post_chain.run_offscreen(renderer, targets, frame)))
+            .and_then(|_| RkTepid::read_pixels(renderer, targets[0].width as u32, targets[0].height as u32))
+    }
+
+    /// Remembers an error, unless an identical one has already been recorded.
+    /// A failing render() would otherwise add the same error on every tick.
+    fn record_error(
+        &mut self,
+        error: RendererError,
     ) {
+        if ! self.errors.contains(&error) {
+            self.errors.push(error);
+        }
     }
 
 }
@@ -0,0 +1,113 @@
+//! Renders frames at an exact frame rate, however fast the browser runs.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+use crate::error::ERROR as E;
+use crate::renderer_webgl::{PostChain,RenderTarget,RendererError,RendererWebGl};
+use super::StillImage;
+
+
+/// #### Steps the App’s Timer by one frame per tick, and keeps each frame.
+///
+/// While capturing, `App::update_state()` ignores `time_in_ms`, so a frame
+/// which takes a second to render still only moves the song on by `1 / fps`.
+/// Each frame is rendered offscreen, at `width` by `height`, and kept until
+/// `App::take_captured_frame()` is called. Until then, the song waits.
+/// Every frame reuses the same RenderTargets, so call `delete()` when done.
+pub struct Capture {
+    pub fps: f32,
+    pub width: u32,
+    pub height: u32,
+    /// Index of the frame which the next tick renders.
+    pub frame_index: u32,
+    /// The most recently rendered frame, if it’s not been taken yet.
+    pub frame: Option<CapturedFrame>,
+    /// The Scene renders into the first, and post-processing passes ping-pong
+    /// between them. See `PostChain::create_targets()`.
+    pub targets: [RenderTarget; 2],
+}
+
+impl Capture {
+
+    pub fn new(
+        r: &RendererWebGl,
+        fps: f32,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, RendererError> {
+        if ! fps.is_finite() || fps <= 0. || width == 0 || height == 0 {
+            return Err(RendererError::new(E::R11911,
+                format!("{}x{} at {} fps", width, height, fps)));
+        }
+        Ok(Self {
+            fps,
+            width,
+            height,
+            frame_index: 0,
+            frame: None,
+            targets: PostChain::create_targets(r, width as i32, height as i32)?,
+        })
+    }
+
+    /// Recreates the RenderTargets after the WebGL context has been restored,
+    /// as the browser freed the old ones.
+    pub fn restore(
+        &mut self,
+        r: &RendererWebGl,
+    ) -> Result<(), RendererError> {
+        self.targets = PostChain::create_targets(r, self.width as i32, self.height as i32)?;
+        Ok(())
+    }
+
+    /// Frees the RenderTargets.
+    pub fn delete(
+        self,
+        r: &RendererWebGl,
+    ) {
+        PostChain::delete_targets(r, &self.targets);
+    }
+
+    /// The song time of the next frame. Calculated from the frame index, rather
+    /// than by adding up steps, so that rounding errors can’t accumulate.
+    pub fn get_time_in_ms(
+        &self,
+    ) -> f32 {
+        self.frame_index as f32 * 1000. / self.fps
+    }
+
+}
+
+
+/// One captured frame, and its position in the sequence.
+#[wasm_bindgen]
+pub struct CapturedFrame {
+    index: u32,
+    image: StillImage,
+}
+
+impl CapturedFrame {
+    pub fn new(
+        index: u32,
+        image: StillImage,
+    ) -> Self {
+        Self { index, image }
+    }
+}
+
+#[wasm_bindgen]
+impl CapturedFrame {
+
+    /// 0 for the first frame after `App::start_capture()`, then 1, 2, 3...
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// RGBA, top row first, four bytes per pixel.
+    pub fn rgba(&self) -> Vec<u8> {
+        self.image.get_rgba().to_vec()
+    }
+
+    pub fn png(&self) -> Vec<u8> {
+        self.image.to_png()
+    }
+
+}
@@ -1,5 +1,11 @@
 mod app;
 pub use app::App;
 
+mod capture;
+pub use capture::{Capture,CapturedFrame};
+
+mod still_image;
+pub use still_image::{StillImage,StillTile};
+
 mod timer;
 pub use timer::Timer;
@@ -0,0 +1,327 @@
+//! Stitches tiles rendered offscreen into one large image, and encodes it as a PNG.
+
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use crate::error::ERROR as E;
+use crate::renderer_webgl::RendererError;
+
+/// The most RGBA bytes a StillImage may hold, eg 11585 x 11585 pixels. WASM
+/// has at most 4 GiB of memory, and `to_png()` needs room for a second copy.
+const MAX_RGBA_BYTES: u64 = 512 * 1024 * 1024;
+
+/// One rectangle of a StillImage, rendered on its own.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct StillTile {
+    /// Left edge, in pixels from the left of the StillImage.
+    pub x: u32,
+    /// Top edge, in pixels from the top of the StillImage.
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Extra pixels rendered beyond each edge, and then cropped off, so that
+    /// post-processing passes read real neighbours at the tile’s edges.
+    pub gutter: u32,
+    /// Sent to `RendererWebGl::clip_tile`, so that this tile’s part of the
+    /// view, and its gutter, fills the whole offscreen framebuffer.
+    pub clip: [f32; 4],
+}
+
+impl StillTile {
+
+    /// The width and height to render at, including the gutter.
+    pub fn get_render_size(
+        &self,
+    ) -> [u32; 2] {
+        [self.width + self.gutter * 2, self.height + self.gutter * 2]
+    }
+
+}
+
+
+/// #### An RGBA image, built up tile by tile.
+///
+/// The image is split into `tiles` columns and `tiles` rows, so a poster far
+/// bigger than the canvas, or than the GPU’s largest framebuffer, can still be
+/// rendered. If its aspect ratio differs from the canvas’s, the view is
+/// widened or narrowed to fit, rather than stretched.
+pub struct StillImage {
+    pub width: u32,
+    pub height: u32,
+    pub tiles: u32,
+    /// `width / height` of the canvas, which the Scene’s projection assumes.
+    canvas_aspect_ratio: f32,
+    /// Top row first, four bytes per pixel.
+    rgba: Vec<u8>,
+}
+
+impl StillImage {
+
+    pub fn new(
+        width: u32,
+        height: u32,
+        tiles: u32,
+        canvas_aspect_ratio: f32,
+    ) -> Result<Self, RendererError> {
+        let rgba_bytes = (width as u64).checked_mul(height as u64)
+            .and_then(|pixels| pixels.checked_mul(4))
+            .filter(|rgba_bytes| *rgba_bytes <= MAX_RGBA_BYTES);
+        let rgba_bytes = match rgba_bytes {
+            Some(rgba_bytes) if width != 0 && height != 0 && tiles != 0
+                && tiles <= width && tiles <= height => rgba_bytes,
+            _ => return Err(RendererError::new(E::R11907,
+                format!("{}x{} in {} tiles per side", width, height, tiles))),
+        };
+        Ok(Self {
+            width,
+            height,
+            tiles,
+            canvas_aspect_ratio,
+            rgba: vec![0; rgba_bytes as usize],
+        })
+    }
+
+    /// Every tile, left to right and then top to bottom. When the size does
+    /// not divide evenly, tiles differ by at most one pixel. Each is rendered
+    /// `gutter` pixels bigger on every side — see `PostChain::gutter_px()`.
+    pub fn get_tiles(
+        &self,
+        gutter: u32,
+    ) -> Vec<StillTile> {
+        // Widen the projection’s x axis from the canvas’s aspect ratio to ours.
+        let aspect_scale = self.canvas_aspect_ratio * self.height as f32 / self.width as f32;
+        let mut still_tiles = vec![];
+        for row in 0..self.tiles {
+            let (y, bottom) = split(self.height, self.tiles, row);
+            for column in 0..self.tiles {
+                let (x, right) = split(self.width, self.tiles, column);
+                let (scale_x, offset_x) = clip_span(x, right, self.width, gutter);
+                // Clip space y points up, but rows are counted down.
+                let (scale_y, offset_y) = clip_span(self.height - bottom, self.height - y, self.height, gutter);
+                still_tiles.push(StillTile {
+                    x,
+                    y,
+                    width: right - x,
+                    height: bottom - y,
+                    gutter,
+                    clip: [scale_x * aspect_scale, scale_y, offset_x, offset_y],
+                });
+            }
+        }
+        still_tiles
+    }
+
+    /// Where a tile’s framebuffer sits in the whole image, for the vignette
+    /// pass: its `[x, y]` from the image’s bottom left, including the gutter,
+    /// then the image’s `[width, height]`, all in pixels.
+    pub fn get_frame(
+        &self,
+        tile: &StillTile,
+    ) -> [f32; 4] {
+        let bottom = self.height - tile.y - tile.height; // rows below the tile
+        [
+            tile.x as f32 - tile.gutter as f32,
+            bottom as f32 - tile.gutter as f32,
+            self.width as f32,
+            self.height as f32,
+        ]
+    }
+
+    /// Copies a tile’s pixels into place, cropping off its gutter. `pixels`
+    /// comes from `RkTepid::read_pixels()`, so its bottom row is first.
+    pub fn paste(
+        &mut self,
+        tile: &StillTile,
+        pixels: &[u8],
+    ) {
+        let [render_width, _] = tile.get_render_size();
+        let gutter_length = (tile.gutter * 4) as usize;
+        let row_length = (tile.width * 4) as usize;
+        let rows = pixels.chunks_exact((render_width * 4) as usize)
+            .skip(tile.gutter as usize)
+            .take(tile.height as usize);
+        for (i, row) in rows.enumerate() {
+            let y = (tile.y + tile.height - 1) as usize - i;
+            let start = (y * self.width as usize + tile.x as usize) * 4;
+            self.rgba[start..start + row_length].copy_from_slice(&row[gutter_length..gutter_length + row_length]);
+        }
+    }
+
+    /// Top row first, four bytes per pixel.
+    pub fn get_rgba(
+        &self,
+    ) -> &[u8] {
+        &self.rgba
+    }
+
+    /// Encodes the image as an 8 bit RGBA PNG.
+    pub fn to_png(
+        &self,
+    ) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        header.extend_from_slice(&[
+            8, // bit depth
+            6, // colour type, RGBA
+            0, // compression method, deflate
+            0, // filter method, adaptive
+            0, // interlace method, none
+        ]);
+
+        // Each row is preceded by its filter type, 0 meaning unfiltered.
+        let row_length = (self.width * 4) as usize;
+        let mut filtered = Vec::with_capacity((row_length + 1) * self.height as usize);
+        for row in self.rgba.chunks_exact(row_length) {
+            filtered.push(0);
+            filtered.extend_from_slice(row);
+        }
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        push_chunk(&mut png, b"IHDR", &header);
+        push_chunk(&mut png, b"IDAT", &compress_to_vec_zlib(&filtered, 6));
+        push_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+}
+
+
+
+
+// HELPERS
+
+/// The start and end, in pixels, of the `index`th of `count` near-equal parts.
+fn split(
+    length: u32,
+    count: u32,
+    index: u32,
+) -> (u32, u32) {
+    let length = length as u64;
+    let count = count as u64;
+    let index = index as u64;
+    ((length * index / count) as u32, (length * (index + 1) / count) as u32)
+}
+
+/// The scale and offset which stretch the pixels `start..end` of `length`, and
+/// `gutter` pixels either side, in clip space, to fill the whole -1 to 1 range.
+fn clip_span(
+    start: u32,
+    end: u32,
+    length: u32,
+    gutter: u32,
+) -> (f32, f32) {
+    let low = (start as f32 - gutter as f32) / length as f32 * 2. - 1.;
+    let high = (end as f32 + gutter as f32) / length as f32 * 2. - 1.;
+    let scale = 2. / (high - low);
+    (scale, - scale * (low + high) / 2.)
+}
+
+/// Appends a PNG chunk: its length, type, data and CRC.
+fn push_chunk(
+    png: &mut Vec<u8>,
+    chunk_type: &[u8; 4],
+    data: &[u8],
+) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let crc_start = png.len();
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[crc_start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// The CRC-32 which PNG chunks end with, over the chunk’s type and data.
+fn crc32(
+    bytes: &[u8],
+) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+    let mut crc = 0xFFFF_FFFF;
+    for byte in bytes {
+        crc = table[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    crc ^ 0xFFFF_FFFF
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_bad_sizes() {
+        for (width, height, tiles) in [(0, 8, 1), (8, 8, 0), (8, 2, 3), (u32::MAX, u32::MAX, 1), (16384, 16384, 4)] {
+            let error = StillImage::new(width, height, tiles, 1.).err().unwrap();
+            assert_eq!(error.code, E::R11907);
+        }
+    }
+
+    #[test]
+    fn splits_uneven_sizes_into_tiles() {
+        let still_image = StillImage::new(10, 7, 3, 10. / 7.).unwrap();
+        let tiles = still_image.get_tiles(0);
+        assert_eq!(tiles.len(), 9);
+        let columns: Vec<(u32, u32)> = tiles[..3].iter().map(|tile| (tile.x, tile.width)).collect();
+        assert_eq!(columns, vec![(0, 3), (3, 3), (6, 4)]);
+        let rows: Vec<(u32, u32)> = tiles.iter().step_by(3).map(|tile| (tile.y, tile.height)).collect();
+        assert_eq!(rows, vec![(0, 2), (2, 2), (4, 3)]);
+        let area: u32 = tiles.iter().map(|tile| tile.width * tile.height).sum();
+        assert_eq!(area, 10 * 7);
+    }
+
+    #[test]
+    fn one_tile_needs_no_clip() {
+        let still_image = StillImage::new(1024, 576, 1, 1024. / 576.).unwrap();
+        assert_eq!(still_image.get_tiles(0)[0].clip, [1., 1., 0., 0.]);
+    }
+
+    #[test]
+    fn pastes_bottom_row_first_pixels_top_row_first() {
+        let mut still_image = StillImage::new(2, 2, 2, 1.).unwrap();
+        let tile = still_image.get_tiles(0)[1]; // top right, 1 x 1
+        still_image.paste(&tile, &[1, 2, 3, 4]);
+        let tile = StillTile { x: 0, y: 0, width: 1, height: 2, gutter: 0, clip: [1., 1., 0., 0.] };
+        still_image.paste(&tile, &[5, 5, 5, 5, 6, 6, 6, 6]);
+        assert_eq!(still_image.get_rgba(), &[
+            6, 6, 6, 6, 1, 2, 3, 4,
+            5, 5, 5, 5, 0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn crops_gutter_when_pasting() {
+        let mut still_image = StillImage::new(2, 2, 1, 1.).unwrap();
+        let tile = still_image.get_tiles(1)[0];
+        assert_eq!(tile.get_render_size(), [4, 4]);
+        assert_eq!(tile.clip, [0.5, 0.5, 0., 0.]);
+        assert_eq!(still_image.get_frame(&tile), [-1., -1., 2., 2.]);
+        // Each pixel is its index, counting from the bottom left of the render.
+        let pixels: Vec<u8> = (0..16).flat_map(|i| [i; 4]).collect();
+        still_image.paste(&tile, &pixels);
+        let expected: Vec<u8> = [9, 10, 5, 6].iter().flat_map(|i| [*i; 4]).collect();
+        assert_eq!(still_image.get_rgba(), &expected[..]);
+    }
+
+    #[test]
+    fn crc32_matches_known_values() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn encodes_png_header_and_chunks() {
+        let png = StillImage::new(3, 2, 1, 1.5).unwrap().to_png();
+        assert_eq!(png[..8], [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        assert_eq!(png[8..16], [0, 0, 0, 13, b'I', b'H', b'D', b'R']);
+        assert_eq!(png[16..29], [0, 0, 0, 3, 0, 0, 0, 2, 8, 6, 0, 0, 0]);
+        assert_eq!(png[29..33], crc32(&png[12..29]).to_be_bytes());
+        assert_eq!(png[png.len() - 12..], [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]);
+    }
+}
@@ -1,5 +1,8 @@
+/// A song lasts 32 bars of 6.4 seconds, which is 2048 tenths of a second.
+const SONG_TENTHS: f32 = 2048.;
+
 /// Used by the App to keep track of time.
-#[derive(Debug)]
+#[derive(Clone,Debug)]
 pub struct Timer {
     pub bar: u8, // lasts 6.4 seconds — there are 32 bars in a song
     // pub bar_continuous_normalised: f32, // like bar, but ranges from 0.0 to (nearly) 1.0
@@ -32,6 +35,14 @@ impl Timer {
         }
     }
 
+    /// How many frames, at `fps`, show a whole 32-bar song. The last frame is
+    /// the one just before the song loops back to its start.
+    pub fn frames_per_song(
+        fps: f32,
+    ) -> u32 {
+        (SONG_TENTHS * fps / 10.).ceil() as u32
+    }
+
     pub fn update(
         &mut self,
         time_in_ms: f32,
@@ -60,4 +71,19 @@ impl Timer {
         self.beat4_continuous_normalised = beat4 / 64.0;
    }
 
-}
\ No newline at end of file
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_frames_per_song() {
+        assert_eq!(Timer::frames_per_song(30.), 6144);
+        assert_eq!(Timer::frames_per_song(60.), 12288);
+        // A partial last frame still counts, so the whole song is shown.
+        assert_eq!(Timer::frames_per_song(29.97), 6138);
+    }
+}
@@ -3,10 +3,12 @@
 use wasm_bindgen::prelude::wasm_bindgen;
 
 #[wasm_bindgen]
-#[derive(Debug,PartialEq)]
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum CameraPreset {
     ChosenByScene,
     OrthographicFront,
     OrthographicLeft,
     OrthographicTop,
+    /// The Scene’s camera and all three orthographic cameras, in four panes.
+    QuadView,
 }
@@ -0,0 +1,22 @@
+//! Used during development to see how a Scene is drawn, rather than how it looks.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug,PartialEq)]
+pub enum DebugPreset {
+    ChosenByScene,
+    NoDebug,
+    /// Colours each surface by the direction it faces, with a spike along
+    /// every vertex normal.
+    Normals,
+    /// A box round each instance, and a box round each whole Furl.
+    BoundingBoxes,
+    /// From white at the nearest surface, to black at the farthest.
+    Depth,
+    /// Blends every fragment additively, with no depth test, so the brightest
+    /// pixels were drawn the most times.
+    Overdraw,
+    /// A different false colour for each instance, from its index.
+    InstanceIds,
+}
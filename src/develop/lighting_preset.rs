@@ -0,0 +1,13 @@
+//! Used during development to compare lit shading with flat vertex colours.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug,PartialEq)]
+pub enum LightingPreset {
+    ChosenByScene,
+    /// Diffuse and specular shading, from the Scene’s lights.
+    Lit,
+    /// Vertex colours only, with no shading.
+    Unlit,
+}
@@ -4,9 +4,15 @@ pub use console::{error,info,log};
 mod camera_preset;
 pub use camera_preset::CameraPreset;
 
+mod debug_preset;
+pub use debug_preset::DebugPreset;
+
 mod guides_preset;
 pub use guides_preset::GuidesPreset;
 
+mod lighting_preset;
+pub use lighting_preset::LightingPreset;
+
 mod lod_preset;
 pub use lod_preset::LodPreset;
 
@@ -15,7 +21,9 @@ pub use wireframe_preset::WireframePreset;
 
 pub struct Develop {
     pub camera_preset: CameraPreset,
+    pub debug_preset: DebugPreset,
     pub guides_preset: GuidesPreset,
+    pub lighting_preset: LightingPreset,
     pub lod_preset: LodPreset,
     pub wireframe_preset: WireframePreset,
 }
@@ -7,6 +7,9 @@ use wasm_bindgen::prelude::wasm_bindgen;
 pub enum WireframePreset {
     ChosenByScene,
     Dots,
+    /// Every edge once, in the vertex colours.
     Lines,
     Solid,
+    /// Solid shading, with every edge drawn over it.
+    SolidAndLines,
 }
@@ -14,7 +14,6 @@ pub const SLOWLY_GET_ERROR_COOL_PATH: bool = true;
 /// Likely to add noticable jank/stalling, so best avoided in production code.  
 /// Probably keep this set to `true` during development, but `false` during
 /// performance optimisation and benchmarking.
-#[allow(dead_code)]
 pub const SLOWLY_GET_ERROR_TEPID_PATH: bool = true;
 
 /// If true, `gl.get_error()` runs after most gl calls during the render phase.  
@@ -28,55 +27,154 @@ pub const SLOWLY_GET_ERROR_WARM_PATH: bool = false;
 
 
 /// Each of the app’s possible errors has a unique code.
-#[derive(Debug)]
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum ERROR {
     /// R11006 RENDERER ERROR: uniform location not found
     R11006,
-    // /// R11279 RENDERER ERROR: failed to create buffer
-    // R11279,
+    /// R11245 RENDERER ERROR: WebGL error while building a shader
+    R11245,
     /// R11331 RENDERER ERROR: error creating program
     R11331,
     /// R11418 RENDERER ERROR: error creating program object
     R11418,
+    /// R11447 RENDERER ERROR: GLSL chunk not found
+    R11447,
     /// R11530 RENDERER ERROR: error compiling vertex shader
     R11530,
+    /// R11563 RENDERER ERROR: GPU resource has already been deleted
+    R11563,
     /// R11572 RENDERER ERROR: error compiling fragment shader
     R11572,
+    /// R11691 RENDERER ERROR: shader does not use that attribute
+    R11691,
+    /// R11714 RENDERER ERROR: shader does not use that uniform
+    R11714,
+    /// R11738 RENDERER ERROR: shader has no attribute at that index
+    R11738,
     /// R11820 RENDERER ERROR: canvas_id not found
     R11820,
     /// R11833 RENDERER ERROR: canvas_id not a canvas element
     R11833,
     /// R11872 RENDERER ERROR: error creating shader
     R11872,
+    /// R11907 RENDERER ERROR: still image size or tile count not valid
+    R11907,
+    /// R11911 RENDERER ERROR: capture frame rate or size not valid
+    R11911,
     /// R11982 RENDERER ERROR: unable to get shader info log
     R11982,
 
+    /// R22214 RKCOLD ERROR: unable to get WebGL context
+    R22214,
+    /// R22388 RKCOLD ERROR: WebGL error during app initialisation
+    R22388,
+    /// R22451 RKCOLD ERROR: unable to listen for context loss
+    R22451,
     /// R22860 RKCOLD ERROR: invalid enum MAX_VERTEX_ATTRIBS
     R22860,
+    /// R22861 RKCOLD ERROR: invalid enum MAX_COMBINED_TEXTURE_IMAGE_UNITS
+    R22861,
     /// R22863 RKCOLD ERROR: MAX_VERTEX_ATTRIBS is too small
     R22863,
     /// R22870 RKCOLD ERROR: unable to get extension
     R22870,
+
+    /// R33245 RKCOOL ERROR: WebGL error during scene initialisation
+    R33245,
+    /// R33279 RKCOOL ERROR: failed to create buffer
+    R33279,
+    /// R33281 RKCOOL ERROR: failed to create texture, framebuffer or renderbuffer
+    R33281,
+    /// R33296 RKCOOL ERROR: framebuffer is incomplete
+    R33296,
+    /// R33302 RKCOOL ERROR: texture too big, or not power-of-two for mipmaps or repeat wrapping
+    R33302,
+    /// R33305 RKCOOL ERROR: texture data does not match its size
+    R33305,
+    /// R33486 RKCOOL ERROR: 32-bit indices need OES_element_index_uint
+    R33486,
+    /// R33512 RKCOOL ERROR: attribute name not recognised
+    R33512,
+    /// R33560 RKCOOL ERROR: attribute location mismatch
+    R33560,
+    /// R33593 RKCOOL ERROR: vertex data does not match the vertex format
+    R33593,
+    /// R33617 RKCOOL ERROR: shader uses an unexpected signature
+    R33617,
+    /// R33724 RKCOOL ERROR: no shader with that name to reload
+    R33724,
+    /// R33748 RKCOOL ERROR: post-processing pass not recognised
+    R33748,
+
+    /// R36101 RKTEPID ERROR: WebGL error during state update
+    R36101,
+    /// R36250 RKTEPID ERROR: uniform kind does not match this setter
+    R36250,
+    /// R36279 RKTEPID ERROR: failed to create buffer
+    R36279,
+    /// R36318 RKTEPID ERROR: unable to read pixels
+    R36318,
+
+    /// R44101 RKWARM ERROR: WebGL error during render
+    R44101,
+    /// R44250 RKWARM ERROR: uniform kind not supported by this setter
+    R44250,
+    /// R44566 RKWARM ERROR: texture unit out of range
+    R44566,
 }
 
 /// Returns a description about an error.
 /// Be aware of the RegExp below `<pre id="wasm-panic"></pre>` in index.html.
-/// Usage: `oooops().expect(e(E::R12345))`
+/// Usage: `oooops().expect(e(E::R12345))`, or for the Renderer, see RendererError.
 pub fn error_to_string(error: ERROR) -> &'static str {
     match error {
         ERROR::R11006 => "R11006 RENDERER ERROR: uniform location not found",
-        // ERROR::R11279 => "R11279 RENDERER ERROR: failed to create buffer",
+        ERROR::R11245 => "R11245 RENDERER ERROR: WebGL error while building a shader",
         ERROR::R11331 => "R11331 RENDERER ERROR: error creating program",
         ERROR::R11418 => "R11418 RENDERER ERROR: error creating program object",
+        ERROR::R11447 => "R11447 RENDERER ERROR: GLSL chunk not found",
         ERROR::R11530 => "R11530 RENDERER ERROR: error compiling vertex shader",
+        ERROR::R11563 => "R11563 RENDERER ERROR: GPU resource has already been deleted",
         ERROR::R11572 => "R11572 RENDERER ERROR: error compiling fragment shader",
+        ERROR::R11691 => "R11691 RENDERER ERROR: shader does not use that attribute",
+        ERROR::R11714 => "R11714 RENDERER ERROR: shader does not use that uniform",
+        ERROR::R11738 => "R11738 RENDERER ERROR: shader has no attribute at that index",
         ERROR::R11820 => "R11820 RENDERER ERROR: canvas_id not found",
-        ERROR::R11833 => "R11820 RENDERER ERROR: canvas_id not a canvas element",
+        ERROR::R11833 => "R11833 RENDERER ERROR: canvas_id not a canvas element",
         ERROR::R11872 => "R11872 RENDERER ERROR: error creating shader",
+        ERROR::R11907 => "R11907 RENDERER ERROR: still image size or tile count not valid",
+        ERROR::R11911 => "R11911 RENDERER ERROR: capture frame rate or size not valid",
         ERROR::R11982 => "R11982 RENDERER ERROR: unable to get shader info log",
 
+        ERROR::R22214 => "R22214 RKCOLD ERROR: unable to get WebGL context",
+        ERROR::R22388 => "R22388 RKCOLD ERROR: WebGL error during app initialisation",
+        ERROR::R22451 => "R22451 RKCOLD ERROR: unable to listen for context loss",
         ERROR::R22860 => "R22860 RKCOLD ERROR: invalid enum MAX_VERTEX_ATTRIBS",
+        ERROR::R22861 => "R22861 RKCOLD ERROR: invalid enum MAX_COMBINED_TEXTURE_IMAGE_UNITS",
         ERROR::R22863 => "R22863 RKCOLD ERROR: MAX_VERTEX_ATTRIBS is too small",
         ERROR::R22870 => "R22870 RKCOLD ERROR: unable to get extension",
+
+        ERROR::R33245 => "R33245 RKCOOL ERROR: WebGL error during scene initialisation",
+        ERROR::R33279 => "R33279 RKCOOL ERROR: failed to create buffer",
+        ERROR::R33281 => "R33281 RKCOOL ERROR: failed to create texture, framebuffer or renderbuffer",
+        ERROR::R33296 => "R33296 RKCOOL ERROR: framebuffer is incomplete",
+        ERROR::R33302 => "R33302 RKCOOL ERROR: texture too big, or not power-of-two for mipmaps or repeat wrapping",
+        ERROR::R33305 => "R33305 RKCOOL ERROR: texture data does not match its size",
+        ERROR::R33486 => "R33486 RKCOOL ERROR: 32-bit indices need OES_element_index_uint",
+        ERROR::R33512 => "R33512 RKCOOL ERROR: attribute name not recognised",
+        ERROR::R33560 => "R33560 RKCOOL ERROR: attribute location mismatch",
+        ERROR::R33593 => "R33593 RKCOOL ERROR: vertex data does not match the vertex format",
+        ERROR::R33617 => "R33617 RKCOOL ERROR: shader uses an unexpected signature",
+        ERROR::R33724 => "R33724 RKCOOL ERROR: no shader with that name to reload",
+        ERROR::R33748 => "R33748 RKCOOL ERROR: post-processing pass not recognised",
+
+        ERROR::R36101 => "R36101 RKTEPID ERROR: WebGL error during state update",
+        ERROR::R36250 => "R36250 RKTEPID ERROR: uniform kind does not match this setter",
+        ERROR::R36279 => "R36279 RKTEPID ERROR: failed to create buffer",
+        ERROR::R36318 => "R36318 RKTEPID ERROR: unable to read pixels",
+
+        ERROR::R44101 => "R44101 RKWARM ERROR: WebGL error during render",
+        ERROR::R44250 => "R44250 RKWARM ERROR: uniform kind not supported by this setter",
+        ERROR::R44566 => "R44566 RKWARM ERROR: texture unit out of range",
     }
 }
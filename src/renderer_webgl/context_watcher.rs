@@ -0,0 +1,84 @@
+//! Listens for the browser dropping, and then restoring, the WebGL context.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use web_sys::{Event,HtmlCanvasElement};
+use crate::error::ERROR as E;
+use super::RendererError;
+
+
+/// #### Listens for the browser dropping, and then restoring, the WebGL context.
+///
+/// The browser may drop the GPU context at any time, eg when a mobile tab is
+/// backgrounded, or a graphics driver resets. Every WebGlBuffer, WebGlProgram
+/// and WebGlUniformLocation then becomes invalid. The event listeners just
+/// set flags — the App checks them during Phase 1, and does the actual work.
+pub struct ContextWatcher {
+    lost: Rc<Cell<bool>>,
+    restored: Rc<Cell<bool>>,
+    // The closures must outlive the canvas’s event listeners, so keep them here.
+    _on_lost: Closure<dyn FnMut(Event)>,
+    _on_restored: Closure<dyn FnMut(Event)>,
+}
+
+impl ContextWatcher {
+
+    pub fn new(
+        canvas: &HtmlCanvasElement,
+    ) -> Result<Self, RendererError> {
+        let lost = Rc::new(Cell::new(false));
+        let restored = Rc::new(Cell::new(false));
+
+        // Calling preventDefault() tells the browser we intend to handle the
+        // ‘webglcontextrestored’ event. Otherwise, it would never be fired.
+        let on_lost = {
+            let lost = lost.clone();
+            Closure::wrap(Box::new(move |event: Event| {
+                event.prevent_default();
+                lost.set(true);
+            }) as Box<dyn FnMut(Event)>)
+        };
+        let on_restored = {
+            let restored = restored.clone();
+            Closure::wrap(Box::new(move |_event: Event| {
+                restored.set(true);
+            }) as Box<dyn FnMut(Event)>)
+        };
+
+        canvas.add_event_listener_with_callback(
+            "webglcontextlost",
+            on_lost.as_ref().unchecked_ref(),
+        ).map_err(|_| RendererError::new(E::R22451, "webglcontextlost".into()))?;
+        canvas.add_event_listener_with_callback(
+            "webglcontextrestored",
+            on_restored.as_ref().unchecked_ref(),
+        ).map_err(|_| RendererError::new(E::R22451, "webglcontextrestored".into()))?;
+
+        Ok(Self {
+            lost,
+            restored,
+            _on_lost: on_lost,
+            _on_restored: on_restored,
+        })
+    }
+
+    /// Returns `true` from ‘webglcontextlost’ until the restore is handled.
+    pub fn is_lost(
+        &self,
+    ) -> bool {
+        self.lost.get()
+    }
+
+    /// Returns `true` once after each ‘webglcontextrestored’ event, and marks
+    /// the context as no longer lost. The caller must then rebuild everything.
+    pub fn take_restored(
+        &self,
+    ) -> bool {
+        if ! self.restored.replace(false) { return false }
+        self.lost.set(false);
+        true
+    }
+
+}
@@ -0,0 +1,92 @@
+//! Uploads Rust slices to WebGL buffers, without copying them first.
+
+use js_sys::{Float32Array,Int16Array,Object,Uint16Array,Uint32Array,Uint8Array};
+use web_sys::WebGlRenderingContext as GL;
+
+
+/// #### A number type which WebGL can read straight out of wasm memory.
+///
+/// Each implementation knows its own byte length, so callers never need to
+/// divide a pointer by 4 or 2 to find where a slice starts.
+pub trait GlElement: Copy {
+    /// Eg 4 for `f32`.
+    const BYTES: usize;
+
+    /// Views `values` in place, as the matching JavaScript typed array.
+    ///
+    /// # Safety
+    /// The view points into wasm memory, which is replaced with a bigger
+    /// ArrayBuffer whenever it grows. So nothing may allocate between creating
+    /// the view and handing it to WebGL. Use `buffer_data()` or
+    /// `buffer_sub_data()`, which guarantee that.
+    unsafe fn view(values: &[Self]) -> Object;
+}
+
+impl GlElement for f32 {
+    const BYTES: usize = 4;
+    unsafe fn view(values: &[Self]) -> Object { Float32Array::view(values).into() }
+}
+
+impl GlElement for i16 {
+    const BYTES: usize = 2;
+    unsafe fn view(values: &[Self]) -> Object { Int16Array::view(values).into() }
+}
+
+impl GlElement for u8 {
+    const BYTES: usize = 1;
+    unsafe fn view(values: &[Self]) -> Object { Uint8Array::view(values).into() }
+}
+
+impl GlElement for u16 {
+    const BYTES: usize = 2;
+    unsafe fn view(values: &[Self]) -> Object { Uint16Array::view(values).into() }
+}
+
+/// Only usable as indices if the OES_element_index_uint extension is enabled.
+impl GlElement for u32 {
+    const BYTES: usize = 4;
+    unsafe fn view(values: &[Self]) -> Object { Uint32Array::view(values).into() }
+}
+
+
+
+/// #### A GlElement which can be used as an index, in an ELEMENT_ARRAY_BUFFER.
+pub trait GlIndex: GlElement {
+    /// The `type` to pass to `drawElements()`, eg `GL::UNSIGNED_SHORT`.
+    const GL_TYPE: u32;
+}
+
+impl GlIndex for u8 { const GL_TYPE: u32 = GL::UNSIGNED_BYTE; }
+impl GlIndex for u16 { const GL_TYPE: u32 = GL::UNSIGNED_SHORT; }
+impl GlIndex for u32 { const GL_TYPE: u32 = GL::UNSIGNED_INT; }
+
+
+
+
+// UPLOADS
+
+/// Writes `values` into the buffer currently bound to `target`, replacing its
+/// contents and size. `usage` is eg `GL::STATIC_DRAW`.
+pub fn buffer_data<T: GlElement>(
+    gl: &GL,
+    target: u32,
+    values: &[T],
+    usage: u32,
+) {
+    // SAFETY: the view is created and used in a single expression, so wasm
+    // memory can’t grow while it exists.
+    unsafe { gl.buffer_data_with_array_buffer_view(target, &T::view(values), usage) }
+}
+
+/// Overwrites part of the buffer currently bound to `target`, starting
+/// `offset` elements (not bytes) in.
+pub fn buffer_sub_data<T: GlElement>(
+    gl: &GL,
+    target: u32,
+    offset: usize,
+    values: &[T],
+) {
+    let byte_offset = (offset * T::BYTES) as i32;
+    // SAFETY: as in `buffer_data()`.
+    unsafe { gl.buffer_sub_data_with_i32_and_array_buffer_view(target, byte_offset, &T::view(values)) }
+}
@@ -0,0 +1,363 @@
+//! Keeps track of every WebGL object the Renderer has created, so it can be freed.
+
+use web_sys::{WebGlBuffer,WebGlFramebuffer,WebGlProgram,WebGlRenderbuffer,WebGlRenderingContext as GL,WebGlTexture};
+use crate::error::ERROR as E;
+use super::RendererError;
+
+
+/// Refers to a WebGLBuffer in `RendererWebGl::resources`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct BufferHandle(usize);
+
+/// An index buffer, and the type of index it holds. Draw calls need both.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct IndexBuffer {
+    pub buffer: BufferHandle,
+    /// How many indices the buffer holds.
+    pub count: usize,
+    /// `GL::UNSIGNED_BYTE`, `GL::UNSIGNED_SHORT` or `GL::UNSIGNED_INT`.
+    pub index_type: u32,
+}
+
+impl IndexBuffer {
+
+    /// Eg 2 for `GL::UNSIGNED_SHORT`.
+    pub fn bytes_per_index(
+        &self,
+    ) -> i32 {
+        match self.index_type {
+            GL::UNSIGNED_BYTE => 1,
+            GL::UNSIGNED_SHORT => 2,
+            _ => 4,
+        }
+    }
+}
+
+/// Refers to a WebGLProgram in `RendererWebGl::resources`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct ProgramHandle(usize);
+
+/// Refers to a WebGLTexture in `RendererWebGl::resources`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct TextureHandle(usize);
+
+/// Refers to a WebGLFramebuffer in `RendererWebGl::resources`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct FramebufferHandle(usize);
+
+/// Refers to a WebGLRenderbuffer in `RendererWebGl::resources`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct RenderbufferHandle(usize);
+
+
+/// Who is responsible for a resource, which decides when it gets freed.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum GpuOwner {
+    /// Lives as long as the Renderer, eg a shader program in the ShaderVariantCache,
+    /// or a PostChain’s render targets.
+    Renderer,
+    /// Freed by `RendererWebGl::release_scene_resources()`, eg a vertex buffer.
+    Scene,
+}
+
+
+/// The WebGL object behind a handle.
+pub enum GpuObject {
+    Buffer(WebGlBuffer),
+    Framebuffer(WebGlFramebuffer),
+    Program(WebGlProgram),
+    Renderbuffer(WebGlRenderbuffer),
+    Texture(WebGlTexture),
+}
+
+struct GpuResource {
+    object: GpuObject,
+    owner: GpuOwner,
+    /// How much VRAM the object’s data takes up. WebGL does not say how big a
+    /// linked program is, so programs are counted as zero bytes. So are
+    /// framebuffers, whose attachments are counted instead.
+    bytes: usize,
+}
+
+
+/// #### Keeps track of every WebGL object the Renderer has created.
+///
+/// WebGL objects are not garbage collected until the whole context is, so a
+/// buffer which is just dropped keeps its VRAM. Each object is registered here
+/// when it’s created, and a typed handle is returned in its place. Slots are
+/// never reused, so a handle to a deleted object fails rather than silently
+/// pointing at a newer one.
+pub struct GpuResources {
+    resources: Vec<Option<GpuResource>>,
+}
+
+impl GpuResources {
+
+    pub fn new() -> Self {
+        Self {
+            resources: vec![],
+        }
+    }
+
+    pub fn add_buffer(
+        &mut self,
+        buffer: WebGlBuffer,
+        owner: GpuOwner,
+        bytes: usize,
+    ) -> BufferHandle {
+        BufferHandle(self.add(GpuObject::Buffer(buffer), owner, bytes))
+    }
+
+    pub fn add_program(
+        &mut self,
+        program: WebGlProgram,
+        owner: GpuOwner,
+    ) -> ProgramHandle {
+        ProgramHandle(self.add(GpuObject::Program(program), owner, 0))
+    }
+
+    pub fn add_texture(
+        &mut self,
+        texture: WebGlTexture,
+        owner: GpuOwner,
+        bytes: usize,
+    ) -> TextureHandle {
+        TextureHandle(self.add(GpuObject::Texture(texture), owner, bytes))
+    }
+
+    pub fn add_framebuffer(
+        &mut self,
+        framebuffer: WebGlFramebuffer,
+        owner: GpuOwner,
+    ) -> FramebufferHandle {
+        FramebufferHandle(self.add(GpuObject::Framebuffer(framebuffer), owner, 0))
+    }
+
+    pub fn add_renderbuffer(
+        &mut self,
+        renderbuffer: WebGlRenderbuffer,
+        owner: GpuOwner,
+        bytes: usize,
+    ) -> RenderbufferHandle {
+        RenderbufferHandle(self.add(GpuObject::Renderbuffer(renderbuffer), owner, bytes))
+    }
+
+    /// Returns the WebGLBuffer behind a handle, or R11563 if it’s been deleted.
+    pub fn buffer(
+        &self,
+        handle: BufferHandle,
+    ) -> Result<WebGlBuffer, RendererError> {
+        match self.get(handle.0) {
+            Some(GpuResource { object: GpuObject::Buffer(buffer), .. }) => Ok(buffer.clone()),
+            _ => Err(RendererError::new(E::R11563, format!("{:?}", handle))),
+        }
+    }
+
+    /// Returns the WebGLTexture behind a handle, or R11563 if it’s been deleted.
+    pub fn texture(
+        &self,
+        handle: TextureHandle,
+    ) -> Result<WebGlTexture, RendererError> {
+        match self.get(handle.0) {
+            Some(GpuResource { object: GpuObject::Texture(texture), .. }) => Ok(texture.clone()),
+            _ => Err(RendererError::new(E::R11563, format!("{:?}", handle))),
+        }
+    }
+
+    /// Returns the WebGLFramebuffer behind a handle, or R11563 if it’s been deleted.
+    pub fn framebuffer(
+        &self,
+        handle: FramebufferHandle,
+    ) -> Result<WebGlFramebuffer, RendererError> {
+        match self.get(handle.0) {
+            Some(GpuResource { object: GpuObject::Framebuffer(framebuffer), .. }) => Ok(framebuffer.clone()),
+            _ => Err(RendererError::new(E::R11563, format!("{:?}", handle))),
+        }
+    }
+
+    /// Records a buffer’s new size, eg after `RkTepid::orphan_buffer_f32()`.
+    pub fn resize_buffer(
+        &mut self,
+        handle: BufferHandle,
+        bytes: usize,
+    ) -> Result<(), RendererError> {
+        match self.resources.get_mut(handle.0) {
+            Some(Some(resource)) => { resource.bytes = bytes; Ok(()) },
+            _ => Err(RendererError::new(E::R11563, format!("{:?}", handle))),
+        }
+    }
+
+    /// Finds the handle of a registered program, eg before it’s relinked.
+    pub fn find_program(
+        &self,
+        program: &WebGlProgram,
+    ) -> Option<ProgramHandle> {
+        self.resources.iter().position(|resource| matches!(resource,
+            Some(GpuResource { object: GpuObject::Program(p), .. }) if p == program))
+            .map(ProgramHandle)
+    }
+
+    /// Points an existing handle at a relinked program. The caller deletes the
+    /// old program, once it’s no longer in use.
+    pub fn replace_program(
+        &mut self,
+        handle: ProgramHandle,
+        program: WebGlProgram,
+    ) -> Result<(), RendererError> {
+        match self.resources.get_mut(handle.0) {
+            Some(Some(resource)) => { resource.object = GpuObject::Program(program); Ok(()) },
+            _ => Err(RendererError::new(E::R11563, format!("{:?}", handle))),
+        }
+    }
+
+    /// Deletes one buffer. Does nothing if it’s already been deleted.
+    pub fn delete_buffer(
+        &mut self,
+        gl: &GL,
+        handle: BufferHandle,
+    ) {
+        self.delete(gl, handle.0);
+    }
+
+    /// Deletes one texture. Does nothing if it’s already been deleted.
+    pub fn delete_texture(
+        &mut self,
+        gl: &GL,
+        handle: TextureHandle,
+    ) {
+        self.delete(gl, handle.0);
+    }
+
+    /// Deletes one framebuffer, but not its attachments.
+    pub fn delete_framebuffer(
+        &mut self,
+        gl: &GL,
+        handle: FramebufferHandle,
+    ) {
+        self.delete(gl, handle.0);
+    }
+
+    /// Deletes one renderbuffer. Does nothing if it’s already been deleted.
+    pub fn delete_renderbuffer(
+        &mut self,
+        gl: &GL,
+        handle: RenderbufferHandle,
+    ) {
+        self.delete(gl, handle.0);
+    }
+
+    /// Deletes every object with the given owner, eg when a Scene is torn down.
+    pub fn delete_owned_by(
+        &mut self,
+        gl: &GL,
+        owner: GpuOwner,
+    ) {
+        for slot in self.resources.iter_mut() {
+            if slot.as_ref().is_some_and(|resource| resource.owner == owner) {
+                if let Some(resource) = slot.take() { delete_object(gl, &resource.object) }
+            }
+        }
+    }
+
+    /// Deletes every object, whoever owns it.
+    pub fn delete_all(
+        &mut self,
+        gl: &GL,
+    ) {
+        for resource in self.resources.iter_mut().filter_map(Option::take) {
+            delete_object(gl, &resource.object);
+        }
+    }
+
+    /// Drops every handle without calling WebGL. Used after the context has been
+    /// lost, because the browser has already freed all of its objects.
+    pub fn forget_all(
+        &mut self,
+    ) {
+        for slot in self.resources.iter_mut() { *slot = None }
+    }
+
+    /// The total size of every live buffer, texture and renderbuffer, in bytes.
+    pub fn live_bytes(
+        &self,
+    ) -> usize {
+        self.resources.iter().flatten().map(|resource| resource.bytes).sum()
+    }
+
+    /// Eg `{"live_bytes":1536,"buffers":3,"programs":2,"textures":0,"framebuffers":0,"renderbuffers":0,"resources":[{"handle":0,"kind":"buffer","owner":"Scene","bytes":512},...]}`
+    pub fn to_json(
+        &self,
+    ) -> String {
+        let mut buffers = 0;
+        let mut programs = 0;
+        let mut textures = 0;
+        let mut framebuffers = 0;
+        let mut renderbuffers = 0;
+        let resources: Vec<String> = self.resources.iter().enumerate()
+            .filter_map(|(handle, slot)| slot.as_ref().map(|resource| (handle, resource)))
+            .map(|(handle, resource)| {
+                let kind = match resource.object {
+                    GpuObject::Buffer(_) => { buffers += 1; "buffer" },
+                    GpuObject::Framebuffer(_) => { framebuffers += 1; "framebuffer" },
+                    GpuObject::Program(_) => { programs += 1; "program" },
+                    GpuObject::Renderbuffer(_) => { renderbuffers += 1; "renderbuffer" },
+                    GpuObject::Texture(_) => { textures += 1; "texture" },
+                };
+                format!(r#"{{"handle":{},"kind":"{}","owner":"{:?}","bytes":{}}}"#,
+                    handle, kind, resource.owner, resource.bytes)
+            })
+            .collect();
+        format!(r#"{{"live_bytes":{},"buffers":{},"programs":{},"textures":{},"framebuffers":{},"renderbuffers":{},"resources":[{}]}}"#,
+            self.live_bytes(), buffers, programs, textures, framebuffers, renderbuffers, resources.join(","))
+    }
+
+}
+
+
+
+
+// HELPERS
+
+impl GpuResources {
+
+    fn add(
+        &mut self,
+        object: GpuObject,
+        owner: GpuOwner,
+        bytes: usize,
+    ) -> usize {
+        self.resources.push(Some(GpuResource { object, owner, bytes }));
+        self.resources.len() - 1
+    }
+
+    fn get(
+        &self,
+        index: usize,
+    ) -> Option<&GpuResource> {
+        self.resources.get(index).and_then(Option::as_ref)
+    }
+
+    fn delete(
+        &mut self,
+        gl: &GL,
+        index: usize,
+    ) {
+        if let Some(resource) = self.resources.get_mut(index).and_then(Option::take) {
+            delete_object(gl, &resource.object);
+        }
+    }
+
+}
+
+fn delete_object(
+    gl: &GL,
+    object: &GpuObject,
+) {
+    match object {
+        GpuObject::Buffer(buffer) => gl.delete_buffer(Some(buffer)),
+        GpuObject::Framebuffer(framebuffer) => gl.delete_framebuffer(Some(framebuffer)),
+        GpuObject::Program(program) => gl.delete_program(Some(program)),
+        GpuObject::Renderbuffer(renderbuffer) => gl.delete_renderbuffer(Some(renderbuffer)),
+        GpuObject::Texture(texture) => gl.delete_texture(Some(texture)),
+    }
+}
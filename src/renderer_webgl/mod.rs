@@ -6,11 +6,32 @@ mod signature;
 pub use signature::*;
 
 mod shader;
-pub use shader::ShaderProgramName;
+pub use shader::{ShaderDiagnostic,ShaderFeatures,ShaderProgramName};
 
 mod renderer;
 pub use renderer::RendererWebGl;
 
+mod renderer_error;
+pub use renderer_error::RendererError;
+
+mod gl_element;
+pub use gl_element::{GlElement,GlIndex};
+
+mod gpu_resources;
+pub use gpu_resources::{BufferHandle,FramebufferHandle,GpuOwner,GpuResources,IndexBuffer,RenderbufferHandle,TextureHandle};
+
+mod context_watcher;
+pub use context_watcher::ContextWatcher;
+
+mod pipeline_state;
+pub use pipeline_state::{BlendMode,CullFace,DepthTest,PipelineState};
+
+mod post_chain;
+pub use post_chain::{PostChain,PostPass};
+
+mod render_target;
+pub use render_target::RenderTarget;
+
 mod rk_cold;
 pub use rk_cold::RkCold;
 
@@ -23,3 +44,14 @@ pub use rk_tepid::RkTepid;
 mod rk_warm;
 pub use rk_warm::RkWarm;
 
+mod shader_variant_cache;
+pub use shader_variant_cache::ShaderVariantCache;
+
+mod texture_settings;
+pub use texture_settings::TextureSettings;
+
+mod uniform_cache;
+pub use uniform_cache::UniformCache;
+
+mod vertex_format;
+pub use vertex_format::{VertexColumn,VertexFormat};
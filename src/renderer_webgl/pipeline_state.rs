@@ -0,0 +1,107 @@
+//! Fixed-function WebGL state, which a Scene declares rather than hard-codes.
+
+use web_sys::WebGlRenderingContext as GL;
+
+
+/// How incoming fragments are combined with the colour already drawn.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum BlendMode {
+    /// No blending. The fastest, and right for solid geometry.
+    Opaque,
+    /// Ordinary transparency, for colours which are not premultiplied.
+    #[allow(dead_code)] // not used by any Scene yet
+    Alpha,
+    /// Adds to what’s already drawn, so overlaps glow, eg for guides.
+    Additive,
+    /// Transparency for colours already multiplied by their alpha.
+    #[allow(dead_code)] // not used by any Scene yet
+    Premultiplied,
+}
+
+impl BlendMode {
+    /// The `gl.blendFunc()` source and destination factors.
+    pub fn to_gl_factors(
+        self,
+    ) -> (u32, u32) {
+        match self {
+            BlendMode::Opaque => (GL::ONE, GL::ZERO),
+            BlendMode::Alpha => (GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA),
+            BlendMode::Additive => (GL::SRC_ALPHA, GL::ONE),
+            BlendMode::Premultiplied => (GL::ONE, GL::ONE_MINUS_SRC_ALPHA),
+        }
+    }
+}
+
+
+/// Which triangles are skipped, depending on which way they face.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum CullFace {
+    /// Draw every triangle.
+    Off,
+    /// Skip triangles wound clockwise on screen.
+    Back,
+    /// Skip triangles wound anticlockwise on screen.
+    #[allow(dead_code)] // not used by any Scene yet
+    Front,
+}
+
+
+/// Whether, and how, fragments are tested against the depth buffer.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum DepthTest {
+    /// Draw every fragment, whatever is in front of it.
+    Off,
+    #[allow(dead_code)] // not used by any Scene yet
+    Less,
+    LessOrEqual,
+    #[allow(dead_code)] // not used by any Scene yet
+    Always,
+}
+
+impl DepthTest {
+    /// The `gl.depthFunc()` constant. Meaningless for `DepthTest::Off`.
+    pub fn to_gl(
+        self,
+    ) -> u32 {
+        match self {
+            DepthTest::Off | DepthTest::Always => GL::ALWAYS,
+            DepthTest::Less => GL::LESS,
+            DepthTest::LessOrEqual => GL::LEQUAL,
+        }
+    }
+}
+
+
+/// #### The fixed-function state which a Scene renders with.
+///
+/// Each Scene declares one with `Scene::get_pipeline_state()`, and the App
+/// applies it before clearing. Individual draws can override parts of it, eg:
+/// `RkWarm::set_pipeline_state(r, &PipelineState { blend_mode: BlendMode::Additive, ..state })`
+/// The Renderer remembers the state WebGL is in, so only changes are sent.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct PipelineState {
+    /// RGBA, used by `RkWarm::clear()`.
+    pub clear_color: [f32; 4],
+    pub depth_test: DepthTest,
+    pub blend_mode: BlendMode,
+    pub cull_face: CullFace,
+    /// `[x, y, width, height]` in pixels, from the bottom left of the bound
+    /// framebuffer. Nothing outside it is drawn or cleared. `None` for no scissoring.
+    pub scissor: Option<[i32; 4]>,
+    /// Which of the red, green, blue and alpha channels can be written to.
+    pub color_mask: [bool; 4],
+}
+
+/// A dark purple background, depth testing, and no blending or culling.
+impl Default for PipelineState {
+    fn default() -> Self {
+        Self {
+            clear_color: [0.1, 0.05, 0.15, 1.0],
+            depth_test: DepthTest::LessOrEqual,
+            blend_mode: BlendMode::Opaque,
+            cull_face: CullFace::Off,
+            scissor: None,
+            color_mask: [true, true, true, true],
+        }
+    }
+}
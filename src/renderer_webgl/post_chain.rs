@@ -0,0 +1,286 @@
+//! Full-screen post-processing passes, run after the Scene has rendered.
+
+use web_sys::WebGlRenderingContext as GL;
+use crate::error::ERROR as E;
+use super::{
+    AttributeName,
+    BlendMode,
+    BufferHandle,
+    CullFace,
+    DepthTest,
+    GpuOwner,
+    PipelineState,
+    RenderTarget,
+    RendererError,
+    RendererWebGl,
+    RkCool,
+    RkTepid,
+    RkWarm,
+    ShaderFeatures,
+    ShaderProgramName,
+    UniformName,
+};
+
+
+/// One full-screen pass, and the settings sent to its `u_params` uniform.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum PostPass {
+    /// Adds a glow around the parts of the image brighter than `threshold`.
+    Bloom { threshold: f32, intensity: f32, radius_px: f32 },
+    /// Fast approximate anti-aliasing. Best run last, on the finished image.
+    Fxaa { reduce_min: f32, reduce_mul: f32, span_max_px: f32 },
+    /// Colour grading. `exposure` is in stops, and a negative `warmth` is cooler.
+    Grade { exposure: f32, contrast: f32, saturation: f32, warmth: f32 },
+    /// Darkens the edges of the image, beyond `radius` screen heights from the centre.
+    Vignette { strength: f32, radius: f32, softness: f32 },
+}
+
+impl PostPass {
+
+    /// Eg "bloom" becomes `PostPass::Bloom`, with its default settings.
+    pub fn from_name(
+        name: &str,
+    ) -> Result<Self, RendererError> {
+        match name {
+            "bloom" => Ok(PostPass::Bloom { threshold: 0.7, intensity: 0.8, radius_px: 6. }),
+            "fxaa" => Ok(PostPass::Fxaa { reduce_min: 1. / 128., reduce_mul: 1. / 8., span_max_px: 8. }),
+            "grade" => Ok(PostPass::Grade { exposure: 0.1, contrast: 1.08, saturation: 1.15, warmth: 0.2 }),
+            "vignette" => Ok(PostPass::Vignette { strength: 0.45, radius: 0.85, softness: 0.5 }),
+            _ => Err(RendererError::new(E::R33748,
+                format!("\"{}\" — try bloom, fxaa, grade or vignette", name))),
+        }
+    }
+
+    /// Eg "bloom, grade, fxaa" becomes three PostPasses, in that order. An
+    /// empty string becomes no passes at all.
+    pub fn from_names(
+        names: &str,
+    ) -> Result<Vec<Self>, RendererError> {
+        names.split(',')
+            .map(str::trim)
+            .filter(|name| ! name.is_empty())
+            .map(Self::from_name)
+            .collect()
+    }
+
+    /// How many pixels away from each pixel the pass reads. A tile of a still
+    /// image is rendered this much bigger on each side, so the pass never
+    /// reads past the edge of the tile.
+    fn reach_px(
+        &self,
+    ) -> u32 {
+        match *self {
+            PostPass::Bloom { radius_px, .. } => radius_px.ceil() as u32,
+            PostPass::Fxaa { span_max_px, .. } => span_max_px.ceil() as u32 + 1,
+            PostPass::Grade { .. } => 0,
+            PostPass::Vignette { .. } => 0,
+        }
+    }
+
+    fn program_name(
+        &self,
+    ) -> ShaderProgramName {
+        match self {
+            PostPass::Bloom { .. } => ShaderProgramName::PostBloom,
+            PostPass::Fxaa { .. } => ShaderProgramName::PostFxaa,
+            PostPass::Grade { .. } => ShaderProgramName::PostGrade,
+            PostPass::Vignette { .. } => ShaderProgramName::PostVignette,
+        }
+    }
+
+    /// The value of the pass’s `u_params` uniform. See the comment beside it in
+    /// each ‘.frag’ file.
+    fn params(
+        &self,
+    ) -> [f32; 4] {
+        match *self {
+            PostPass::Bloom { threshold, intensity, radius_px } => [threshold, intensity, radius_px, 0.],
+            PostPass::Fxaa { reduce_min, reduce_mul, span_max_px } => [reduce_min, reduce_mul, span_max_px, 0.],
+            PostPass::Grade { exposure, contrast, saturation, warmth } => [exposure, contrast, saturation, warmth],
+            PostPass::Vignette { strength, radius, softness } => [strength, radius, softness, 0.],
+        }
+    }
+}
+
+
+/// #### Renders the Scene offscreen, then runs it through PostPasses.
+///
+/// `begin()` points the Scene’s draw calls at `targets[0]`. `run()` then
+/// draws a full-screen quad once per pass, each pass reading the texture which
+/// the previous one wrote. The passes ping-pong between the two targets, and
+/// the last one draws to the canvas. `run_offscreen()` does the same with
+/// other targets, eg for one tile of a still image.
+/// Everything here is owned by the Renderer, so the chain outlives Scenes.
+pub struct PostChain {
+    pub passes: Vec<PostPass>,
+    /// Index in `RendererWebGl::shaders` of each pass’s Shader.
+    shader_indices: Vec<usize>,
+    /// Two triangles, as a strip, covering the whole viewport.
+    quad: BufferHandle,
+    /// The first has a depth buffer, for the Scene. The second needs none.
+    targets: [RenderTarget; 2],
+}
+
+impl PostChain {
+
+    /// Compiles the passes’ Shaders, if they’ve not been already, and creates
+    /// two render targets the size of the canvas. `passes` must not be empty —
+    /// `RendererWebGl::set_post_passes()` takes care of that.
+    pub fn new(
+        r: &mut RendererWebGl,
+        passes: Vec<PostPass>,
+    ) -> Result<Self, RendererError> {
+        let scene_shader = r.current_shader.get();
+        let width = r.gl.drawing_buffer_width();
+        let height = r.gl.drawing_buffer_height();
+
+        // Each pass reads texture unit 0. Its resolution depends on the
+        // targets, so is set by `run_passes()`.
+        let mut shader_indices = vec![];
+        for pass in passes.iter() {
+            let shader_index = RkCool::add_shader(r, pass.program_name(), ShaderFeatures::NONE)?;
+            RkCool::use_shader(r, shader_index)?;
+            RkTepid::set_uniform_sampler2d(r, shader_index, UniformName::Source, 0)?;
+            shader_indices.push(shader_index);
+        }
+
+        // The quad’s attribute location is used by no Scene, so it only needs
+        // pointing at the quad once.
+        let quad = RkCool::create_buffer_owned_by(r, GL::ARRAY_BUFFER, &[
+            -1., -1.,
+             1., -1.,
+            -1.,  1.,
+             1.,  1.,
+        ], GpuOwner::Renderer)?;
+        RkCool::enable_attribute(r, AttributeName::Corner)?;
+        RkCool::bind_buffer(r, quad)?;
+        RkCool::use_attribute(r, AttributeName::Corner, 2)?;
+
+        let targets = Self::create_targets(r, width, height)?;
+
+        // Leave the Scene’s Shader in use, as it was.
+        if let Some(shader_index) = scene_shader { RkCool::use_shader(r, shader_index)? }
+
+        Ok(Self {
+            passes,
+            shader_indices,
+            quad,
+            targets,
+        })
+    }
+
+    /// Creates a pair of render targets to ping-pong between. The first has a
+    /// depth buffer, for the Scene. The second needs none.
+    pub fn create_targets(
+        r: &RendererWebGl,
+        width: i32,
+        height: i32,
+    ) -> Result<[RenderTarget; 2], RendererError> {
+        let scene_target = RkCool::create_render_target(r, width, height, true, GpuOwner::Renderer)?;
+        match RkCool::create_render_target(r, width, height, false, GpuOwner::Renderer) {
+            Ok(pass_target) => Ok([scene_target, pass_target]),
+            Err(error) => { RkTepid::delete_render_target(r, &scene_target); Err(error) },
+        }
+    }
+
+    /// Frees both of a pair of render targets from `create_targets()`.
+    pub fn delete_targets(
+        r: &RendererWebGl,
+        targets: &[RenderTarget; 2],
+    ) {
+        for target in targets.iter() { RkTepid::delete_render_target(r, target) }
+    }
+
+    /// Frees the quad and both render targets. The Shaders are kept, in case
+    /// a later PostChain uses the same passes.
+    pub fn delete(
+        self,
+        r: &RendererWebGl,
+    ) {
+        RkTepid::delete_buffer(r, self.quad);
+        Self::delete_targets(r, &self.targets);
+    }
+
+    /// How many pixels each tile of a still image must be rendered beyond its
+    /// edges, so that the passes read real neighbours there.
+    pub fn gutter_px(
+        &self,
+    ) -> u32 {
+        self.passes.iter().map(PostPass::reach_px).sum()
+    }
+
+    /// Call before `RkWarm::clear()`, so the Scene renders offscreen.
+    pub fn begin(
+        &self,
+        r: &RendererWebGl,
+    ) -> Result<(), RendererError> {
+        RkWarm::bind_render_target(r, Some(&self.targets[0]))
+    }
+
+    /// Call after the Scene has rendered. Runs every pass, and leaves the
+    /// canvas bound, and the Scene’s Shader and PipelineState in use.
+    pub fn run(
+        &self,
+        r: &RendererWebGl,
+    ) -> Result<(), RendererError> {
+        let [width, height] = [self.targets[0].width as f32, self.targets[0].height as f32];
+        self.run_passes(r, &self.targets, None, [0., 0., width, height])
+    }
+
+    /// Like `run()`, but for a Scene rendered into `targets[0]` of a pair from
+    /// `create_targets()`. The result is left in the other target, which stays
+    /// bound so that its pixels can be read back. `frame` is the targets’ `[x,
+    /// y]` in the whole image, from its bottom left, then the image’s `[width,
+    /// height]`, all in pixels.
+    pub fn run_offscreen(
+        &self,
+        r: &RendererWebGl,
+        targets: &[RenderTarget; 2],
+        frame: [f32; 4],
+    ) -> Result<(), RendererError> {
+        self.run_passes(r, targets, Some(&targets[self.passes.len() % 2]), frame)
+    }
+
+    /// Runs every pass, reading the Scene from `targets[0]`, ping-ponging
+    /// between the targets, and drawing the last pass to `last_destination`.
+    fn run_passes(
+        &self,
+        r: &RendererWebGl,
+        targets: &[RenderTarget; 2],
+        last_destination: Option<&RenderTarget>,
+        frame: [f32; 4],
+    ) -> Result<(), RendererError> {
+        let scene_shader = r.current_shader.get();
+        let scene_pipeline_state = r.pipeline_state.get();
+
+        // A full-screen quad covers every pixel, so depth testing only wastes
+        // time. Each pass replaces the whole image, so nothing is blended.
+        RkWarm::set_pipeline_state(r, &PipelineState {
+            depth_test: DepthTest::Off,
+            blend_mode: BlendMode::Opaque,
+            cull_face: CullFace::Off,
+            scissor: None,
+            color_mask: [true, true, true, true],
+            ..scene_pipeline_state
+        })?;
+        // The resolution converts gl_FragCoord to texture coordinates.
+        let resolution = [targets[0].width as f32, targets[0].height as f32];
+        let last = self.passes.len() - 1;
+        for (i, (pass, shader_index)) in self.passes.iter().zip(self.shader_indices.iter()).enumerate() {
+            let destination = if i == last { last_destination } else { Some(&targets[(i + 1) % 2]) };
+            RkWarm::bind_render_target(r, destination)?;
+            RkWarm::use_shader(r, *shader_index)?;
+            RkWarm::set_uniform_vec4_f32(r, *shader_index, UniformName::PostParams, pass.params())?;
+            RkWarm::set_uniform_vec2_f32(r, *shader_index, UniformName::Resolution, resolution)?;
+            if let PostPass::Vignette { .. } = pass {
+                RkWarm::set_uniform_vec4_f32(r, *shader_index, UniformName::PostFrame, frame)?;
+            }
+            RkWarm::bind_texture(r, 0, targets[i % 2].color)?;
+            RkWarm::draw(r, GL::TRIANGLE_STRIP, 0, 4)?;
+        }
+        RkWarm::set_pipeline_state(r, &scene_pipeline_state)?;
+
+        if let Some(shader_index) = scene_shader { RkWarm::use_shader(r, shader_index)? }
+        Ok(())
+    }
+}
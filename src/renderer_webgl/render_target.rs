@@ -0,0 +1,21 @@
+//! An offscreen framebuffer, which can be drawn into and then sampled.
+
+use super::{FramebufferHandle,RenderbufferHandle,TextureHandle};
+
+
+/// #### An offscreen framebuffer, with a colour texture and maybe a depth buffer.
+///
+/// Created by `RkCool::create_render_target()`, and drawn into after
+/// `RkWarm::bind_render_target()`. Its colour attachment is a texture, so a
+/// later draw can sample what was rendered, eg in a post-processing pass.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct RenderTarget {
+    pub framebuffer: FramebufferHandle,
+    /// RGBA, 8 bits per channel, linearly filtered and clamped to its edges.
+    pub color: TextureHandle,
+    /// A 16 bit depth buffer, if the target was created with one. Full-screen
+    /// passes don’t need depth testing, so can save the VRAM.
+    pub depth: Option<RenderbufferHandle>,
+    pub width: i32,
+    pub height: i32,
+}
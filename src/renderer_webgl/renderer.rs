@@ -1,18 +1,56 @@
+use std::cell::{Cell,RefCell};
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlCanvasElement,WebGlRenderingContext as GL,window};
-use crate::error::{ERROR as E,error_to_string as e};
+use crate::error::ERROR as E;
+use super::{ContextWatcher,GpuOwner,GpuResources,PipelineState,PostChain,PostPass,RendererError,ShaderVariantCache,TextureHandle,UniformName};
 use super::rk_cold::{AngleInstancedArrays,RkCold};
-use super::shader::ShaderProgram;
+use super::shader::{ShaderProgram,ShaderProgramName};
+use super::uniform_cache::{SkippedCalls,UniformCache};
 
 
 pub struct RendererWebGl {
-    _canvas: HtmlCanvasElement, // maybe not needed?
+    /// The texture unit which `gl.bindTexture()` currently affects, eg 0 for
+    /// `GL::TEXTURE0`.
+    pub active_texture_unit: Cell<u32>,
+    canvas: HtmlCanvasElement,
     pub aspect_ratio: f32,
     pub canvas_extent_horizontal: f32,
     pub canvas_extent_vertical: f32,
+    /// While one tile of a still image renders, maps the whole image’s clip
+    /// space to the tile’s, as `[scale_x, scale_y, offset_x, offset_y]`.
+    pub clip_tile: Cell<Option<[f32; 4]>>,
+    /// Notices when the browser drops or restores the WebGL context.
+    pub context_watcher: ContextWatcher,
+    /// Index of the Shader whose program WebGL is currently using, if known.
+    pub current_shader: Cell<Option<usize>>,
     pub ext_instanced_arrays: AngleInstancedArrays,
+    /// Width and height of the canvas or RenderTarget being drawn into.
+    pub framebuffer_size: Cell<[i32; 2]>,
     pub gl: GL,
-    pub shaders: Vec<Box<dyn ShaderProgram>>,
+    /// Whether `u32` index buffers can be used, via `OES_element_index_uint`.
+    pub has_element_index_uint: bool,
+    /// The fixed-function state WebGL is currently in.
+    pub pipeline_state: Cell<PipelineState>,
+    /// Post-processing passes run after the Scene renders, if any.
+    pub post_chain: Option<PostChain>,
+    /// The GLSL each ShaderProgramName was last reloaded with, as (name, vert,
+    /// frag). Outlives the context, so reloads survive `restore()`.
+    pub reloaded_sources: Vec<(ShaderProgramName, String, String)>,
+    /// Every buffer and program created so far, so they can be freed later.
+    pub resources: RefCell<GpuResources>,
+    /// The Shaders which the current Scene passed to
+    /// `RkCool::confirm_signature_locations()`, so they share attribute locations.
+    pub scene_shaders: RefCell<Vec<usize>>,
+    pub shaders: Vec<ShaderProgram>,
+    /// Which (ShaderProgramName, ShaderFeatures) pairs are in `shaders`.
+    pub shader_variants: ShaderVariantCache,
+    /// Counts redundant WebGL calls which were avoided, for benchmarking.
+    pub skipped_calls: Cell<SkippedCalls>,
+    /// The texture bound to each texture unit, if known. Its length is the
+    /// number of units the device has.
+    pub texture_units: RefCell<Vec<Option<TextureHandle>>>,
+    /// One UniformCache per Shader, so `uniform_caches[i]` matches `shaders[i]`.
+    pub uniform_caches: RefCell<Vec<UniformCache>>,
 }
 
 impl RendererWebGl {
@@ -20,31 +58,218 @@ impl RendererWebGl {
         canvas_extent_horizontal: f32,
         canvas_extent_vertical: f32,
         canvas_id: String,
-    ) -> Self {
+    ) -> Result<Self, RendererError> {
     
         // Initialise the canvas.
         let document = window().unwrap().document().unwrap();
         let canvas: HtmlCanvasElement =
             document.get_element_by_id(&canvas_id)
-                .expect(e(E::R11820))
+                .ok_or_else(|| RendererError::new(E::R11820, canvas_id.clone()))?
                 .dyn_into::<HtmlCanvasElement>() // `dyn_into` is provided by JsCast
-                .expect(e(E::R11833));
+                .map_err(|_| RendererError::new(E::R11833, canvas_id.clone()))?;
 
         // Run the ‘cold path’ — operations which only need to be executed once.
-        let gl = RkCold::init_context(&canvas);
-        RkCold::check_max_vertex_attributes(&gl, 16); // panic if it fails
-        let ext_instanced_arrays = RkCold::init_extensions(&gl);
-        RkCold::init_pipeline(&gl);
+        let gl = RkCold::init_context(&canvas)?;
+        RkCold::check_max_vertex_attributes(&gl, 16)?;
+        let ext_instanced_arrays = RkCold::init_extensions(&gl)?;
+        let has_element_index_uint = RkCold::init_element_index_uint(&gl)?;
+        let max_texture_units = RkCold::get_max_texture_units(&gl)?;
+        RkCold::init_pipeline(&gl, &PipelineState::default())?;
+        let context_watcher = ContextWatcher::new(&canvas)?;
 
-        Self {
-            _canvas: canvas,
+        Ok(Self {
+            active_texture_unit: Cell::new(0),
+            canvas,
             aspect_ratio: canvas_extent_horizontal / canvas_extent_vertical,
             canvas_extent_horizontal,
             canvas_extent_vertical,
+            clip_tile: Cell::new(None),
+            context_watcher,
+            current_shader: Cell::new(None),
             ext_instanced_arrays,
+            framebuffer_size: Cell::new([gl.drawing_buffer_width(), gl.drawing_buffer_height()]),
             gl,
+            has_element_index_uint,
+            pipeline_state: Cell::new(PipelineState::default()),
+            post_chain: None,
+            reloaded_sources: vec![],
+            resources: RefCell::new(GpuResources::new()),
+            scene_shaders: RefCell::new(vec![]),
             shaders: vec![],
+            shader_variants: ShaderVariantCache::new(),
+            skipped_calls: Cell::new(SkippedCalls::default()),
+            texture_units: RefCell::new(vec![None; max_texture_units]),
+            uniform_caches: RefCell::new(vec![]),
+        })
+    }
+
+    /// Re-runs the ‘cold path’ after the WebGL context has been restored.
+    /// Every Shader is forgotten, so the Scene must add its Shaders again.
+    /// They are relinked from any reloaded GLSL, not the embedded sources.
+    /// The PostChain is rebuilt with the same passes.
+    pub fn restore(
+        &mut self,
+    ) -> Result<(), RendererError> {
+        let post_passes = self.post_chain.take().map(|post_chain| post_chain.passes);
+        self.gl = RkCold::init_context(&self.canvas)?;
+        RkCold::check_max_vertex_attributes(&self.gl, 16)?;
+        self.ext_instanced_arrays = RkCold::init_extensions(&self.gl)?;
+        self.has_element_index_uint = RkCold::init_element_index_uint(&self.gl)?;
+        let max_texture_units = RkCold::get_max_texture_units(&self.gl)?;
+        RkCold::init_pipeline(&self.gl, &PipelineState::default())?;
+        self.pipeline_state.set(PipelineState::default());
+
+        // The browser freed every WebGL object when the context was lost.
+        self.resources.borrow_mut().forget_all();
+        self.shaders.clear();
+        self.scene_shaders.borrow_mut().clear();
+        self.shader_variants = ShaderVariantCache::new();
+        self.uniform_caches.borrow_mut().clear();
+        self.current_shader.set(None);
+        self.texture_units.replace(vec![None; max_texture_units]);
+        self.active_texture_unit.set(0);
+        self.framebuffer_size.set([self.gl.drawing_buffer_width(), self.gl.drawing_buffer_height()]);
+
+        if let Some(post_passes) = post_passes {
+            self.post_chain = Some(PostChain::new(self, post_passes)?);
+        }
+        Ok(())
+    }
+
+    /// Replaces the PostChain, freeing the old one’s render targets. No passes
+    /// means no PostChain, so the Scene renders straight to the canvas.
+    pub fn set_post_passes(
+        &mut self,
+        post_passes: Vec<PostPass>,
+    ) -> Result<(), RendererError> {
+        if let Some(post_chain) = self.post_chain.take() { post_chain.delete(self) }
+        if ! post_passes.is_empty() {
+            self.post_chain = Some(PostChain::new(self, post_passes)?);
+        }
+        Ok(())
+    }
+
+    /// Frees every buffer the Scene created, eg before it’s replaced by another.
+    /// Shader programs are kept, so the next Scene can reuse cached variants.
+    pub fn release_scene_resources(
+        &self,
+    ) {
+        self.resources.borrow_mut().delete_owned_by(&self.gl, GpuOwner::Scene);
+        self.scene_shaders.borrow_mut().clear();
+    }
+
+    /// The vert/frag pair which `name` was last reloaded with, or its default.
+    pub fn get_shader_sources(
+        &self,
+        name: ShaderProgramName,
+    ) -> (&str, &str) {
+        self.reloaded_sources.iter()
+            .find(|(reloaded_name, _, _)| *reloaded_name == name)
+            .map_or_else(|| name.default_sources(), |(_, vert, frag)| (vert, frag))
+    }
+
+    /// Returns `true` if the Shader at `shader_index` is already in use, so
+    /// `gl.useProgram()` can be skipped. Otherwise, records it as in use.
+    pub fn switch_current_shader(
+        &self,
+        shader_index: usize,
+    ) -> bool {
+        if self.current_shader.get() == Some(shader_index) {
+            let mut skipped_calls = self.skipped_calls.get();
+            skipped_calls.use_program += 1;
+            self.skipped_calls.set(skipped_calls);
+            return false;
+        }
+        self.current_shader.set(Some(shader_index));
+        true
+    }
+
+    /// Returns the previous PipelineState if `pipeline_state` differs from it,
+    /// and records the new one. Returns `None` if nothing would change.
+    pub fn switch_pipeline_state(
+        &self,
+        pipeline_state: &PipelineState,
+    ) -> Option<PipelineState> {
+        let previous = self.pipeline_state.get();
+        if previous == *pipeline_state {
+            let mut skipped_calls = self.skipped_calls.get();
+            skipped_calls.pipeline_state += 1;
+            self.skipped_calls.set(skipped_calls);
+            return None;
+        }
+        self.pipeline_state.set(*pipeline_state);
+        Some(previous)
+    }
+
+    /// Returns `true` if `texture` is not already bound to texture unit `unit`,
+    /// so `gl.bindTexture()` needs to be called. Otherwise, records it as bound.
+    /// Returns R44566 if the device has no such unit.
+    pub fn switch_texture_binding(
+        &self,
+        unit: u32,
+        texture: TextureHandle,
+    ) -> Result<bool, RendererError> {
+        let mut texture_units = self.texture_units.borrow_mut();
+        let max_texture_units = texture_units.len();
+        let bound = texture_units.get_mut(unit as usize)
+            .ok_or_else(|| RendererError::new(E::R44566,
+                format!("unit {} of {}", unit, max_texture_units)))?;
+        if *bound == Some(texture) {
+            let mut skipped_calls = self.skipped_calls.get();
+            skipped_calls.bind_texture += 1;
+            self.skipped_calls.set(skipped_calls);
+            return Ok(false);
         }
+        *bound = Some(texture);
+        Ok(true)
+    }
+
+    /// Records that `gl.bindTexture()` has bound `texture`, or nothing, to the
+    /// active texture unit, eg while a texture is being created.
+    pub fn record_texture_binding(
+        &self,
+        texture: Option<TextureHandle>,
+    ) {
+        self.texture_units.borrow_mut()[self.active_texture_unit.get() as usize] = texture;
     }
 
+    /// Returns `true` if the Shader at `shader_index` already has this uniform
+    /// value, so the `gl.uniform*()` call can be skipped. Counts the skip.
+    pub fn uniform_is_cached(
+        &self,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: &[f32],
+    ) -> bool {
+        let cached = self.uniform_caches.borrow()[shader_index]
+            .contains(uniform_name, value);
+        if cached {
+            let mut skipped_calls = self.skipped_calls.get();
+            skipped_calls.uniform += 1;
+            self.skipped_calls.set(skipped_calls);
+        }
+        cached
+    }
+
+    /// Records a uniform value once `gl.uniform*()` has sent it. Called only
+    /// after the call succeeds, so a failed call is retried next time.
+    pub fn cache_uniform(
+        &self,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: &[f32],
+    ) {
+        self.uniform_caches.borrow_mut()[shader_index].update(uniform_name, value);
+    }
+
+}
+
+/// Frees every WebGL object, eg when JavaScript calls `app.free()`. Otherwise
+/// they would hold on to their VRAM until the page is closed.
+impl Drop for RendererWebGl {
+    fn drop(
+        &mut self,
+    ) {
+        self.resources.borrow_mut().delete_all(&self.gl);
+    }
 }
@@ -0,0 +1,164 @@
+//! An error which the Renderer can recover from, or at least report.
+
+use std::fmt;
+use web_sys::WebGlRenderingContext as GL;
+use crate::error::{ERROR,error_to_string};
+use super::ShaderDiagnostic;
+
+
+/// #### An error which the Renderer can recover from, or at least report.
+///
+/// The RkCold, RkCool, RkWarm and SkCool kits return these, instead of
+/// panicking. That way, one bad shader does not kill the whole wasm instance,
+/// and the App can pass the error to the browser, in its Phase 9 report.
+#[derive(Clone,Debug,PartialEq)]
+pub struct RendererError {
+    /// The app’s unique code for this kind of error, eg `ERROR::R44101`.
+    pub code: ERROR,
+    /// The value of `gl.getError()`, eg `GL::INVALID_OPERATION`, or 0 if the
+    /// error was not reported by WebGL.
+    pub gl_error: u32,
+    /// What the Renderer was doing when the error occurred.
+    pub context: String,
+    /// Each problem in the GLSL source, if a shader failed to compile.
+    pub diagnostics: Vec<ShaderDiagnostic>,
+}
+
+impl RendererError {
+
+    /// Creates a RendererError which was not reported by WebGL.
+    pub fn new(
+        code: ERROR,
+        context: String,
+    ) -> Self {
+        Self {
+            code,
+            gl_error: GL::NO_ERROR,
+            context,
+            diagnostics: vec![],
+        }
+    }
+
+    /// Attaches the diagnostics parsed from a shader info log.
+    pub fn with_diagnostics(
+        mut self,
+        diagnostics: Vec<ShaderDiagnostic>,
+    ) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// Asks WebGL for its most recent error, and returns it as a RendererError.
+    /// This is slow, so call it from inside an `if SLOWLY_GET_ERROR_*_PATH {}`.
+    pub fn check(
+        gl: &GL,
+        code: ERROR,
+        context: &str,
+    ) -> Result<(), Self> {
+        let gl_error = gl.get_error(); // @TODO does this actually pick up errors?
+        if gl_error == GL::NO_ERROR { return Ok(()) }
+        Err(Self {
+            code,
+            gl_error,
+            context: context.into(),
+            diagnostics: vec![],
+        })
+    }
+
+    /// Decodes `gl_error` to the name of its WebGL constant, eg "INVALID_VALUE".
+    pub fn gl_error_name(
+        &self,
+    ) -> &'static str {
+        match self.gl_error {
+            GL::NO_ERROR => "NO_ERROR",
+            GL::INVALID_ENUM => "INVALID_ENUM",
+            GL::INVALID_VALUE => "INVALID_VALUE",
+            GL::INVALID_OPERATION => "INVALID_OPERATION",
+            GL::OUT_OF_MEMORY => "OUT_OF_MEMORY",
+            GL::INVALID_FRAMEBUFFER_OPERATION => "INVALID_FRAMEBUFFER_OPERATION",
+            GL::CONTEXT_LOST_WEBGL => "CONTEXT_LOST_WEBGL",
+            _ => "UNKNOWN_GL_ERROR",
+        }
+    }
+
+    /// Serialises the error, so it can be added to the App’s Phase 9 report.
+    pub fn to_json(
+        &self,
+    ) -> String {
+        let diagnostics: Vec<String> = self.diagnostics
+            .iter()
+            .map(diagnostic_to_json)
+            .collect();
+        format!(
+            r#"{{"code":"{:?}","description":"{}","gl_error":"{}","context":"{}","diagnostics":[{}]}}"#,
+            self.code,
+            escape_json(error_to_string(self.code)),
+            self.gl_error_name(),
+            escape_json(&self.context),
+            diagnostics.join(","),
+        )
+    }
+}
+
+/// Eg "R44101 RKWARM ERROR: WebGL error during render: INVALID_ENUM in clear()"
+/// Any diagnostics follow on subsequent lines.
+impl fmt::Display for RendererError {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        if self.gl_error == GL::NO_ERROR {
+            write!(f, "{}: {}", error_to_string(self.code), self.context)?;
+        } else {
+            write!(f, "{}: {} in {}",
+                error_to_string(self.code), self.gl_error_name(), self.context)?;
+        }
+        for diagnostic in self.diagnostics.iter() {
+            write!(f, "\n{}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+
+// HELPERS
+
+fn diagnostic_to_json(
+    diagnostic: &ShaderDiagnostic,
+) -> String {
+    let excerpt: Vec<String> = diagnostic.excerpt
+        .iter()
+        .map(|(line, text)| format!(r#"{{"line":{},"text":"{}"}}"#, line, escape_json(text)))
+        .collect();
+    format!(
+        r#"{{"shader_name":"{:?}","stage":"{}","severity":"{}","chunk":{},"line":{},"column":{},"message":"{}","excerpt":[{}]}}"#,
+        diagnostic.shader_name,
+        diagnostic.stage,
+        diagnostic.severity,
+        diagnostic.chunk.map_or("null".into(), |chunk| format!(r#""{}""#, chunk)),
+        diagnostic.line.map_or("null".into(), |line| line.to_string()),
+        diagnostic.column.map_or("null".into(), |column| column.to_string()),
+        escape_json(&diagnostic.message),
+        excerpt.join(","),
+    )
+}
+
+fn escape_json(
+    raw: &str,
+) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
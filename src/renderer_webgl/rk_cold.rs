@@ -3,7 +3,8 @@
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::wasm_bindgen;
 use web_sys::{HtmlCanvasElement,WebGlRenderingContext as GL};
-use crate::error::{ERROR as E,error_to_string as e,SLOWLY_GET_ERROR_COLD_PATH};
+use crate::error::{ERROR as E,SLOWLY_GET_ERROR_COLD_PATH};
+use super::{BlendMode,CullFace,DepthTest,PipelineState,RendererError};
 
 
 // Declare the type of the `ANGLE_instanced_arrays` WebGL extension.
@@ -54,23 +55,22 @@ impl RkCold {
     /// Initialises the WebGL context.
     pub fn init_context(
         canvas: &HtmlCanvasElement,
-    ) -> GL {
+    ) -> Result<GL, RendererError> {
         let gl: GL =
             canvas
                 .get_context("webgl")
-                .unwrap()
-                .unwrap()
-                .dyn_into::<GL>() // `dyn_into` is provided by JsCast
-                .unwrap();
+                .ok()
+                .flatten()
+                .and_then(|context| context.dyn_into::<GL>().ok()) // `dyn_into` is provided by JsCast
+                .ok_or_else(|| RendererError::new(E::R22214, "get_context(\"webgl\")".into()))?;
 
         // Ask WebGL for its most recent error.
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("initializing {}", e) }
+            RendererError::check(&gl, E::R22388, "get_context()")?;
         }
 
         // The WebGL context has been successfully initialized.
-        gl
+        Ok(gl)
     }
 
 
@@ -78,41 +78,60 @@ impl RkCold {
     pub fn check_max_vertex_attributes(
         gl: &GL,
         needed_max: u16,
-    ) {
+    ) -> Result<(), RendererError> {
         let actual_max =
             gl.get_parameter(GL::MAX_VERTEX_ATTRIBS)
-                .unwrap()
-                .as_f64()
-                .expect(e(E::R22860)) as u16;
+                .ok()
+                .and_then(|value| value.as_f64())
+                .ok_or_else(|| RendererError::new(E::R22860, "get_parameter()".into()))? as u16;
 
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("get_parameter() {}", e) }
+            RendererError::check(gl, E::R22388, "get_parameter()")?;
         }
 
         if actual_max < needed_max {
-            panic!("{}: {} < {}", e(E::R22863), actual_max, needed_max);
+            return Err(RendererError::new(E::R22863,
+                format!("{} < {}", actual_max, needed_max)));
         }
+        Ok(())
+    }
+
+
+    /// Finds how many texture units the vertex and fragment shaders can read
+    /// between them. WebGL guarantees at least eight.
+    pub fn get_max_texture_units(
+        gl: &GL,
+    ) -> Result<usize, RendererError> {
+        let max_texture_units =
+            gl.get_parameter(GL::MAX_COMBINED_TEXTURE_IMAGE_UNITS)
+                .ok()
+                .and_then(|value| value.as_f64())
+                .ok_or_else(|| RendererError::new(E::R22861, "get_parameter()".into()))? as usize;
+
+        if SLOWLY_GET_ERROR_COLD_PATH {
+            RendererError::check(gl, E::R22388, "get_parameter()")?;
+        }
+        Ok(max_texture_units)
     }
 
 
     /// Initialises the `ANGLE_instanced_arrays` WebGL extension
     pub fn init_extensions(
         gl: &GL,
-    ) -> AngleInstancedArrays {
+    ) -> Result<AngleInstancedArrays, RendererError> {
 
         // Check that the extension is present on the device. @TODO
         // info(&format!("{:?}",gl.get_supported_extensions()));
 
         let ext_instanced_arrays =
             gl.get_extension("ANGLE_instanced_arrays")
-                .unwrap()
-                .expect(e(E::R22870))
+                .ok()
+                .flatten()
+                .ok_or_else(|| RendererError::new(E::R22870, "ANGLE_instanced_arrays".into()))?
                 .unchecked_into::<AngleInstancedArrays>();
 
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("get_extension() {}", e) }
+            RendererError::check(gl, E::R22388, "get_extension()")?;
         }
     
         // let property = JsValue::from_str("drawArraysInstancedANGLE");
@@ -122,64 +141,112 @@ impl RkCold {
         // console::log_1(&value.unwrap());
 
         // The `ANGLE_instanced_arrays` extension has been successfully initialized.
-        ext_instanced_arrays
+        Ok(ext_instanced_arrays)
+    }
+
+
+    /// Enables `OES_element_index_uint`, so that index buffers can hold `u32`
+    /// indices, and address more than 65,536 vertices. Returns `false` if the
+    /// device doesn’t support it, in which case only `u8` and `u16` indices can
+    /// be used. The extension is built into WebGL2.
+    pub fn init_element_index_uint(
+        gl: &GL,
+    ) -> Result<bool, RendererError> {
+        let supported = gl.get_extension("OES_element_index_uint")
+            .ok()
+            .flatten()
+            .is_some();
+        if SLOWLY_GET_ERROR_COLD_PATH {
+            RendererError::check(gl, E::R22388, "get_extension(\"OES_element_index_uint\")")?;
+        }
+        Ok(supported)
     }
 
 
-    /// Initialise the graphics pipeline.
+    /// Initialise the graphics pipeline. Every part of `pipeline_state` is sent
+    /// to WebGL, because nothing can be assumed about the context’s state.
+    /// After this, `RkWarm::set_pipeline_state()` only sends what changes.
     pub fn init_pipeline(
         gl: &GL,
-    ) {
+        pipeline_state: &PipelineState,
+    ) -> Result<(), RendererError> {
+
+        // The viewport is set by `RkWarm::bind_render_target()`, to match the
+        // canvas or the RenderTarget being drawn into.
+
+        // Tell WebGL whether to test the depth when drawing, so if a triangle
+        // is behind another triangle it won't be drawn.
+        match pipeline_state.depth_test {
+            DepthTest::Off => gl.disable(GL::DEPTH_TEST),
+            depth_test => {
+                gl.enable(GL::DEPTH_TEST);
+                gl.depth_func(depth_test.to_gl());
+            },
+        }
+        if SLOWLY_GET_ERROR_COLD_PATH {
+            RendererError::check(gl, E::R22388, "enable(GL::DEPTH_TEST), depth_func()")?;
+        }
 
-        // @TODO investigate what viewport() does
-        // gl.viewport(200, 200, 500, 500);
-        // gl.viewport(0, 0, gl.drawingBufferWidth, gl.drawingBufferHeight);
-        // gl.viewport(0, 0, canvas_extent_horizontal, canvas_extent_vertical);
-    
-        // // Allow semitransparent fills — this has a performance hit though!
-        // // Also, define how blending should work. 
-        // gl.enable(GL::BLEND);
-        // gl.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
-
-        // Tell WebGL to test the depth when drawing, so if a triangle is behind
-        // another triangle it won't be drawn.
-        gl.enable(GL::DEPTH_TEST);
+        // Allow semitransparent fills — this has a performance hit though!
+        // Also, define how blending should work.
+        match pipeline_state.blend_mode {
+            BlendMode::Opaque => gl.disable(GL::BLEND),
+            blend_mode => {
+                let (source, destination) = blend_mode.to_gl_factors();
+                gl.enable(GL::BLEND);
+                gl.blend_func(source, destination);
+            },
+        }
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("enable(GL::DEPTH_TEST) {}", e) }
+            RendererError::check(gl, E::R22388, "enable(GL::BLEND), blend_func()")?;
+        }
+
+        // Skip triangles facing away from (or towards) the camera.
+        match pipeline_state.cull_face {
+            CullFace::Off => gl.disable(GL::CULL_FACE),
+            CullFace::Back => { gl.enable(GL::CULL_FACE); gl.cull_face(GL::BACK) },
+            CullFace::Front => { gl.enable(GL::CULL_FACE); gl.cull_face(GL::FRONT) },
         }
-        gl.depth_func(GL::LEQUAL);
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("depth_func() {}", e) }
+            RendererError::check(gl, E::R22388, "enable(GL::CULL_FACE), cull_face()")?;
         }
-        
+
         // Pipeline Stage 1: Color Clearing
         // Define what colour should be used when the canvas is cleared.
         // Also, set the clear-depth to its maximum, which will clear everything.
         // developer.mozilla.org/en-US/docs/Web/API/WebGL_API/By_example/Clearing_with_colors
-        gl.clear_color(0.1, 0.05, 0.15, 1.0); // rgba
+        let [red, green, blue, alpha] = pipeline_state.clear_color;
+        gl.clear_color(red, green, blue, alpha);
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("clear_color() {}", e) }
+            RendererError::check(gl, E::R22388, "clear_color()")?;
         }
         gl.clear_depth(1.0);
         if SLOWLY_GET_ERROR_COLD_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("clear_depth() {}", e) }
+            RendererError::check(gl, E::R22388, "clear_depth()")?;
         }
 
         // Pipeline Stage 2: Scissoring
-        // Enable scissoring, and define the position and size of its area.
-        // gl.enable(GL::SCISSOR_TEST);
-        // gl.scissor(40, 20,
-        //     (canvas_extent_horizontal - 70.0) as i32, // 30px right-border
-        //     (canvas_extent_vertical - 30.0) as i32); // 10px tp[-border
+        // Maybe enable scissoring, and define the position and size of its area.
+        match pipeline_state.scissor {
+            None => gl.disable(GL::SCISSOR_TEST),
+            Some([x, y, width, height]) => {
+                gl.enable(GL::SCISSOR_TEST);
+                gl.scissor(x, y, width, height);
+            },
+        }
+        if SLOWLY_GET_ERROR_COLD_PATH {
+            RendererError::check(gl, E::R22388, "enable(GL::SCISSOR_TEST), scissor()")?;
+        }
 
         // Pipeline Stage 3: Colour Masking
-        // Switch off the blue channel, just for fun!
+        // Eg `[true, true, false, true]` switches off the blue channel.
         // developer.mozilla.org/en-US/docs/Web/API/WebGL_API/By_example/Color_masking
-        // gl.color_mask(true, true, false, true);
+        let [red, green, blue, alpha] = pipeline_state.color_mask;
+        gl.color_mask(red, green, blue, alpha);
+        if SLOWLY_GET_ERROR_COLD_PATH {
+            RendererError::check(gl, E::R22388, "color_mask()")?;
+        }
 
+        Ok(())
     }
 }
@@ -1,20 +1,15 @@
 //! A kit of ‘cool path’ operations for the Renderer.
 
-use js_sys::WebAssembly;
-use wasm_bindgen::JsCast;
-use web_sys::{WebGlBuffer,WebGlRenderingContext as GL};
+use web_sys::{HtmlImageElement,WebGlRenderingContext as GL};
 
-use crate::error::SLOWLY_GET_ERROR_COOL_PATH;
+use crate::error::{ERROR as E,SLOWLY_GET_ERROR_COOL_PATH,error_to_string};
 
-use super::{AttributeName,RendererWebGl,UniformName};
-use super::shader::{
-    ShaderBlueBox,
-    ShaderFurlBasic,
-    ShaderGuides,
-    ShaderProgramName,
-    ShaderRainbowCactus,
-    ShaderRedBox
-};
+use super::{AttributeName,BufferHandle,GlElement,GlIndex,GpuOwner,IndexBuffer,RenderTarget,RendererError,RendererWebGl,ShaderFeatures,TextureHandle,TextureSettings,UniformCache,UniformKind,UniformName,VertexFormat};
+use super::gl_element;
+use super::shader::{ShaderProgram,ShaderProgramName};
+
+/// The most RGBA bytes a texture may hold before mipmaps, eg 8192 x 8192 pixels.
+const MAX_TEXTURE_BYTES: u64 = 256 * 1024 * 1024;
 
 
 /// #### A kit of ‘cool path’ operations for the Renderer.
@@ -28,9 +23,9 @@ impl RkCool {
     pub fn store_signature_locations(
         r: &mut RendererWebGl,
         shader_index: usize,
-    ) {
+    ) -> Result<(), RendererError> {
         // Tell WebGL to start using the shader under investigation.
-        Self::use_shader(r, shader_index);
+        Self::use_shader(r, shader_index)?;
 
         // Get the Shader’s WebGlProgram and ShaderSignature.
         let shader = &mut r.shaders[shader_index];
@@ -48,10 +43,10 @@ impl RkCool {
             // Ask WebGL what location it has given the attribute.
             let gl_location = r.gl.get_attrib_location(&program, name_glsl);
             if SLOWLY_GET_ERROR_COOL_PATH {
-                let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-                if e != 0 { panic!("store...() get_attrib_location() {} {}", name_glsl, e) }
+                RendererError::check(&r.gl, E::R33245,
+                    &format!("store...() get_attrib_location(..., \"{}\")", name_glsl))?;
             }
-            if gl_location < 0 { panic!("store...() attribute name '{}' not recognised", name_glsl) }
+            if gl_location < 0 { return Err(RendererError::new(E::R33512, name_glsl.into())) }
             index_to_location.push(gl_location as u32);
         }
 
@@ -59,168 +54,320 @@ impl RkCool {
         // @TOD combine these two loops into one... which means solve a Rust mutability puzzle
         for i in 0..index_to_location.len() {
             let location = index_to_location[i];
-            shader.set_attribute_location(i, location);
+            shader.set_attribute_location(i, location)?;
             // crate::info(&format!("shaders[{}].signature.attribute_signatures[{}].location = {}", shader_index, i, location));
         }
+        Ok(())
     }
 
-    ///
-    pub fn _confirm_signature_locations(
-        r: &RendererWebGl,
-        shader_index: usize,
-    ) {
-        // Tell WebGL to start using the shader under investigation.
-        Self::use_shader(r, shader_index);
-
-        // Get the Shader’s WebGlProgram and ShaderSignature.
-        let shader = &r.shaders[shader_index];
-        let program = shader.get_program();
-        let signature = shader.get_signature();
-
-        // Confirm each AttributeSignature.
-        for i in 0..signature.attribute_signatures.len() {
-            let attribute_signature = &signature.attribute_signatures[i];
-            let location = attribute_signature.location;
-            let name_glsl = attribute_signature.name_glsl;
-
-            // // This is an error in our Rust code — the `location` field should
-            // // simply match the Vec<> index, so:
-            // // vec![ { location: 0, ... }, { location: 1, ... }, ...etc ]
-            // if location as u32 != attribute_signature.location {
-            //     panic!("easy-to-fix location mismatch [{}] {} {} != {}",
-            //         shader_index, name_glsl, location, attribute_signature.location);
-            // }
-
-            // Ask WebGL what location it has given the attribute.
-            let gl_location = r.gl.get_attrib_location(&program, name_glsl);
-            if SLOWLY_GET_ERROR_COOL_PATH {
-                let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-                if e != 0 { panic!("confirm...() get_attrib_location() {} {}", name_glsl, e) }
-            }
-            if gl_location < 0 { panic!("confirm...() attribute name '{}' not recognised", name_glsl) }
-
-            // During development, this is probably a mismatch between the GLSL
-            // in the .vect, and the Rust in the AttributeSignature.
-            // If it occurs in production, it may be because some graphics card
-            // out there in the wild does not allocate locations predictably.
-            if location != gl_location as u32 {
-                panic!("confirm...() serious location mismatch shaders[{}] \"{}\" is at {}, not {}",
-                    shader_index, name_glsl, gl_location, location);
+    /// Checks that each of a Scene’s Shaders has its attributes at the
+    /// locations in its ShaderSignature, and that Shaders which share an
+    /// attribute agree on its location. `enable_attribute()`, `use_attribute()`
+    /// and `set_repeat_gap()` rely on this.  
+    /// Any mismatched Shader is rebound to the locations of the first Shader
+    /// which uses each attribute, and relinked. If that doesn’t fix it, every
+    /// remaining mismatch is returned in one error.
+    pub fn confirm_signature_locations(
+        r: &mut RendererWebGl,
+        shader_indices: &[usize],
+    ) -> Result<(), RendererError> {
+        r.scene_shaders.replace(shader_indices.to_vec());
+        let locations = shared_locations(r, shader_indices);
+        let mismatches = find_location_mismatches(r, shader_indices, &locations)?;
+        if mismatches.is_empty() { return Ok(()) }
+
+        // During development, a mismatch is probably an error in a Rust
+        // AttributeSignature. In production, it may be because some graphics
+        // card out there in the wild does not allocate locations predictably.
+        let mut replacements: Vec<(usize, ShaderProgram)> = vec![];
+        for (shader_index, mismatch) in mismatches.iter() {
+            crate::error(&format!("{}: {} (relinking)", error_to_string(E::R33560), mismatch));
+            if replacements.iter().any(|(index, _)| index == shader_index) { continue }
+            let shader = &r.shaders[*shader_index];
+            let mut signature = shader.get_signature().clone();
+            for attribute_signature in signature.attribute_signatures.iter_mut() {
+                if let Some((_, location)) = locations.iter()
+                    .find(|(name_glsl, _)| *name_glsl == attribute_signature.name_glsl) {
+                    attribute_signature.location = *location;
+                }
             }
-
-            crate::info(&format!("[{}] {} {}", shader_index, name_glsl, location));
+            replacements.push((*shader_index, ShaderProgram::from_sources(
+                &r.gl,
+                shader.get_program_name(),
+                signature,
+                shader.get_vert_source(),
+                shader.get_frag_source(),
+                shader.get_defines(),
+            )?));
         }
+        swap_shaders(r, replacements)?;
+
+        let remaining = find_location_mismatches(r, shader_indices, &locations)?;
+        if remaining.is_empty() { return Ok(()) }
+        let remaining: Vec<String> = remaining.into_iter()
+            .map(|(_, mismatch)| mismatch)
+            .collect();
+        Err(RendererError::new(E::R33560, remaining.join("; ")))
     }
 
 
-    ///
-    pub fn create_buffer_f32(
+    /// Uploads `values` to a new WebGLBuffer, which is freed along with the
+    /// Scene. Returns a handle to it, for `bind_buffer()`.  
+    /// `target` is `GL::ARRAY_BUFFER` for attributes, or
+    /// `GL::ELEMENT_ARRAY_BUFFER` for indices. `T` can be any GlElement —
+    /// `f32`, `i16`, `u8`, `u16` or `u32`.
+    pub fn create_buffer<T: GlElement>(
         r: &RendererWebGl,
-        values: Vec<f32>,
-    ) -> WebGlBuffer {
-
-        // Convert `values` (a Rust vector) into a JavaScript `Float32Array`.
-        // A WASM buffer is a different beast to the WebGL buffer created below.
-        // __IMPORTANT NOTE:__ `/ 4` because items in `Vec<f32>` are four bytes.
-        let wasm_pointer = values.as_ptr() as u32 / 4; // <-- don’t get 4 wrong!
-        let wasm_buffer = wasm_bindgen::memory()
-            .dyn_into::<WebAssembly::Memory>() // `dyn_into` is provided by JsCast
-            .unwrap()
-            .buffer();
-        let wasm_array = js_sys::Float32Array::new(&wasm_buffer)
-            .subarray(
-                wasm_pointer,
-                wasm_pointer + values.len() as u32,
-            );
+        target: u32,
+        values: &[T],
+    ) -> Result<BufferHandle, RendererError> {
+        Self::create_buffer_owned_by(r, target, values, GpuOwner::Scene)
+    }
+
+
+    /// Like `create_buffer()`, but `GpuOwner::Renderer` keeps the buffer alive
+    /// when the Scene is torn down, eg for a PostChain’s full-screen quad.
+    pub fn create_buffer_owned_by<T: GlElement>(
+        r: &RendererWebGl,
+        target: u32,
+        values: &[T],
+        owner: GpuOwner,
+    ) -> Result<BufferHandle, RendererError> {
 
         // Tell WebGL to initialise a WebGLBuffer object, which represents a
         // block of VRAM, physically close to the GPU. Get a pointer to it.
         let webgl_buffer = r.gl.create_buffer()
-            .expect("failed to create attribute buffer");
+            .ok_or_else(|| RendererError::new(E::R33279, "create_buffer()".into()))?;
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("create_buffer() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "create_buffer()")?;
         }
 
         // WebGL is a state machine, which has an internal ‘current buffer’
         // pointer. Tell it to switch to using the WebGL buffer we just created.
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&webgl_buffer));
+        r.gl.bind_buffer(target, Some(&webgl_buffer));
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("bind_buffer() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "bind_buffer()")?;
         }
-    
-        // Write the `Float32Array` into the WebGL buffer we just created.
-        r.gl.buffer_data_with_array_buffer_view(
-            GL::ARRAY_BUFFER,
-            &wasm_array,
-            GL::STATIC_DRAW, // because `wasm_array` will remain the same for many renders
+
+        // Write `values` into the WebGL buffer we just created. A WASM buffer
+        // is a different beast to a WebGL buffer, so `buffer_data()` views the
+        // slice as a JavaScript typed array of the right element size.
+        gl_element::buffer_data(
+            &r.gl,
+            target,
+            values,
+            GL::STATIC_DRAW, // because `values` will remain the same for many renders
         );
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("buffer_data_with_array_buffer_view() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "buffer_data_with_array_buffer_view()")?;
         }
 
-        // The pointer to the new WebGLBuffer object will be used every time an
-        // attribute needs some of its values.
-        webgl_buffer
+        // Register the new WebGLBuffer object, so that it can be freed when its
+        // owner is torn down. Its handle will be used every time an attribute
+        // needs some of its values.
+        Ok(r.resources.borrow_mut().add_buffer(webgl_buffer, owner, values.len() * T::BYTES))
+    }
+
+
+    /// Shorthand for `create_buffer(r, GL::ARRAY_BUFFER, values)`.
+    pub fn create_buffer_f32(
+        r: &RendererWebGl,
+        values: &[f32],
+    ) -> Result<BufferHandle, RendererError> {
+        Self::create_buffer(r, GL::ARRAY_BUFFER, values)
+    }
+
+
+    /// Uploads `values` to a new index buffer, and leaves it bound as the
+    /// `GL::ELEMENT_ARRAY_BUFFER`. The returned IndexBuffer records the index
+    /// type, so that draw calls read it correctly. `u32` indices need the
+    /// `OES_element_index_uint` extension, and fail with R33486 without it.
+    pub fn create_index_buffer<T: GlIndex>(
+        r: &RendererWebGl,
+        values: &[T],
+    ) -> Result<IndexBuffer, RendererError> {
+        if T::GL_TYPE == GL::UNSIGNED_INT && ! r.has_element_index_uint {
+            return Err(RendererError::new(E::R33486,
+                format!("{} indices", values.len())));
+        }
+        Ok(IndexBuffer {
+            buffer: Self::create_buffer(r, GL::ELEMENT_ARRAY_BUFFER, values)?,
+            count: values.len(),
+            index_type: T::GL_TYPE,
+        })
     }
 
 
-    /// Uses `GL::ELEMENT_ARRAY_BUFFER` instead of `GL::ARRAY_BUFFER`.
+    /// Shorthand for `create_index_buffer::<u16>(r, values)`.
     pub fn create_index_buffer_u16(
         r: &RendererWebGl,
-        values: Vec<u16>,
-    ) -> WebGlBuffer {
-
-        // Convert `values` (a Rust vector) into a JavaScript `Uint16Array`.
-        // A WASM buffer is a different beast to the WebGL buffer created below.
-        // __IMPORTANT NOTE:__ `/ 2` because items in `Vec<f16>` are two bytes.
-        let wasm_pointer = values.as_ptr() as u32 / 2; // <-- don’t get 2 wrong!
-        let wasm_buffer = wasm_bindgen::memory()
-            .dyn_into::<WebAssembly::Memory>() // `dyn_into` is provided by JsCast
-            .unwrap()
-            .buffer();
-        let wasm_array = js_sys::Uint16Array::new(&wasm_buffer)
-            .subarray(
-                wasm_pointer,
-                wasm_pointer + values.len() as u32,
-            );
+        values: &[u16],
+    ) -> Result<IndexBuffer, RendererError> {
+        Self::create_index_buffer(r, values)
+    }
 
-        // Tell WebGL to initialise a WebGLBuffer object, which represents a
-        // block of VRAM, physically close to the GPU. Get a pointer to it.
-        let webgl_buffer = r.gl.create_buffer()
-            .expect("failed to create attribute buffer");
+
+    /// Makes a buffer the current `GL::ARRAY_BUFFER`, ready for `use_attribute()`.
+    pub fn bind_buffer(
+        r: &RendererWebGl,
+        buffer: BufferHandle,
+    ) -> Result<(), RendererError> {
+        let webgl_buffer = r.resources.borrow().buffer(buffer)?;
+        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&webgl_buffer));
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("create_buffer() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "bind_buffer()")?;
         }
+        Ok(())
+    }
 
-        // WebGL is a state machine, which has an internal ‘current buffer’
-        // pointer. Tell it to switch to using the WebGL buffer we just created.
-        r.gl.bind_buffer(
-            GL::ELEMENT_ARRAY_BUFFER,
-            Some(&webgl_buffer),
-        );
+
+    /// Creates an offscreen framebuffer, `width` by `height` pixels, with an RGBA
+    /// texture to draw colours into, and optionally a 16 bit depth buffer.  
+    /// Leaves the default framebuffer (the canvas) bound.
+    pub fn create_render_target(
+        r: &RendererWebGl,
+        width: i32,
+        height: i32,
+        with_depth: bool,
+        owner: GpuOwner,
+    ) -> Result<RenderTarget, RendererError> {
+        let gl = &r.gl;
+
+        // The colour attachment. Non-power-of-two textures must be clamped to
+        // their edges, and can’t be mipmapped, in WebGL1.
+        let texture = gl.create_texture()
+            .ok_or_else(|| RendererError::new(E::R33281, "create_texture()".into()))?;
+        gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            GL::TEXTURE_2D,
+            0, // level
+            GL::RGBA as i32, // internal format
+            width,
+            height,
+            0, // border — must be 0
+            GL::RGBA, // format — must match the internal format
+            GL::UNSIGNED_BYTE,
+            None, // no pixels yet, it will be drawn into
+        ).map_err(|_| RendererError::new(E::R33281, format!("tex_image_2d(..., {}, {}, ...)", width, height)))?;
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+        r.record_texture_binding(None);
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("bind_buffer() {}", e) }
+            RendererError::check(gl, E::R33245, "create_render_target() texture")?;
         }
 
-        // Write the `Uint16Array` into the WebGL buffer we just created.
-        r.gl.buffer_data_with_array_buffer_view(
-            GL::ELEMENT_ARRAY_BUFFER,
-            &wasm_array,
-            GL::STATIC_DRAW, // because `wasm_array` will remain the same for many renders
-        );
+        let framebuffer = gl.create_framebuffer()
+            .ok_or_else(|| RendererError::new(E::R33281, "create_framebuffer()".into()))?;
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&framebuffer));
+        gl.framebuffer_texture_2d(GL::FRAMEBUFFER, GL::COLOR_ATTACHMENT0, GL::TEXTURE_2D, Some(&texture), 0);
+
+        // The depth attachment is never sampled, so a renderbuffer will do.
+        let renderbuffer = if with_depth {
+            let renderbuffer = gl.create_renderbuffer()
+                .ok_or_else(|| RendererError::new(E::R33281, "create_renderbuffer()".into()))?;
+            gl.bind_renderbuffer(GL::RENDERBUFFER, Some(&renderbuffer));
+            gl.renderbuffer_storage(GL::RENDERBUFFER, GL::DEPTH_COMPONENT16, width, height);
+            gl.framebuffer_renderbuffer(GL::FRAMEBUFFER, GL::DEPTH_ATTACHMENT, GL::RENDERBUFFER, Some(&renderbuffer));
+            gl.bind_renderbuffer(GL::RENDERBUFFER, None);
+            Some(renderbuffer)
+        } else {
+            None
+        };
+
+        let status = gl.check_framebuffer_status(GL::FRAMEBUFFER);
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+        if status != GL::FRAMEBUFFER_COMPLETE {
+            gl.delete_framebuffer(Some(&framebuffer));
+            gl.delete_texture(Some(&texture));
+            if let Some(renderbuffer) = renderbuffer { gl.delete_renderbuffer(Some(&renderbuffer)) }
+            return Err(RendererError::new(E::R33296,
+                format!("status 0x{:x} for {}x{}", status, width, height)));
+        }
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("buffer_data_with_array_buffer_view() {}", e) }
+            RendererError::check(gl, E::R33245, "create_render_target() framebuffer")?;
         }
 
-        // The pointer to the new WebGLBuffer object will be used every time an
-        // attribute needs some of its values.
-        webgl_buffer
+        // Register the three objects, so that they can be freed together.
+        let mut resources = r.resources.borrow_mut();
+        let pixels = (width * height) as usize;
+        Ok(RenderTarget {
+            framebuffer: resources.add_framebuffer(framebuffer, owner),
+            color: resources.add_texture(texture, owner, pixels * 4),
+            depth: renderbuffer.map(|renderbuffer| resources.add_renderbuffer(renderbuffer, owner, pixels * 2)),
+            width,
+            height,
+        })
+    }
+
+
+    /// Creates a texture from RGBA pixels, four bytes per pixel. The first row
+    /// is sampled at a `v` texture coordinate of 0.0. The Scene owns it, so
+    /// it’s freed when the Scene is torn down.
+    pub fn create_texture(
+        r: &RendererWebGl,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        settings: TextureSettings,
+    ) -> Result<TextureHandle, RendererError> {
+        if width == 0 || height == 0
+            || texture_bytes(width, height).is_some_and(|bytes| bytes != rgba.len()) {
+            return Err(RendererError::new(E::R33305,
+                format!("{} bytes for {}x{}", rgba.len(), width, height)));
+        }
+        let texture = begin_texture(r, width, height, settings)?;
+        let uploaded = r.gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            GL::TEXTURE_2D,
+            0, // level
+            GL::RGBA as i32, // internal format
+            width as i32,
+            height as i32,
+            0, // border — must be 0
+            GL::RGBA, // format — must match the internal format
+            GL::UNSIGNED_BYTE,
+            Some(rgba),
+        );
+        if uploaded.is_err() {
+            r.resources.borrow_mut().delete_texture(&r.gl, texture);
+            return Err(RendererError::new(E::R33281, format!("tex_image_2d(..., {}, {}, ...)", width, height)));
+        }
+        finish_texture(r, settings)?;
+        Ok(texture)
+    }
+
+
+    /// Creates a texture from an image which JavaScript has already loaded,
+    /// eg a PNG of seeds. Its top row is sampled at a `v` of 0.0. The Scene
+    /// owns it, so it’s freed when the Scene is torn down.
+    pub fn create_texture_from_image(
+        r: &RendererWebGl,
+        image: &HtmlImageElement,
+        settings: TextureSettings,
+    ) -> Result<TextureHandle, RendererError> {
+        let width = image.natural_width();
+        let height = image.natural_height();
+        if width == 0 || height == 0 {
+            return Err(RendererError::new(E::R33305,
+                format!("{}x{} image — has \"{}\" loaded?", width, height, image.src())));
+        }
+        let texture = begin_texture(r, width, height, settings)?;
+        let uploaded = r.gl.tex_image_2d_with_u32_and_u32_and_image(
+            GL::TEXTURE_2D,
+            0, // level
+            GL::RGBA as i32, // internal format
+            GL::RGBA, // format — must match the internal format
+            GL::UNSIGNED_BYTE,
+            image,
+        );
+        if uploaded.is_err() {
+            r.resources.borrow_mut().delete_texture(&r.gl, texture);
+            return Err(RendererError::new(E::R33281, format!("tex_image_2d(..., \"{}\")", image.src())));
+        }
+        finish_texture(r, settings)?;
+        Ok(texture)
     }
 
 
@@ -230,42 +377,57 @@ impl RkCool {
     pub fn enable_attribute(
         r: &RendererWebGl,
         attribute_name: AttributeName,
-    ) {
-        // We’ve made sure that a Scene’s attribute locations are identical for
-        // all the vertex shaders it uses. So `[0]` is as good as any index.
-        let location = r.shaders[0].get_attribute_location(attribute_name);
+    ) -> Result<(), RendererError> {
+        let location = attribute_location(r, attribute_name)?;
 
         r.gl.enable_vertex_attrib_array(location);
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("enable_vertex_attrib_array() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "enable_vertex_attrib_array()")?;
         }
+        Ok(())
     }
 
 
-    /// @TODO describe
+    /// Points an attribute at the current `GL::ARRAY_BUFFER`, which holds
+    /// `size` tightly packed f32s per vertex, and nothing else.
     pub fn use_attribute(
         r: &RendererWebGl,
         attribute_name: AttributeName,
         size: i32,
-    ) {
-        // We’ve made sure that a Scene’s attribute locations are identical for
-        // all the vertex shaders it uses. So `[0]` is as good as any index.
-        let location = r.shaders[0].get_attribute_location(attribute_name);
-
-        // void gl.vertexAttribPointer(index, size, type, normalized, stride, offset)
-        r.gl.vertex_attrib_pointer_with_i32(
-            location, // index of the attribute to be modified
+    ) -> Result<(), RendererError> {
+        attribute_pointer(
+            r,
+            attribute_name,
             size, // number of values per attribute — must be 1, 2, 3, or 4
-            GL::FLOAT, // type — must all be BYTE, SHORT, UNSIGNED_BYTE|SHORT, FLOAT
-            false, // normalized — should integers be clamped when cast to float?
+            GL::FLOAT,
+            false, // normalized — irrelevant for floats
             0, // stride — 0 means the attributes are ‘tightly packed’.
-            0, // offset — ???? must be a multiple of the byte length of `type`
-        );
-        if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // eg 1281 "Channel count `size` must be within [1,4]."
-            if e != 0 { panic!("vertex_attrib_pointer_with_i32() {}", e) }
+            0, // offset
+        )
+    }
+
+
+    /// Binds an interleaved buffer, and points each attribute in `format` at
+    /// its part of every vertex. One bind_buffer() call serves them all.  
+    /// Remember to call `enable_attribute()` for each of them, too.
+    pub fn use_vertex_format(
+        r: &RendererWebGl,
+        buffer: BufferHandle,
+        format: &VertexFormat,
+    ) -> Result<(), RendererError> {
+        Self::bind_buffer(r, buffer)?;
+        for entry in format.attributes.iter() {
+            attribute_pointer(
+                r,
+                entry.name,
+                entry.kind.components(),
+                entry.kind.component_type(),
+                entry.kind.normalized(),
+                format.stride,
+                entry.offset,
+            )?;
         }
+        Ok(())
     }
 
 
@@ -274,78 +436,393 @@ impl RkCool {
         r: &RendererWebGl,
         attribute_name: AttributeName,
         divisor: u32,
-    ) {
-        // We’ve made sure that a Scene’s attribute locations are identical for
-        // all the vertex shaders it uses. So `[0]` is as good as any index.
-        let location = r.shaders[0].get_attribute_location(attribute_name);
+    ) -> Result<(), RendererError> {
+        let location = attribute_location(r, attribute_name)?;
 
         r.ext_instanced_arrays.vertexAttribDivisorANGLE(location, divisor);
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("vertexAttribDivisorANGLE() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "vertexAttribDivisorANGLE()")?;
         }
+        Ok(())
     }
 
 
-    /// Inits a new Shader, and returns its index in the `renderer.shaders` vector.
-    /// Under the hood, `link_program()` tells WebGL to start using the new shader.
+    /// Returns the index in the `renderer.shaders` vector of a Shader with the
+    /// given features. Each feature becomes a `#define` in both the vertex and
+    /// fragment shader, eg `ShaderFeatures::INSTANCE_COLOR` adds `#define INSTANCE_COLOR 1`.  
+    /// A variant is only compiled the first time it’s asked for, and is reused
+    /// after that, eg by the next Scene. `renderer.shader_variants` records how
+    /// long each one took to compile.
     pub fn add_shader(
         r: &mut RendererWebGl,
         shader_kind: ShaderProgramName,
-    ) -> usize {
-        let gl = &r.gl;
+        features: ShaderFeatures,
+    ) -> Result<usize, RendererError> {
+        if let Some(shader_index) = r.shader_variants.request(shader_kind, features) {
+            return Ok(shader_index);
+        }
+        let start_ms = js_sys::Date::now();
+        let (vert_source, frag_source) = r.get_shader_sources(shader_kind);
+        let shader = ShaderProgram::new(&r.gl, shader_kind, features, vert_source, frag_source)?;
+        let compile_ms = js_sys::Date::now() - start_ms;
 
-        &r.shaders.push(
-            match shader_kind {
-                ShaderProgramName::BlueBox =>
-                    Box::new(ShaderBlueBox::new(&gl)),
-                ShaderProgramName::FurlBasic =>
-                    Box::new(ShaderFurlBasic::new(&gl)),
-                ShaderProgramName::Guides =>
-                    Box::new(ShaderGuides::new(&gl)),
-                ShaderProgramName::RainbowCactus =>
-                    Box::new(ShaderRainbowCactus::new(&gl)),
-                ShaderProgramName::RedBox =>
-                    Box::new(ShaderRedBox::new(&gl)),
-            }
-        );
+        // Variants are reused by later Scenes, so the Renderer owns the program.
+        r.resources.borrow_mut().add_program(shader.get_program().clone(), GpuOwner::Renderer);
+        r.shaders.push(shader);
+        r.shader_variants.insert(shader_kind, features, r.shaders.len() - 1, compile_ms);
+
+        // Each Shader gets its own cache of the uniform values sent to it.
+        r.uniform_caches.borrow_mut().push(UniformCache::new());
 
         // Tell the caller the index of the new shader, for future reference.
-        r.shaders.len() - 1
+        Ok(r.shaders.len() - 1)
+    }
+
+
+    /// Replaces every shader called `shader_name` with a program linked from
+    /// the given GLSL source, eg while live-editing a .vert or .frag file.  
+    /// The new program keeps the old one’s ShaderSignature, so it must use each
+    /// attribute at the same location, and declare each uniform. If it fails to
+    /// compile, link or validate, the old program stays in place. Otherwise the
+    /// new GLSL is kept, for variants added later and for `restore()`.
+    pub fn reload_shader(
+        r: &mut RendererWebGl,
+        shader_name: ShaderProgramName,
+        vert_source: &str,
+        frag_source: &str,
+    ) -> Result<(), RendererError> {
+        // Build, validate and give uniforms to every replacement before
+        // swapping any of them in.
+        let mut replacements: Vec<(usize, ShaderProgram)> = vec![];
+        for (shader_index, shader) in r.shaders.iter().enumerate() {
+            if shader.get_program_name() != shader_name { continue }
+            let built = ShaderProgram::from_sources(&r.gl, shader_name,
+                shader.get_signature().clone(), vert_source, frag_source, shader.get_defines())
+                .and_then(|replacement| {
+                    let ready = confirm_attributes(&r.gl, &replacement)
+                        .and_then(|_| resend_uniforms(r, shader_index, &replacement));
+                    if let Err(error) = ready {
+                        r.gl.delete_program(Some(replacement.get_program()));
+                        return Err(error);
+                    }
+                    Ok(replacement)
+                });
+            match built {
+                Ok(replacement) => replacements.push((shader_index, replacement)),
+                Err(error) => {
+                    for (_, replacement) in replacements.iter() {
+                        r.gl.delete_program(Some(replacement.get_program()));
+                    }
+                    restore_current_shader(r)?;
+                    return Err(error);
+                },
+            }
+        }
+        if replacements.is_empty() {
+            return Err(RendererError::new(E::R33724,
+                format!("ShaderProgramName::{:?}", shader_name)));
+        }
+
+        swap_shaders(r, replacements)?;
+
+        // Variants compiled later, and Shaders relinked by `restore()`, use
+        // the reloaded GLSL too.
+        r.reloaded_sources.retain(|(name, _, _)| *name != shader_name);
+        r.reloaded_sources.push((shader_name, vert_source.into(), frag_source.into()));
+        restore_current_shader(r)
     }
 
 
     /// Tells WebGL to stop using its current shader program, and start using a
     /// different one.  
+    /// Does nothing if that shader is already active.  
     /// NOTE: There is an RkWarm equivalent of this function.
-    /// 
-    /// @TODO maybe do nothing if we know that shader is already active?
     pub fn use_shader(
         r: &RendererWebGl,
         shader_index: usize,
-    ) {
+    ) -> Result<(), RendererError> {
+        if ! r.switch_current_shader(shader_index) { return Ok(()) }
         r.shaders[shader_index].use_program(&r.gl);
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("use_program() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "use_program()")?;
         }
+        Ok(())
     }
 
 
-    /// @TODO describe
+    /// Does nothing if the uniform already has that value.
     pub fn set_uniform_f32(
         r: &RendererWebGl,
         shader_index: usize,
         uniform_name: UniformName,
         value: f32,
-    ) {
-        let location = r.shaders[shader_index].get_uniform_location(uniform_name);
+    ) -> Result<(), RendererError> {
+        if r.uniform_is_cached(shader_index, uniform_name, &[value]) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
 
-        r.gl.uniform1f(Some(&location), value);
+        r.gl.uniform1f(Some(location), value);
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("uniform1f() {}", e) }
+            RendererError::check(&r.gl, E::R33245, "uniform1f()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &[value]);
+        Ok(())
+    }
+
+}
+
+
+
+
+// HELPERS
+
+/// `confirm_signature_locations()` has made sure that the current Scene’s
+/// Shaders agree on each attribute’s location, so the first of them which uses
+/// it is as good as any. Shaders left over from earlier Scenes may disagree, so
+/// they are only asked about an attribute no Scene Shader uses, eg the
+/// PostChain’s `AttributeName::Corner`.
+fn attribute_location(
+    r: &RendererWebGl,
+    attribute_name: AttributeName,
+) -> Result<u32, RendererError> {
+    for shader_index in r.scene_shaders.borrow().iter() {
+        if let Ok(location) = r.shaders[*shader_index].get_attribute_location(attribute_name) {
+            return Ok(location);
+        }
+    }
+    for shader in r.shaders.iter() {
+        if let Ok(location) = shader.get_attribute_location(attribute_name) {
+            return Ok(location);
+        }
+    }
+    Err(RendererError::new(E::R33512, format!("no shader uses AttributeName::{:?}", attribute_name)))
+}
+
+/// The location of each attribute, taken from the signature of the first of
+/// the Shaders which uses it.
+fn shared_locations(
+    r: &RendererWebGl,
+    shader_indices: &[usize],
+) -> Vec<(&'static str, u32)> {
+    let mut locations: Vec<(&'static str, u32)> = vec![];
+    for shader_index in shader_indices.iter() {
+        for attribute_signature in r.shaders[*shader_index].get_signature().attribute_signatures.iter() {
+            if ! locations.iter().any(|(name_glsl, _)| *name_glsl == attribute_signature.name_glsl) {
+                locations.push((attribute_signature.name_glsl, attribute_signature.location));
+            }
+        }
+    }
+    locations
+}
+
+/// Describes every attribute which WebGL has not put at its shared location.
+fn find_location_mismatches(
+    r: &RendererWebGl,
+    shader_indices: &[usize],
+    locations: &[(&'static str, u32)],
+) -> Result<Vec<(usize, String)>, RendererError> {
+    let mut mismatches = vec![];
+    for shader_index in shader_indices.iter() {
+        let shader = &r.shaders[*shader_index];
+        for attribute_signature in shader.get_signature().attribute_signatures.iter() {
+            let name_glsl = attribute_signature.name_glsl;
+            let expected = locations.iter()
+                .find(|(name, _)| *name == name_glsl)
+                .map_or(attribute_signature.location, |(_, location)| *location);
+
+            // Ask WebGL what location it has given the attribute.
+            let gl_location = r.gl.get_attrib_location(shader.get_program(), name_glsl);
+            if SLOWLY_GET_ERROR_COOL_PATH {
+                RendererError::check(&r.gl, E::R33245,
+                    &format!("confirm...() get_attrib_location(..., \"{}\")", name_glsl))?;
+            }
+            if gl_location < 0 {
+                mismatches.push((*shader_index, format!("shaders[{}] {:?} has no \"{}\"",
+                    shader_index, shader.get_program_name(), name_glsl)));
+            } else if gl_location as u32 != expected {
+                mismatches.push((*shader_index, format!("shaders[{}] {:?} has \"{}\" at {}, not {}",
+                    shader_index, shader.get_program_name(), name_glsl, gl_location, expected)));
+            }
         }
     }
+    Ok(mismatches)
+}
+
+/// Calls `gl.vertexAttribPointer()` for the attribute’s shared location.
+fn attribute_pointer(
+    r: &RendererWebGl,
+    attribute_name: AttributeName,
+    size: i32,
+    component_type: u32,
+    normalized: bool,
+    stride: i32,
+    offset: i32,
+) -> Result<(), RendererError> {
+    let location = attribute_location(r, attribute_name)?;
+
+    // void gl.vertexAttribPointer(index, size, type, normalized, stride, offset)
+    r.gl.vertex_attrib_pointer_with_i32(
+        location, // index of the attribute to be modified
+        size, // number of values per attribute — must be 1, 2, 3, or 4
+        component_type, // type — must be BYTE, SHORT, UNSIGNED_BYTE|SHORT or FLOAT
+        normalized, // should integers be mapped to 0.0..1.0 (or -1.0..1.0) when cast to float?
+        stride, // bytes from one vertex to the next, or 0 if ‘tightly packed’
+        offset, // bytes into the vertex — must be a multiple of the byte length of `type`
+    );
+    if SLOWLY_GET_ERROR_COOL_PATH { // eg INVALID_VALUE "Channel count `size` must be within [1,4]."
+        RendererError::check(&r.gl, E::R33245,
+            &format!("vertex_attrib_pointer_with_i32() for {:?}", attribute_name))?;
+    }
+    Ok(())
+}
+
+/// Swaps each replacement Shader into `r.shaders`, then deletes the old
+/// programs. Each replacement must already have been given its uniforms.
+fn swap_shaders(
+    r: &mut RendererWebGl,
+    replacements: Vec<(usize, ShaderProgram)>,
+) -> Result<(), RendererError> {
+    // Point the registry at the replacements first, as that’s all that can fail.
+    let mut resources = r.resources.borrow_mut();
+    for (shader_index, replacement) in replacements.iter() {
+        if let Some(handle) = resources.find_program(r.shaders[*shader_index].get_program()) {
+            resources.replace_program(handle, replacement.get_program().clone())?;
+        }
+    }
+    drop(resources);
+    for (shader_index, replacement) in replacements {
+        let old = std::mem::replace(&mut r.shaders[shader_index], replacement);
+        r.gl.delete_program(Some(old.get_program()));
+    }
+    Ok(())
+}
+
+/// Scenes expect the active program to persist between frames, so switch
+/// back to whichever one was in use before `resend_uniforms()` changed it.
+fn restore_current_shader(
+    r: &RendererWebGl,
+) -> Result<(), RendererError> {
+    let previous_shader = r.current_shader.take();
+    match previous_shader {
+        Some(shader_index) => RkCool::use_shader(r, shader_index),
+        None => Ok(()),
+    }
+}
 
+/// Fails if the linked program dropped an attribute, eg because the edited GLSL
+/// no longer uses it. Otherwise `bind_attrib_location()` has fixed its location.
+fn confirm_attributes(
+    gl: &GL,
+    shader: &ShaderProgram,
+) -> Result<(), RendererError> {
+    for attribute_signature in shader.get_signature().attribute_signatures.iter() {
+        let name_glsl = attribute_signature.name_glsl;
+        let gl_location = gl.get_attrib_location(shader.get_program(), name_glsl);
+        if gl_location < 0 {
+            return Err(RendererError::new(E::R11691,
+                format!("reloaded {:?} does not use \"{}\"", shader.get_program_name(), name_glsl)));
+        }
+        if gl_location as u32 != attribute_signature.location {
+            return Err(RendererError::new(E::R33560,
+                format!("reloaded {:?} has \"{}\" at {}, not {}", shader.get_program_name(),
+                    name_glsl, gl_location, attribute_signature.location)));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the size suits the settings, then creates a texture, binds it
+/// to the active texture unit, and registers it as owned by the Scene.
+fn begin_texture(
+    r: &RendererWebGl,
+    width: u32,
+    height: u32,
+    settings: TextureSettings,
+) -> Result<TextureHandle, RendererError> {
+    let bytes = texture_bytes(width, height)
+        .ok_or_else(|| RendererError::new(E::R33302,
+            format!("{}x{} is over {} bytes", width, height, MAX_TEXTURE_BYTES)))?;
+    if settings.needs_power_of_two() && ! (width.is_power_of_two() && height.is_power_of_two()) {
+        return Err(RendererError::new(E::R33302,
+            format!("{}x{} with {:?}", width, height, settings)));
+    }
+    let texture = r.gl.create_texture()
+        .ok_or_else(|| RendererError::new(E::R33281, "create_texture()".into()))?;
+    r.gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+
+    // Mipmaps add a quarter, plus a sixteenth, and so on — about a third.
+    let bytes = if settings.mipmaps { bytes * 4 / 3 } else { bytes };
+    let texture = r.resources.borrow_mut().add_texture(texture, GpuOwner::Scene, bytes);
+    r.record_texture_binding(Some(texture));
+    Ok(texture)
+}
+
+/// Returns `width * height * 4`, or None if that overflows or is over
+/// MAX_TEXTURE_BYTES.
+fn texture_bytes(
+    width: u32,
+    height: u32,
+) -> Option<usize> {
+    (width as u64).checked_mul(height as u64)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .filter(|bytes| *bytes <= MAX_TEXTURE_BYTES)
+        .map(|bytes| bytes as usize)
+}
+
+/// Applies the settings to the texture bound to the active texture unit, once
+/// its pixels have been uploaded.
+fn finish_texture(
+    r: &RendererWebGl,
+    settings: TextureSettings,
+) -> Result<(), RendererError> {
+    let gl = &r.gl;
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, settings.min_filter_to_gl() as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, settings.mag_filter_to_gl() as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, settings.wrap.to_gl() as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, settings.wrap.to_gl() as i32);
+    if settings.mipmaps { gl.generate_mipmap(GL::TEXTURE_2D) }
+    if SLOWLY_GET_ERROR_COOL_PATH {
+        RendererError::check(gl, E::R33245, "create_texture()")?;
+    }
+    Ok(())
+}
+
+/// A new program starts with every uniform at zero, so this makes `replacement`
+/// the active program, and sends it the values that `r.shaders[shader_index]`
+/// was last given.
+fn resend_uniforms(
+    r: &RendererWebGl,
+    shader_index: usize,
+    replacement: &ShaderProgram,
+) -> Result<(), RendererError> {
+    replacement.use_program(&r.gl);
+    for (uniform_name, value) in r.uniform_caches.borrow()[shader_index].iter() {
+        resend_uniform(&r.gl, replacement, *uniform_name, value)?;
+    }
+    Ok(())
+}
+
+/// Sends a cached uniform value to the currently active program.
+fn resend_uniform(
+    gl: &GL,
+    shader: &ShaderProgram,
+    uniform_name: UniformName,
+    value: &[f32],
+) -> Result<(), RendererError> {
+    let location = Some(shader.get_uniform_location(uniform_name)?);
+    let kind = shader.get_signature().uniform_signatures.iter()
+        .find(|uniform_signature| uniform_signature.name == uniform_name)
+        .map(|uniform_signature| uniform_signature.kind)
+        .ok_or_else(|| RendererError::new(E::R11714, format!("{:?}", uniform_name)))?;
+    match kind {
+        UniformKind::F32 => gl.uniform1fv_with_f32_array(location, value),
+        UniformKind::Mat4 => gl.uniform_matrix4fv_with_f32_array(location, false, value),
+        UniformKind::Sampler2D => gl.uniform1i(location, value[0] as i32), // cached as the texture unit
+        UniformKind::Vec2 => gl.uniform2fv_with_f32_array(location, value),
+        UniformKind::Vec3 => gl.uniform3fv_with_f32_array(location, value),
+        UniformKind::Vec4 => gl.uniform4fv_with_f32_array(location, value),
+    }
+    if SLOWLY_GET_ERROR_COOL_PATH {
+        RendererError::check(gl, E::R33245, &format!("resend_uniform({:?})", uniform_name))?;
+    }
+    Ok(())
 }
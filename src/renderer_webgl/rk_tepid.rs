@@ -1,13 +1,337 @@
 //! A kit of ‘tepid path’ operations for the Renderer.
 
+use web_sys::WebGlRenderingContext as GL;
+
+use crate::error::{ERROR as E,SLOWLY_GET_ERROR_TEPID_PATH};
+use crate::shape::Point3;
+
+use super::{BufferHandle,GlElement,GpuOwner,RenderTarget,RendererError,RendererWebGl,TextureHandle,UniformKind,UniformName};
+use super::gl_element;
+
 
 /// #### A kit of ‘tepid path’ operations for the Renderer.
-/// 
+///
 /// These are used for mutating shader state as the app runs. They are called
 /// fairly often, perhaps once a second on average — but not on every frame.
+/// For example, regenerating nubbin geometry or curve buffers after a slider
+/// has changed.
 #[allow(dead_code)]
 pub struct RkTepid;
 
+#[allow(dead_code)]
 impl RkTepid {
 
+
+    // BUFFERS
+
+    /// Like `RkCool::create_buffer()`, but tells WebGL to expect the buffer to
+    /// be rewritten by `update_buffer()` or `orphan_buffer()`.
+    pub fn create_dynamic_buffer<T: GlElement>(
+        r: &RendererWebGl,
+        target: u32,
+        values: &[T],
+    ) -> Result<BufferHandle, RendererError> {
+        let buffer = r.gl.create_buffer()
+            .ok_or_else(|| RendererError::new(E::R36279, "create_buffer()".into()))?;
+        r.gl.bind_buffer(target, Some(&buffer));
+        gl_element::buffer_data(
+            &r.gl,
+            target,
+            values,
+            GL::DYNAMIC_DRAW, // because the buffer will be rewritten
+        );
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "buffer_data_with_array_buffer_view(..., DYNAMIC_DRAW)")?;
+        }
+        Ok(r.resources.borrow_mut().add_buffer(buffer, GpuOwner::Scene, values.len() * T::BYTES))
+    }
+
+
+    /// Overwrites part of an existing buffer, starting `offset` elements (not
+    /// bytes) in. The buffer keeps its size, so `offset + values.len()` must
+    /// not be more than the number of elements it was created with.
+    pub fn update_buffer<T: GlElement>(
+        r: &RendererWebGl,
+        target: u32,
+        buffer: BufferHandle,
+        offset: usize,
+        values: &[T],
+    ) -> Result<(), RendererError> {
+        let webgl_buffer = r.resources.borrow().buffer(buffer)?;
+        r.gl.bind_buffer(target, Some(&webgl_buffer));
+        gl_element::buffer_sub_data(&r.gl, target, offset, values);
+        if SLOWLY_GET_ERROR_TEPID_PATH { // eg INVALID_VALUE if the range overflows the buffer
+            RendererError::check(&r.gl, E::R36101,
+                &format!("buffer_sub_data_with_i32_and_array_buffer_view(..., {}, ...)", offset * T::BYTES))?;
+        }
+        Ok(())
+    }
+
+
+    /// Replaces all of an existing buffer’s contents, which may change its size.
+    /// The old storage is ‘orphaned’ first, so WebGL can hand the GPU a fresh
+    /// block of VRAM instead of waiting until it has finished drawing the old one.
+    pub fn orphan_buffer<T: GlElement>(
+        r: &RendererWebGl,
+        target: u32,
+        buffer: BufferHandle,
+        values: &[T],
+    ) -> Result<(), RendererError> {
+        let byte_length = values.len() * T::BYTES;
+        let webgl_buffer = r.resources.borrow().buffer(buffer)?;
+        r.gl.bind_buffer(target, Some(&webgl_buffer));
+
+        // Passing a size but no data discards the old storage.
+        r.gl.buffer_data_with_i32(target, byte_length as i32, GL::DYNAMIC_DRAW);
+        gl_element::buffer_sub_data(&r.gl, target, 0, values);
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101,
+                &format!("orphan_buffer(..., {} bytes)", byte_length))?;
+        }
+        r.resources.borrow_mut().resize_buffer(buffer, byte_length)
+    }
+
+
+    /// Shorthand for `create_dynamic_buffer(r, GL::ARRAY_BUFFER, values)`.
+    pub fn create_dynamic_buffer_f32(
+        r: &RendererWebGl,
+        values: &[f32],
+    ) -> Result<BufferHandle, RendererError> {
+        Self::create_dynamic_buffer(r, GL::ARRAY_BUFFER, values)
+    }
+
+
+    /// Shorthand for `update_buffer(r, GL::ARRAY_BUFFER, ...)`.
+    pub fn update_buffer_f32(
+        r: &RendererWebGl,
+        buffer: BufferHandle,
+        offset: usize,
+        values: &[f32],
+    ) -> Result<(), RendererError> {
+        Self::update_buffer(r, GL::ARRAY_BUFFER, buffer, offset, values)
+    }
+
+
+    /// Shorthand for `orphan_buffer(r, GL::ARRAY_BUFFER, ...)`.
+    pub fn orphan_buffer_f32(
+        r: &RendererWebGl,
+        buffer: BufferHandle,
+        values: &[f32],
+    ) -> Result<(), RendererError> {
+        Self::orphan_buffer(r, GL::ARRAY_BUFFER, buffer, values)
+    }
+
+
+    /// Frees a buffer’s VRAM straight away, rather than waiting for the Scene
+    /// to be torn down, eg when a nubbin is removed. Its handle stops working.
+    pub fn delete_buffer(
+        r: &RendererWebGl,
+        buffer: BufferHandle,
+    ) {
+        r.resources.borrow_mut().delete_buffer(&r.gl, buffer);
+    }
+
+
+    /// Frees a texture’s VRAM straight away, eg when JavaScript replaces a
+    /// Scene’s image. Its handle stops working.
+    pub fn delete_texture(
+        r: &RendererWebGl,
+        texture: TextureHandle,
+    ) {
+        r.resources.borrow_mut().delete_texture(&r.gl, texture);
+    }
+
+
+    /// Frees a RenderTarget’s framebuffer, texture and depth buffer, eg when
+    /// the PostChain is rebuilt. Its handles stop working.
+    pub fn delete_render_target(
+        r: &RendererWebGl,
+        render_target: &RenderTarget,
+    ) {
+        let mut resources = r.resources.borrow_mut();
+        resources.delete_framebuffer(&r.gl, render_target.framebuffer);
+        resources.delete_texture(&r.gl, render_target.color);
+        if let Some(depth) = render_target.depth { resources.delete_renderbuffer(&r.gl, depth) }
+    }
+
+
+    /// Copies RGBA pixels from the bound framebuffer, starting at its bottom
+    /// left corner. So the first row in the returned Vec is the bottom row.
+    pub fn read_pixels(
+        r: &RendererWebGl,
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<u8>, RendererError> {
+        let mut pixels = vec![0; (width * height * 4) as usize];
+        r.gl.read_pixels_with_opt_u8_array(
+            0, 0,
+            width as i32, height as i32,
+            GL::RGBA,
+            GL::UNSIGNED_BYTE,
+            Some(&mut pixels),
+        ).map_err(|_| RendererError::new(E::R36318, format!("{}x{}", width, height)))?;
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "read_pixels_with_opt_u8_array()")?;
+        }
+        Ok(pixels)
+    }
+
+
+
+
+    // UNIFORMS
+
+    /// Does nothing if the uniform already has that value.
+    /// NOTE: There is an RkCool equivalent of this function.
+    pub fn set_uniform_f32(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: f32,
+    ) -> Result<(), RendererError> {
+        check_uniform_kind(r, shader_index, uniform_name, UniformKind::F32)?;
+        if r.uniform_is_cached(shader_index, uniform_name, &[value]) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        r.gl.uniform1f(Some(location), value);
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "uniform1f()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &[value]);
+        Ok(())
+    }
+
+
+    /// Tells a sampler2D uniform which texture unit to read, eg 0 for
+    /// `GL::TEXTURE0`. Does nothing if the uniform already has that value.
+    pub fn set_uniform_sampler2d(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        unit: u32,
+    ) -> Result<(), RendererError> {
+        check_uniform_kind(r, shader_index, uniform_name, UniformKind::Sampler2D)?;
+        if r.uniform_is_cached(shader_index, uniform_name, &[unit as f32]) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        r.gl.uniform1i(Some(location), unit as i32);
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "uniform1i()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &[unit as f32]);
+        Ok(())
+    }
+
+
+    /// Does nothing if the uniform already has that value.
+    /// NOTE: There is an RkWarm equivalent of this function.
+    pub fn set_uniform_vec2_f32(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: [f32; 2],
+    ) -> Result<(), RendererError> {
+        check_uniform_kind(r, shader_index, uniform_name, UniformKind::Vec2)?;
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        r.gl.uniform2fv_with_f32_array(Some(location), &value);
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "uniform2fv_with_f32_array()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
+    }
+
+
+    /// Does nothing if the uniform already has that value.
+    /// NOTE: There is an RkWarm equivalent of this function.
+    pub fn set_uniform_mat4_f32(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: [f32; 16],
+    ) -> Result<(), RendererError> {
+        check_uniform_kind(r, shader_index, uniform_name, UniformKind::Mat4)?;
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        r.gl.uniform_matrix4fv_with_f32_array(
+            Some(location),
+            false, // transpose
+            &value,
+        );
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "uniform_matrix4fv_with_f32_array()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
+    }
+
+
+    /// Does nothing if the uniform already has that value.
+    /// NOTE: There is an RkWarm equivalent of this function.
+    pub fn set_uniform_vec4_f32(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: [f32; 4],
+    ) -> Result<(), RendererError> {
+        check_uniform_kind(r, shader_index, uniform_name, UniformKind::Vec4)?;
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        r.gl.uniform4fv_with_f32_array(Some(location), &value);
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "uniform4fv_with_f32_array()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
+    }
+
+
+    /// Does nothing if the uniform already has that value.
+    /// NOTE: There is an RkWarm equivalent of this function.
+    pub fn set_uniform_point3_f32(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: Point3,
+    ) -> Result<(), RendererError> {
+        check_uniform_kind(r, shader_index, uniform_name, UniformKind::Vec3)?;
+        let value = [value.x, value.y, value.z];
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
+        let location = r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        r.gl.uniform3fv_with_f32_array(Some(location), &value);
+        if SLOWLY_GET_ERROR_TEPID_PATH {
+            RendererError::check(&r.gl, E::R36101, "uniform3fv_with_f32_array()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
+    }
+
+}
+
+
+
+
+// HELPERS
+
+/// Fails if the uniform’s UniformSignature has a different kind, eg when
+/// `set_uniform_vec4_f32()` is called for a mat4 uniform.
+fn check_uniform_kind(
+    r: &RendererWebGl,
+    shader_index: usize,
+    uniform_name: UniformName,
+    expected: UniformKind,
+) -> Result<(), RendererError> {
+    let signature = r.shaders[shader_index].get_signature();
+    match signature.uniform_signatures.iter().find(|u| u.name == uniform_name) {
+        Some(uniform_signature) if uniform_signature.kind == expected => Ok(()),
+        Some(uniform_signature) => Err(RendererError::new(E::R36250,
+            format!("{:?} is {:?}, not {:?}", uniform_name, uniform_signature.kind, expected))),
+        None => Err(RendererError::new(E::R11714,
+            format!("{:?} does not use UniformName::{:?}", signature.name, uniform_name))),
+    }
 }
@@ -1,9 +1,9 @@
 //! A kit of ‘warm path’ operations for the Renderer.
 
 use web_sys::WebGlRenderingContext as GL;
-use crate::error::SLOWLY_GET_ERROR_WARM_PATH;
+use crate::error::{ERROR as E,SLOWLY_GET_ERROR_WARM_PATH};
 use crate::shape::Point3;
-use super::{RendererWebGl,UniformName};
+use super::{BlendMode,CullFace,DepthTest,IndexBuffer,PipelineState,RenderTarget,RendererError,RendererWebGl,TextureHandle,UniformName};
 
 pub struct RkWarm;
 
@@ -23,29 +23,159 @@ impl RkWarm {
     /// each render phase.
     pub fn clear(
         r: &RendererWebGl,
-    ) {
+    ) -> Result<(), RendererError> {
         r.gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("clear() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "clear()")?;
         }
+        Ok(())
+    }
+
+
+    /// Sends only the parts of `pipeline_state` which differ from the state
+    /// WebGL is already in, and does nothing at all if none do.  
+    /// NOTE: `RkCold::init_pipeline()` sends every part, unconditionally.
+    pub fn set_pipeline_state(
+        r: &RendererWebGl,
+        pipeline_state: &PipelineState,
+    ) -> Result<(), RendererError> {
+        let previous = match r.switch_pipeline_state(pipeline_state) {
+            Some(previous) => previous,
+            None => return Ok(()),
+        };
+        let gl = &r.gl;
+
+        if pipeline_state.depth_test != previous.depth_test {
+            match pipeline_state.depth_test {
+                DepthTest::Off => gl.disable(GL::DEPTH_TEST),
+                depth_test => {
+                    if previous.depth_test == DepthTest::Off { gl.enable(GL::DEPTH_TEST) }
+                    gl.depth_func(depth_test.to_gl());
+                },
+            }
+        }
+        if pipeline_state.blend_mode != previous.blend_mode {
+            match pipeline_state.blend_mode {
+                BlendMode::Opaque => gl.disable(GL::BLEND),
+                blend_mode => {
+                    if previous.blend_mode == BlendMode::Opaque { gl.enable(GL::BLEND) }
+                    let (source, destination) = blend_mode.to_gl_factors();
+                    gl.blend_func(source, destination);
+                },
+            }
+        }
+        if pipeline_state.cull_face != previous.cull_face {
+            match pipeline_state.cull_face {
+                CullFace::Off => gl.disable(GL::CULL_FACE),
+                cull_face => {
+                    if previous.cull_face == CullFace::Off { gl.enable(GL::CULL_FACE) }
+                    gl.cull_face(if cull_face == CullFace::Back { GL::BACK } else { GL::FRONT });
+                },
+            }
+        }
+        if pipeline_state.clear_color != previous.clear_color {
+            let [red, green, blue, alpha] = pipeline_state.clear_color;
+            gl.clear_color(red, green, blue, alpha);
+        }
+        if pipeline_state.scissor != previous.scissor {
+            match pipeline_state.scissor {
+                None => gl.disable(GL::SCISSOR_TEST),
+                Some([x, y, width, height]) => {
+                    if previous.scissor.is_none() { gl.enable(GL::SCISSOR_TEST) }
+                    gl.scissor(x, y, width, height);
+                },
+            }
+        }
+        if pipeline_state.color_mask != previous.color_mask {
+            let [red, green, blue, alpha] = pipeline_state.color_mask;
+            gl.color_mask(red, green, blue, alpha);
+        }
+
+        if SLOWLY_GET_ERROR_WARM_PATH {
+            RendererError::check(&r.gl, E::R44101, "set_pipeline_state()")?;
+        }
+        Ok(())
+    }
+
+
+    /// Directs the following draw calls into a RenderTarget, or back to the
+    /// canvas if `None`, and sets the viewport to match.
+    pub fn bind_render_target(
+        r: &RendererWebGl,
+        render_target: Option<&RenderTarget>,
+    ) -> Result<(), RendererError> {
+        let size = match render_target {
+            Some(render_target) => {
+                let framebuffer = r.resources.borrow().framebuffer(render_target.framebuffer)?;
+                r.gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&framebuffer));
+                [render_target.width, render_target.height]
+            },
+            None => {
+                r.gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+                [r.gl.drawing_buffer_width(), r.gl.drawing_buffer_height()]
+            },
+        };
+        r.framebuffer_size.set(size);
+        r.gl.viewport(0, 0, size[0], size[1]);
+        if SLOWLY_GET_ERROR_WARM_PATH {
+            RendererError::check(&r.gl, E::R44101, "bind_framebuffer()")?;
+        }
+        Ok(())
+    }
+
+
+    /// Draws into part of the bound framebuffer, eg one pane of a quad view.
+    /// `viewport` is `[x, y, width, height]` in pixels, from the bottom left.
+    /// Clip space is stretched to fit, but nothing outside is protected from
+    /// drawing or clearing — for that, set a scissor in the PipelineState.
+    pub fn set_viewport(
+        r: &RendererWebGl,
+        viewport: [i32; 4],
+    ) -> Result<(), RendererError> {
+        let [x, y, width, height] = viewport;
+        r.gl.viewport(x, y, width, height);
+        if SLOWLY_GET_ERROR_WARM_PATH {
+            RendererError::check(&r.gl, E::R44101, "viewport()")?;
+        }
+        Ok(())
+    }
+
+
+    /// Makes a texture readable by sampler2D uniforms set to `unit`.
+    /// Does nothing if that texture is already bound to that unit.
+    pub fn bind_texture(
+        r: &RendererWebGl,
+        unit: u32, // eg 0 for GL::TEXTURE0
+        texture: TextureHandle,
+    ) -> Result<(), RendererError> {
+        if ! r.switch_texture_binding(unit, texture)? { return Ok(()) }
+        let webgl_texture = r.resources.borrow().texture(texture)?;
+        if r.active_texture_unit.get() != unit {
+            r.gl.active_texture(GL::TEXTURE0 + unit);
+            r.active_texture_unit.set(unit);
+        }
+        r.gl.bind_texture(GL::TEXTURE_2D, Some(&webgl_texture));
+        if SLOWLY_GET_ERROR_WARM_PATH {
+            RendererError::check(&r.gl, E::R44101, "bind_texture()")?;
+        }
+        Ok(())
     }
 
 
     /// Tells WebGL to stop using its current shader program, and start using a
     /// different one.  
+    /// Does nothing if that shader is already active.  
     /// NOTE: There is an RkCool equivalent of this function.
-    /// 
-    /// @TODO maybe do nothing if we know that shader is already active?
     pub fn use_shader(
         r: &RendererWebGl,
         index: usize,
-    ) {
+    ) -> Result<(), RendererError> {
+        if ! r.switch_current_shader(index) { return Ok(()) }
         r.shaders[index].use_program(&r.gl);
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("use_program() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "use_program()")?;
         }
+        Ok(())
     }
 
 
@@ -55,28 +185,35 @@ impl RkWarm {
         mode: u32, // eg WebGLRenderingContext::POINTS or ::TRIANGLES
         first: i32,
         count: i32,
-    ) {
+    ) -> Result<(), RendererError> {
         r.gl.draw_arrays(mode, first, count);
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("draw_arrays() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "draw_arrays()")?;
         }
+        Ok(())
     }
 
 
-    /// @TODO describe
+    /// Draws `count` vertices, in the order given by `indices`, starting
+    /// `first` indices (not bytes) in. The index buffer must be bound as the
+    /// `GL::ELEMENT_ARRAY_BUFFER`.
     pub fn draw_elements(
         r: &RendererWebGl,
         mode: u32, // eg WebGLRenderingContext::POINTS or ::TRIANGLES
-        offset: i32, // in bytes, so must be a multiple of the size of the given type
+        indices: &IndexBuffer,
+        first: i32,
         count: i32,
-        type_: u32, // eg WebGLRenderingContext::UNSIGNED_SHORT
-    ) {
-        r.gl.draw_elements_with_i32(mode, count, type_, offset);
+    ) -> Result<(), RendererError> {
+        r.gl.draw_elements_with_i32(
+            mode,
+            count,
+            indices.index_type, // eg WebGLRenderingContext::UNSIGNED_SHORT
+            first * indices.bytes_per_index(), // offset, in bytes
+        );
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("draw_elements_with_i32() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "draw_elements_with_i32()")?;
         }
+        Ok(())
     }
 
 
@@ -87,7 +224,7 @@ impl RkWarm {
         first: u32,
         count: u32,
         primcount: u32,
-    ) {
+    ) -> Result<(), RendererError> {
         r.ext_instanced_arrays.drawArraysInstancedANGLE(
             mode,
             first, // starting index in the array of vector points
@@ -95,171 +232,250 @@ impl RkWarm {
             primcount, // number of instances
         );
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("drawArraysInstancedANGLE() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "drawArraysInstancedANGLE()")?;
         }
+        Ok(())
     }
 
 
-    /// @TODO describe
+    /// Like `draw_elements()`, but draws `primcount` instances of the set of
+    /// elements.
     pub fn draw_instances(
         r: &RendererWebGl,
         mode: u32, // eg WebGLRenderingContext::POINTS or ::TRIANGLES
-        offset: i32, // in bytes, so must be a multiple of the size of the given type
+        indices: &IndexBuffer,
+        first: i32, // the first index to draw — not bytes
         count: i32, // the number of elements to be rendered
         primcount: u32, // the number of instances of the set of elements to execute
-    ) {
+    ) -> Result<(), RendererError> {
         r.ext_instanced_arrays.drawElementsInstancedANGLE(
             mode,
             count,
-            GL::UNSIGNED_SHORT, // type
-            offset,
+            indices.index_type, // eg WebGLRenderingContext::UNSIGNED_SHORT
+            first * indices.bytes_per_index(), // offset, in bytes
             primcount
         );
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = r.gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("drawElementsInstancedANGLE() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "drawElementsInstancedANGLE()")?;
         }
+        Ok(())
     }
 
 
-    /// @TODO there should be an RkTepid equivalent of this function.
+    /// Does nothing if the uniform already has that value.  
+    /// While a still image renders, the ProjectionMatrix is narrowed to the
+    /// current tile — see `RendererWebGl::clip_tile`.  
+    /// NOTE: There is an RkTepid equivalent of this function.
     pub fn set_uniform_mat4_f32(
         r: &RendererWebGl,
         shader_index: usize,
         uniform_name: UniformName,
         value: [f32; 16],
-    ) {
+    ) -> Result<(), RendererError> {
+        let value = match (uniform_name, r.clip_tile.get()) {
+            (UniformName::ProjectionMatrix, Some(clip_tile)) => tile_projection(value, clip_tile),
+            _ => value,
+        };
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
         let gl = &r.gl;
 
         let uniform_location =
-            r.shaders[shader_index].get_uniform_location(uniform_name);
+            r.shaders[shader_index].get_uniform_location(uniform_name)?;
 
         match uniform_name {
             // iu_*
             UniformName::Angle => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::Bulge => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::Lean => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::Rise => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::Scale => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::Tilt => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
 
             // Matrices.
             UniformName::ProjectionMatrix => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::ViewMatrix => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
             UniformName::ModelMatrix => gl.uniform_matrix4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 false, // transpose
                 &value,
             ),
 
             // Error.
-            _ => panic!("uniform name '{:?}' not recognised", uniform_name),
+            _ => return Err(RendererError::new(E::R44250,
+                format!("uniform name '{:?}' not recognised", uniform_name))),
         }
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("uniform_matrix4fv_with_f32_array() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "uniform_matrix4fv_with_f32_array()")?;
         }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
     }
 
 
-    /// @TODO there should be an RkTepid equivalent of this function.
+    /// Does nothing if the uniform already has that value.  
+    /// NOTE: There is an RkTepid equivalent of this function.
     pub fn set_uniform_vec4_f32(
         r: &RendererWebGl,
         shader_index: usize,
         uniform_name: UniformName,
         value: [f32; 4],
-    ) {
+    ) -> Result<(), RendererError> {
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
         let gl = &r.gl;
 
         let uniform_location =
-            r.shaders[shader_index].get_uniform_location(uniform_name);
+            r.shaders[shader_index].get_uniform_location(uniform_name)?;
 
         match uniform_name {
             UniformName::Slidermix => gl.uniform4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 &value,
             ),
             UniformName::Timermix => gl.uniform4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 &value,
             ),
             UniformName::Quaternion => gl.uniform4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 &value,
             ),
             UniformName::QuaternionX => gl.uniform4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 &value,
             ),
             UniformName::QuaternionY => gl.uniform4fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
+                &value,
+            ),
+            UniformName::PostFrame => gl.uniform4fv_with_f32_array(
+                Some(uniform_location),
                 &value,
             ),
-            _ => panic!("uniform name '{:?}' not recognised", uniform_name),
+            UniformName::PostParams => gl.uniform4fv_with_f32_array(
+                Some(uniform_location),
+                &value,
+            ),
+            _ => return Err(RendererError::new(E::R44250,
+                format!("uniform name '{:?}' not recognised", uniform_name))),
         }
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("uniform_matrix4fv_with_f32_array() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "uniform4fv_with_f32_array()")?;
         }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
     }
 
 
-    /// @TODO there should be an RkTepid equivalent of this function.
+    /// Does nothing if the uniform already has that value.  
+    /// NOTE: There is an RkTepid equivalent of this function.
+    pub fn set_uniform_vec2_f32(
+        r: &RendererWebGl,
+        shader_index: usize,
+        uniform_name: UniformName,
+        value: [f32; 2],
+    ) -> Result<(), RendererError> {
+        if r.uniform_is_cached(shader_index, uniform_name, &value) { return Ok(()) }
+        let gl = &r.gl;
+
+        let uniform_location =
+            r.shaders[shader_index].get_uniform_location(uniform_name)?;
+
+        match uniform_name {
+            UniformName::Resolution => gl.uniform2fv_with_f32_array(
+                Some(uniform_location),
+                &value,
+            ),
+            _ => return Err(RendererError::new(E::R44250,
+                format!("uniform name '{:?}' not recognised", uniform_name))),
+        }
+        if SLOWLY_GET_ERROR_WARM_PATH {
+            RendererError::check(&r.gl, E::R44101, "uniform2fv_with_f32_array()")?;
+        }
+        r.cache_uniform(shader_index, uniform_name, &value);
+        Ok(())
+    }
+
+
+    /// Does nothing if the uniform already has that value.  
+    /// NOTE: There is an RkTepid equivalent of this function.
     pub fn set_uniform_point3_f32(
         r: &RendererWebGl,
         shader_index: usize,
         uniform_name: UniformName,
         value: Point3,
-    ) {
+    ) -> Result<(), RendererError> {
+        if r.uniform_is_cached(shader_index, uniform_name, &[value.x, value.y, value.z]) { return Ok(()) }
         let gl = &r.gl;
 
         let uniform_location =
-            r.shaders[shader_index].get_uniform_location(uniform_name);
+            r.shaders[shader_index].get_uniform_location(uniform_name)?;
 
         match uniform_name {
             UniformName::Placement => gl.uniform3fv_with_f32_array(
-                Some(&uniform_location),
+                Some(uniform_location),
                 &[value.x, value.y, value.z],
             ),
-            _ => panic!("uniform name '{:?}' not recognised", uniform_name),
+            _ => return Err(RendererError::new(E::R44250,
+                format!("uniform name '{:?}' not recognised", uniform_name))),
         }
         if SLOWLY_GET_ERROR_WARM_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("uniform_matrix4fv_with_f32_array() {}", e) }
+            RendererError::check(&r.gl, E::R44101, "uniform3fv_with_f32_array()")?;
         }
+        r.cache_uniform(shader_index, uniform_name, &[value.x, value.y, value.z]);
+        Ok(())
     }
 
 }
+
+
+
+
+// HELPERS
+
+/// Scales and offsets the x and y rows of a column-major projection matrix, so
+/// that the part of clip space covered by a tile fills the whole viewport.
+fn tile_projection(
+    projection: [f32; 16],
+    clip_tile: [f32; 4],
+) -> [f32; 16] {
+    let [scale_x, scale_y, offset_x, offset_y] = clip_tile;
+    let mut tiled = projection;
+    for column in 0..4 {
+        let w = projection[column * 4 + 3];
+        tiled[column * 4] = projection[column * 4] * scale_x + w * offset_x;
+        tiled[column * 4 + 1] = projection[column * 4 + 1] * scale_y + w * offset_y;
+    }
+    tiled
+}
@@ -0,0 +1,28 @@
+//! GLSL chunks, which vertex and fragment shaders can `#include`.
+
+// Each chunk is baked into the binary by `include_str!()`, so an `#include`
+// is resolved by name from this table, never from the filesystem.
+// `SkCool::preprocess()` gives each chunk a `#line` source string number: its
+// position in the table plus one. Number 0 is the shader’s own source.
+const GLSL_CHUNKS: [(&str, &str); 2] = [
+    ("palette.glsl", include_str!("palette.glsl")),
+    ("quat.glsl", include_str!("quat.glsl")),
+];
+
+/// Returns the source string number and GLSL for eg `#include "quat.glsl"`,
+/// or None if there’s no such chunk.
+pub fn glsl_chunk(
+    name: &str,
+) -> Option<(u32, &'static str)> {
+    GLSL_CHUNKS.iter()
+        .position(|(chunk_name, _)| *chunk_name == name)
+        .map(|index| (index as u32 + 1, GLSL_CHUNKS[index].1))
+}
+
+/// Returns the name and GLSL of the chunk with the given source string number,
+/// or None if the number is 0 (the shader’s own source) or unknown.
+pub fn glsl_chunk_by_number(
+    number: u32,
+) -> Option<(&'static str, &'static str)> {
+    GLSL_CHUNKS.get((number as usize).checked_sub(1)?).copied()
+}
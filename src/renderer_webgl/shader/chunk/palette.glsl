@@ -0,0 +1,8 @@
+// Colour helpers. Include with `#include "palette.glsl"`.
+
+// Converts hue, saturation and value, each from 0.0 to 1.0, to RGB. Hues wrap
+// around, so a hue of 1.25 is the same as 0.25.
+vec3 hsv2rgb(vec3 c) {
+    vec3 p = abs(fract(c.xxx + vec3(1.0, 2.0 / 3.0, 1.0 / 3.0)) * 6.0 - 3.0);
+    return c.z * mix(vec3(1.0), clamp(p - 1.0, 0.0, 1.0), c.y);
+}
@@ -0,0 +1,32 @@
+// Quaternion helpers. Include with `#include "quat.glsl"`.
+
+// Create a quaternion from an axis and angle.
+vec4 quatFromAxisAngle(vec3 axis, float angle) {
+    float halfAngle = angle * 0.5;
+    return vec4(axis.xyz * sin(halfAngle), cos(halfAngle));
+}
+
+// Apply the quaternion (q) to a vector (v).
+vec3 rotateVector(vec4 q, vec3 v) {
+    return v + 2.0 * cross(q.xyz, cross(q.xyz, v) + q.w * v);
+}
+
+// Rotates a point about the x-axis.
+vec3 rotateAboutXAxis(vec3 pt, float angle) {
+    float ha = angle * 0.5; // half angle
+    vec3 quatXAxis = vec3(sin(ha), 0.0, 0.0); // xyz part of the quaternion
+    return pt + 2.0 * cross(
+        quatXAxis,
+        cross(quatXAxis, pt) + cos(ha) * pt // cos(ha) is the w part of the quat
+    );
+}
+
+// Rotates a point about the y-axis.
+vec3 rotateAboutYAxis(vec3 pt, float angle) {
+    float ha = angle * 0.5; // half angle
+    vec3 quatYAxis = vec3(0.0, sin(ha), 0.0); // xyz part of the quaternion
+    return pt + 2.0 * cross(
+        quatYAxis,
+        cross(quatYAxis, pt) + cos(ha) * pt // cos(ha) is the w part of the quat
+    );
+}
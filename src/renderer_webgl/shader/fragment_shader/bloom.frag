@@ -0,0 +1,29 @@
+#version 100
+precision mediump float;
+
+// A single-pass bloom: the brightest parts of sixteen nearby samples, in two
+// rings, are blurred together and added back to the image.
+
+uniform vec4 u_params; // threshold, intensity, radius (in pixels), unused
+uniform vec2 u_resolution;
+uniform sampler2D u_source;
+
+vec3 brightPart(vec2 uv) {
+  vec3 rgb = texture2D(u_source, uv).rgb;
+  return max(rgb - u_params.x, 0.);
+}
+
+void main(void) {
+  vec2 texel = 1. / u_resolution;
+  vec2 uv = gl_FragCoord.xy * texel;
+  vec3 rgb = texture2D(u_source, uv).rgb;
+
+  vec3 glow = vec3(0.);
+  for (int i = 0; i < 8; i++) {
+    float angle = float(i) * 0.785398; // 45°
+    vec2 offset = vec2(cos(angle), sin(angle)) * u_params.z * texel;
+    glow += brightPart(uv + offset * 0.5) * 0.6;
+    glow += brightPart(uv + offset) * 0.4;
+  }
+  gl_FragColor = vec4(rgb + glow / 8. * u_params.y, 1.);
+}
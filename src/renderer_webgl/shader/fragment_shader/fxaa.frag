@@ -0,0 +1,45 @@
+#version 100
+precision mediump float;
+
+// Fast approximate anti-aliasing, after Timothy Lottes’ FXAA. Finds the local
+// edge direction from the luma of the four diagonal neighbours, then blurs
+// along it.
+
+uniform vec4 u_params; // reduce_min, reduce_mul, span_max (in pixels), unused
+uniform vec2 u_resolution;
+uniform sampler2D u_source;
+
+void main(void) {
+  vec2 texel = 1. / u_resolution;
+  vec2 uv = gl_FragCoord.xy * texel;
+  vec3 luma = vec3(0.299, 0.587, 0.114);
+
+  vec3 rgb_m = texture2D(u_source, uv).rgb;
+  float luma_nw = dot(texture2D(u_source, uv + vec2(-1., -1.) * texel).rgb, luma);
+  float luma_ne = dot(texture2D(u_source, uv + vec2( 1., -1.) * texel).rgb, luma);
+  float luma_sw = dot(texture2D(u_source, uv + vec2(-1.,  1.) * texel).rgb, luma);
+  float luma_se = dot(texture2D(u_source, uv + vec2( 1.,  1.) * texel).rgb, luma);
+  float luma_m = dot(rgb_m, luma);
+  float luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+  float luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+  // The edge runs across the steepest change in luma.
+  vec2 dir = vec2(
+    -((luma_nw + luma_ne) - (luma_sw + luma_se)),
+      (luma_nw + luma_sw) - (luma_ne + luma_se)
+  );
+  float dir_reduce = max((luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * u_params.y, u_params.x);
+  float rcp_dir_min = 1. / (min(abs(dir.x), abs(dir.y)) + dir_reduce);
+  dir = clamp(dir * rcp_dir_min, -u_params.z, u_params.z) * texel;
+
+  vec3 rgb_a = 0.5 * (
+    texture2D(u_source, uv + dir * (1. / 3. - 0.5)).rgb +
+    texture2D(u_source, uv + dir * (2. / 3. - 0.5)).rgb);
+  vec3 rgb_b = rgb_a * 0.5 + 0.25 * (
+    texture2D(u_source, uv + dir * -0.5).rgb +
+    texture2D(u_source, uv + dir * 0.5).rgb);
+
+  // If the wider blur strays outside the local luma range, it crossed another edge.
+  float luma_b = dot(rgb_b, luma);
+  gl_FragColor = vec4((luma_b < luma_min || luma_b > luma_max) ? rgb_a : rgb_b, 1.);
+}
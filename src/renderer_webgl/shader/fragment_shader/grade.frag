@@ -0,0 +1,18 @@
+#version 100
+precision mediump float;
+
+uniform vec4 u_params; // exposure (in stops), contrast, saturation, warmth
+uniform vec2 u_resolution;
+uniform sampler2D u_source;
+
+void main(void) {
+  vec3 rgb = texture2D(u_source, gl_FragCoord.xy / u_resolution).rgb;
+
+  rgb *= exp2(u_params.x);
+  rgb = (rgb - 0.5) * u_params.y + 0.5;
+  float grey = dot(rgb, vec3(0.299, 0.587, 0.114));
+  rgb = mix(vec3(grey), rgb, u_params.z);
+  rgb += vec3(u_params.w, 0., -u_params.w) * 0.1; // warmer is redder, cooler is bluer
+
+  gl_FragColor = vec4(clamp(rgb, 0., 1.), 1.);
+}
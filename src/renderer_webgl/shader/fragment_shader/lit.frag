@@ -0,0 +1,47 @@
+#version 100
+precision mediump float;
+
+uniform vec3 u_ambient;
+uniform vec3 u_light_color;
+uniform float u_lighting; // 1.0 for lit, 0.0 for flat vertex colours
+uniform sampler2D u_petal_texture;
+uniform sampler2D u_seed_texture;
+
+varying vec3 vColor;
+varying vec4 vDebug; // unshaded, depth view, overdraw view, depth from 0.0 to 1.0
+varying vec3 vLightDirection;
+varying vec3 vNormal;
+varying float vSeed;
+varying vec2 vUv;
+varying vec3 vViewPosition;
+varying float vWire; // 1.0 while edges are drawn over solid shading, else 0.0
+
+void main(void) {
+  // Tint the vertex colour with petal or seed imagery. A white texture leaves
+  // it unchanged.
+  vec3 petal = texture2D(u_petal_texture, vUv).rgb;
+  vec3 seed = texture2D(u_seed_texture, vUv).rgb;
+  vec3 color = vColor * mix(petal, seed, vSeed);
+
+  vec3 n = normalize(vNormal);
+  vec3 l = normalize(vLightDirection);
+  vec3 v = normalize(-vViewPosition); // towards the camera
+
+  // Lambert diffuse, plus a Blinn-Phong highlight from the half vector.
+  float diffuse = max(dot(n, l), 0.);
+  float specular = pow(max(dot(n, normalize(l + v)), 0.), 32.) * step(0., dot(n, l));
+  vec3 lit = color * (u_ambient + u_light_color * diffuse) + u_light_color * specular * 0.25;
+
+  vec3 rgb = mix(color, lit, u_lighting);
+
+  // Debug views. Normal colours are unshaded. Depth runs from white at the
+  // nearest surface to black at the farthest. Overdraw adds a dim orange for
+  // each fragment, so the most-drawn pixels glow yellow.
+  rgb = mix(rgb, vColor, vDebug.x);
+  rgb = mix(rgb, vec3(1. - vDebug.w), vDebug.y);
+  rgb = mix(rgb, vec3(0.12, 0.05, 0.02), vDebug.z);
+
+  // Edges drawn over solid shading are flat white, so they read against any
+  // colour scheme.
+  gl_FragColor = vec4(mix(rgb, vec3(1.), vWire), 1.);
+}
@@ -17,3 +17,23 @@ pub fn rainbow_frag() -> &'static str {
 pub fn passthru_frag() -> &'static str {
     include_str!("passthru.frag")
 }
+
+pub fn lit_frag() -> &'static str {
+    include_str!("lit.frag")
+}
+
+pub fn bloom_frag() -> &'static str {
+    include_str!("bloom.frag")
+}
+
+pub fn fxaa_frag() -> &'static str {
+    include_str!("fxaa.frag")
+}
+
+pub fn grade_frag() -> &'static str {
+    include_str!("grade.frag")
+}
+
+pub fn vignette_frag() -> &'static str {
+    include_str!("vignette.frag")
+}
@@ -0,0 +1,19 @@
+#version 100
+precision mediump float;
+
+uniform vec4 u_frame; // x and y of this framebuffer in the whole image, and the image’s width and height, in pixels
+uniform vec4 u_params; // strength, radius, softness, unused
+uniform vec2 u_resolution;
+uniform sampler2D u_source;
+
+void main(void) {
+  vec2 uv = gl_FragCoord.xy / u_resolution;
+  vec3 rgb = texture2D(u_source, uv).rgb;
+
+  // Measure from the centre of the whole image, not of this tile, in units of
+  // image height, so the falloff is round.
+  vec2 image_uv = (gl_FragCoord.xy + u_frame.xy) / u_frame.zw;
+  vec2 offset = (image_uv - 0.5) * vec2(u_frame.z / u_frame.w, 1.);
+  float falloff = smoothstep(u_params.y, u_params.y - u_params.z, length(offset));
+  gl_FragColor = vec4(rgb * mix(1. - u_params.x, 1., falloff), 1.);
+}
@@ -0,0 +1,223 @@
+//! One problem from a WebGL shader info log, tied back to the GLSL source.
+
+use std::fmt;
+use super::{glsl_chunk_by_number,ShaderProgramName};
+
+/// How many source lines to show above and below the offending line.
+const CONTEXT_LINES: u32 = 2;
+
+
+/// #### One problem from a WebGL shader info log, tied back to the GLSL source.
+///
+/// Drivers report compile errors as plain text. ANGLE (Chrome, Edge, Firefox
+/// on Windows) writes `ERROR: 0:42: 'foo' : undeclared identifier`, and Mesa
+/// (Firefox on Linux) writes `0:42(10): error: 'foo' undeclared`. This parses
+/// both, so that a failing shader can be reported with its source in view.
+#[derive(Clone,Debug,PartialEq)]
+pub struct ShaderDiagnostic {
+    /// Which shader program was being built, eg `ShaderProgramName::FurlBasic`.
+    pub shader_name: ShaderProgramName,
+    /// "vertex" or "fragment".
+    pub stage: &'static str,
+    /// "error" or "warning".
+    pub severity: &'static str,
+    /// The `#include`d chunk which `line` is in, eg "quat.glsl", or None if
+    /// it’s in the shader’s own source.
+    pub chunk: Option<&'static str>,
+    /// The 1-based GLSL line number, or None if the driver did not give one.
+    pub line: Option<u32>,
+    /// The 1-based column, if the driver gave one (Mesa does, ANGLE doesn’t).
+    pub column: Option<u32>,
+    /// The driver’s description of the problem.
+    pub message: String,
+    /// The offending line and its neighbours, as (line number, text) pairs.
+    pub excerpt: Vec<(u32, String)>,
+}
+
+impl ShaderDiagnostic {
+
+    /// Parses every diagnostic from a `gl.get_shader_info_log()` string.
+    /// Lines which are not in a recognised format are kept, without a line
+    /// number, so that nothing the driver said gets lost.  
+    /// `source` is the GLSL before `SkCool::preprocess()`, whose `#line`
+    /// directives make the driver count lines in it, or in a chunk.
+    pub fn parse_info_log(
+        shader_name: ShaderProgramName,
+        stage: &'static str,
+        info_log: &str,
+        source: &str,
+    ) -> Vec<Self> {
+        let mut diagnostics = vec![];
+
+        for raw in info_log.lines() {
+            let raw = raw.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+            if raw.is_empty() { continue }
+
+            // ANGLE puts the severity first, eg "ERROR: 0:42: ...".
+            let (mut severity, rest) = if let Some(rest) = raw.strip_prefix("ERROR:") {
+                ("error", rest.trim_start())
+            } else if let Some(rest) = raw.strip_prefix("WARNING:") {
+                ("warning", rest.trim_start())
+            } else {
+                ("error", raw)
+            };
+
+            let (number, line, column, message) = match parse_location(rest) {
+                Some((number, line, column, message)) => (number, Some(line), column, message),
+                None => {
+                    // Skip ANGLE’s summary, eg "2 compilation errors. No code generated."
+                    if rest.contains("compilation error") { continue }
+                    (0, None, None, rest)
+                },
+            };
+            let (chunk, text) = match glsl_chunk_by_number(number) {
+                Some((name, glsl)) => (Some(name), glsl),
+                None => (None, source),
+            };
+
+            // Mesa puts the severity after the location, eg "0:42(10): error: ...".
+            let message = if let Some(message) = message.strip_prefix("error:") {
+                message.trim_start()
+            } else if let Some(message) = message.strip_prefix("warning:") {
+                severity = "warning";
+                message.trim_start()
+            } else {
+                message
+            };
+
+            diagnostics.push(Self {
+                shader_name,
+                stage,
+                severity,
+                chunk,
+                line,
+                column,
+                message: message.into(),
+                excerpt: line.map_or(vec![], |line| excerpt(text, line)),
+            });
+        }
+        diagnostics
+    }
+}
+
+/// Eg:
+///     FurlBasic vertex shader, line 42, column 10: error: 'foo' undeclared
+///         40 | void main() {
+///         41 |     vec4 q = u_quaternion_x;
+///     >   42 |     gl_Position = foo;
+///         43 | }
+impl fmt::Display for ShaderDiagnostic {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter,
+    ) -> fmt::Result {
+        write!(f, "{:?} {} shader", self.shader_name, self.stage)?;
+        if let Some(chunk) = self.chunk { write!(f, ", {}", chunk)? }
+        if let Some(line) = self.line { write!(f, ", line {}", line)? }
+        if let Some(column) = self.column { write!(f, ", column {}", column)? }
+        write!(f, ": {}: {}", self.severity, self.message)?;
+        for (number, text) in self.excerpt.iter() {
+            let marker = if Some(*number) == self.line { ">" } else { " " };
+            write!(f, "\n{} {:>4} | {}", marker, number, text)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+
+// HELPERS
+
+/// Splits "0:42: message" or "0:42(10): message" into the source string
+/// number, the line, the optional column, and the message. WebGL only has one
+/// source string, but `#line` directives give each chunk its own number.
+fn parse_location(
+    text: &str,
+) -> Option<(u32, u32, Option<u32>, &str)> {
+    let (number, rest) = text.split_once(':')?;
+    let number = number.trim().parse::<u32>().ok()?;
+
+    let (location, message) = rest.split_once(':')?;
+    let location = location.trim();
+    let (line, column) = match location.split_once('(') {
+        Some((line, column)) => (line, Some(column.strip_suffix(')')?.parse::<u32>().ok()?)),
+        None => (location, None),
+    };
+    Some((number, line.parse::<u32>().ok()?, column, message.trim()))
+}
+
+/// Returns the given 1-based line, and up to CONTEXT_LINES either side of it.
+fn excerpt(
+    source: &str,
+    line: u32,
+) -> Vec<(u32, String)> {
+    let source_lines: Vec<&str> = source.lines().collect();
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line + CONTEXT_LINES).min(source_lines.len() as u32);
+    (first..=last)
+        .map(|number| (number, source_lines[number as usize - 1].into()))
+        .collect()
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "precision mediump float;\nuniform vec4 u_color;\nvoid main() {\n    gl_FragColor = foo;\n}\n";
+
+    #[test]
+    fn parses_angle_format() {
+        let diagnostics = ShaderDiagnostic::parse_info_log(ShaderProgramName::FurlBasic, "fragment",
+            "ERROR: 0:4: 'foo' : undeclared identifier\nERROR: 1 compilation errors.  No code generated.\n\0", SOURCE);
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, "error");
+        assert_eq!(diagnostic.chunk, None);
+        assert_eq!(diagnostic.line, Some(4));
+        assert_eq!(diagnostic.column, None);
+        assert_eq!(diagnostic.message, "'foo' : undeclared identifier");
+        assert_eq!(diagnostic.excerpt.first(), Some(&(2, "uniform vec4 u_color;".into())));
+        assert_eq!(diagnostic.excerpt.last(), Some(&(5, "}".into())));
+    }
+
+    #[test]
+    fn parses_mesa_format() {
+        let diagnostics = ShaderDiagnostic::parse_info_log(ShaderProgramName::FurlBasic, "fragment",
+            "0:4(20): error: `foo' undeclared\n0:2(1): warning: unused uniform\n", SOURCE);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, "error");
+        assert_eq!(diagnostics[0].line, Some(4));
+        assert_eq!(diagnostics[0].column, Some(20));
+        assert_eq!(diagnostics[0].message, "`foo' undeclared");
+        assert_eq!(diagnostics[1].severity, "warning");
+        assert_eq!(diagnostics[1].excerpt, vec![
+            (1, "precision mediump float;".into()),
+            (2, "uniform vec4 u_color;".into()),
+            (3, "void main() {".into()),
+            (4, "    gl_FragColor = foo;".into()),
+        ]);
+    }
+
+    #[test]
+    fn ties_source_string_number_to_chunk() {
+        let (number, chunk) = glsl_chunk_by_number(2).unwrap();
+        let diagnostics = ShaderDiagnostic::parse_info_log(ShaderProgramName::FurlBasic, "vertex",
+            "ERROR: 2:1: 'x' : syntax error", SOURCE);
+        assert_eq!(diagnostics[0].chunk, Some(number));
+        assert_eq!(diagnostics[0].excerpt[0], (1, chunk.lines().next().unwrap().into()));
+    }
+
+    #[test]
+    fn keeps_unrecognised_lines() {
+        let diagnostics = ShaderDiagnostic::parse_info_log(ShaderProgramName::FurlBasic, "vertex",
+            "Link failed: out of memory", SOURCE);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, None);
+        assert_eq!(diagnostics[0].message, "Link failed: out of memory");
+        assert!(diagnostics[0].excerpt.is_empty());
+    }
+}
@@ -1,5 +1,8 @@
 //! @TODO describe
 
+mod shader_features;
+pub use shader_features::ShaderFeatures;
+
 mod shader_program_name;
 pub use shader_program_name::ShaderProgramName;
 
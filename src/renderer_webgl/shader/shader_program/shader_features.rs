@@ -0,0 +1,57 @@
+use std::ops::BitOr;
+
+/// #### A set of optional shader features, eg per-instance colours.
+///
+/// Each feature in the set becomes a `#define` when a shader is compiled, so
+/// that one GLSL file can `#ifdef` its way to several variants. Together with
+/// a ShaderProgramName, this is the key for `RendererWebGl::shader_variants`.
+#[derive(Clone,Copy,Debug,Default,PartialEq)]
+pub struct ShaderFeatures(pub u32);
+
+impl ShaderFeatures {
+    pub const NONE: Self = Self(0);
+    /// `#define INSTANCE_COLOR 1`
+    pub const INSTANCE_COLOR: Self = Self(1 << 0);
+
+    const ALL: [(Self, &'static str); 1] = [
+        (Self::INSTANCE_COLOR, "INSTANCE_COLOR"),
+    ];
+
+    /// Returns `true` if every feature in `other` is also in this set.
+    pub fn contains(
+        self,
+        other: Self,
+    ) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The name of each feature in the set, eg `["INSTANCE_COLOR"]`.
+    pub fn names(
+        self,
+    ) -> Vec<&'static str> {
+        Self::ALL.iter()
+            .filter(|(feature, _)| self.contains(*feature))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// The `#define`s which `SkCool::preprocess()` should inject.
+    pub fn defines(
+        self,
+    ) -> Vec<(String, String)> {
+        self.names().into_iter()
+            .map(|name| (name.to_string(), "1".to_string()))
+            .collect()
+    }
+}
+
+/// The union of two sets of features.
+impl BitOr for ShaderFeatures {
+    type Output = Self;
+    fn bitor(
+        self,
+        other: Self,
+    ) -> Self {
+        Self(self.0 | other.0)
+    }
+}
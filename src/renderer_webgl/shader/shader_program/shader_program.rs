@@ -1,63 +1,129 @@
 use web_sys::{WebGlProgram,WebGlRenderingContext as GL,WebGlUniformLocation};
-use super::super::super::{AttributeName,ShaderSignatureName,ShaderSignature,UniformName};
+use crate::error::ERROR as E;
+use super::super::super::{AttributeName,RendererError,ShaderSignatureName,ShaderSignature,UniformName};
+use super::super::SkCool;
+use super::{ShaderFeatures,ShaderProgramName};
 
-/// Contains one linked WebGlProgram object.
-pub trait ShaderProgram {
 
-    /// Wraps the GLSL program in Some(), ready to pass to gl.use_program().
-    fn option_program(&self) -> Option<&WebGlProgram>;
+/// #### Contains one linked WebGlProgram object.
+///
+/// Built from a ShaderSignature and a vert/frag pair. Every uniform in the
+/// signature is located when the program links, so adding a shader only needs
+/// its GLSL and a signature.
+pub struct ShaderProgram {
+    /// Lets Rust code refer to this program, eg ShaderProgramName::FurlBasic.
+    name: ShaderProgramName,
+    program: WebGlProgram,
+    pub signature: ShaderSignature,
+    /// The GLSL the program was linked from, before preprocessing, kept so
+    /// that it can be relinked.
+    vert_source: String,
+    frag_source: String,
+    /// The `#define`s injected by the preprocessor, as (name, value) pairs.
+    defines: Vec<(String, String)>,
+    /// The location of each uniform in `signature.uniform_signatures`.
+    uniform_locations: Vec<(UniformName, WebGlUniformLocation)>,
+}
 
-    // Tell WebGL to switch from whatever program it was using, to this one.
-    fn use_program(
-        &self,
+impl ShaderProgram {
+
+    /// Links `name` from the given GLSL, eg `RendererWebGl::get_shader_sources()`,
+    /// with a `#define` for each feature. Its signature leaves out whatever the
+    /// features don’t declare.
+    pub fn new(
         gl: &GL,
-    ) {
-        gl.use_program(self.option_program());
+        name: ShaderProgramName,
+        features: ShaderFeatures,
+        vert_source: &str,
+        frag_source: &str,
+    ) -> Result<Self, RendererError> {
+        let signature = ShaderSignature::new(name.signature_name()).for_features(features);
+        Self::from_sources(gl, name, signature, vert_source, frag_source, &features.defines())
     }
 
-    fn get_attribute_location(
-        &self,
-        _attribute_name: AttributeName,
-    ) -> u32 {
-        panic!("get_attribute_location() not implemented");
-    }
+    /// Links a program from the given GLSL source, eg when live-editing.
+    /// Pass in the signature of the program being replaced, to keep its
+    /// attribute locations.
+    pub fn from_sources(
+        gl: &GL,
+        name: ShaderProgramName,
+        signature: ShaderSignature,
+        vert_source: &str,
+        frag_source: &str,
+        defines: &[(String, String)],
+    ) -> Result<Self, RendererError> {
+        let program = SkCool::link_program(gl, name, &signature,
+            vert_source, frag_source, defines)?;
 
-    fn get_program(
-        &self,
-    ) -> &WebGlProgram {
-        panic!("get_program() not implemented");
-    }
+        let mut uniform_locations = vec![];
+        for uniform_signature in signature.uniform_signatures.iter() {
+            uniform_locations.push((
+                uniform_signature.name,
+                SkCool::locate_uniform(gl, &program, &signature, uniform_signature.name)?,
+            ));
+        }
 
-    fn get_signature_name(
-        &self,
-    ) -> &ShaderSignatureName {
-        panic!("get_signature_name() not implemented");
+        Ok(Self {
+            name,
+            program,
+            signature,
+            vert_source: vert_source.into(),
+            frag_source: frag_source.into(),
+            defines: defines.to_vec(),
+            uniform_locations,
+        })
     }
 
-    fn get_signature(
+    /// Tells WebGL to switch from whatever program it was using, to this one.
+    pub fn use_program(
         &self,
-    ) -> &ShaderSignature {
-        panic!("get_signature() not implemented");
+        gl: &GL,
+    ) {
+        gl.use_program(Some(&self.program));
     }
 
-    fn get_signature_mut(
-        &mut self,
-    ) -> &mut ShaderSignature {
-        panic!("get_signature_mut() not implemented");
+    pub fn get_attribute_location(
+        &self,
+        attribute_name: AttributeName,
+    ) -> Result<u32, RendererError> {
+        for attribute_signature in self.signature.attribute_signatures.iter() {
+            if attribute_signature.name == attribute_name {
+                return Ok(attribute_signature.location);
+            }
+        }
+        Err(RendererError::new(E::R11691,
+            format!("{:?} does not use AttributeName::{:?}", self.name, attribute_name)))
     }
 
-    fn set_attribute_location(
+    pub fn set_attribute_location(
         &mut self,
-        _attribute_index: usize,
-        _location: u32,
-    ) {
-        panic!("set_attribute_location() not implemented");
+        attribute_index: usize,
+        location: u32,
+    ) -> Result<(), RendererError> {
+        if attribute_index >= self.signature.attribute_signatures.len() {
+            return Err(RendererError::new(E::R11738,
+                format!("{:?} has no attribute at index {}", self.name, attribute_index)));
+        }
+        self.signature.attribute_signatures[attribute_index].location = location;
+        Ok(())
     }
 
-    fn get_uniform_location(
+    pub fn get_uniform_location(
         &self,
-        _uniform_name: UniformName,
-    ) -> &WebGlUniformLocation {
-        panic!("get_uniform_location() not implemented");
+        uniform_name: UniformName,
+    ) -> Result<&WebGlUniformLocation, RendererError> {
+        for (name, location) in self.uniform_locations.iter() {
+            if *name == uniform_name { return Ok(location) }
+        }
+        Err(RendererError::new(E::R11714,
+            format!("{:?} does not use UniformName::{:?}", self.name, uniform_name)))
     }
+
+    pub fn get_defines(&self) -> &[(String, String)] { &self.defines }
+    pub fn get_frag_source(&self) -> &str { &self.frag_source }
+    pub fn get_program(&self) -> &WebGlProgram { &self.program }
+    pub fn get_program_name(&self) -> ShaderProgramName { self.name }
+    pub fn get_signature_name(&self) -> &ShaderSignatureName { &self.signature.name }
+    pub fn get_signature(&self) -> &ShaderSignature { &self.signature }
+    pub fn get_vert_source(&self) -> &str { &self.vert_source }
 }
@@ -1,7 +1,69 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+use super::super::super::ShaderSignatureName;
+use super::super::{
+    bloom_frag,
+    blue_frag,
+    box_vert,
+    cactus_vert,
+    furl_basic_vert,
+    fxaa_frag,
+    grade_frag,
+    guides_vert,
+    lit_frag,
+    passthru_frag,
+    post_vert,
+    rainbow_frag,
+    red_frag,
+    vignette_frag
+};
+
+#[wasm_bindgen]
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum ShaderProgramName {
     BlueBox,
     Guides,
     FurlBasic,
+    PostBloom,
+    PostFxaa,
+    PostGrade,
+    PostVignette,
     RainbowCactus,
     RedBox,
 }
+
+impl ShaderProgramName {
+
+    /// The vert/frag pair which this program is built from, before any reload.
+    pub fn default_sources(
+        &self,
+    ) -> (&'static str, &'static str) {
+        match self {
+            ShaderProgramName::BlueBox => (box_vert(), blue_frag()),
+            ShaderProgramName::Guides => (guides_vert(), passthru_frag()),
+            ShaderProgramName::FurlBasic => (furl_basic_vert(), lit_frag()),
+            ShaderProgramName::PostBloom => (post_vert(), bloom_frag()),
+            ShaderProgramName::PostFxaa => (post_vert(), fxaa_frag()),
+            ShaderProgramName::PostGrade => (post_vert(), grade_frag()),
+            ShaderProgramName::PostVignette => (post_vert(), vignette_frag()),
+            ShaderProgramName::RainbowCactus => (cactus_vert(), rainbow_frag()),
+            ShaderProgramName::RedBox => (box_vert(), red_frag()),
+        }
+    }
+
+    /// The attributes and uniforms which this program’s GLSL declares.
+    pub fn signature_name(
+        &self,
+    ) -> ShaderSignatureName {
+        match self {
+            ShaderProgramName::BlueBox => ShaderSignatureName::BlueRedBox,
+            ShaderProgramName::Guides => ShaderSignatureName::Guides,
+            ShaderProgramName::FurlBasic => ShaderSignatureName::FurlBasic,
+            ShaderProgramName::PostBloom => ShaderSignatureName::Post,
+            ShaderProgramName::PostFxaa => ShaderSignatureName::Post,
+            ShaderProgramName::PostGrade => ShaderSignatureName::Post,
+            ShaderProgramName::PostVignette => ShaderSignatureName::PostVignette,
+            ShaderProgramName::RainbowCactus => ShaderSignatureName::RainbowCactus,
+            ShaderProgramName::RedBox => ShaderSignatureName::BlueRedBox,
+        }
+    }
+}
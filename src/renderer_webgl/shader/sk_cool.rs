@@ -6,8 +6,9 @@ use web_sys::{
     WebGlShader,
     WebGlUniformLocation
 };
-use super::super::{ShaderSignature,UniformName};
-use crate::error::{ERROR as E,error_to_string as e,SLOWLY_GET_ERROR_COOL_PATH};
+use super::super::{RendererError,ShaderSignature,UniformName};
+use super::{glsl_chunk,ShaderDiagnostic,ShaderProgramName};
+use crate::error::{ERROR as E,SLOWLY_GET_ERROR_COOL_PATH};
 
 
 /// #### A kit of ‘cool path’ operations for Shaders.
@@ -17,33 +18,43 @@ pub struct SkCool;
 
 impl SkCool {
 
-    /// @TODO describe
+    /// Preprocesses, compiles and links a shader program. If either shader
+    /// fails to compile, the error’s `diagnostics` locate each problem in the
+    /// GLSL source, or in the `#include`d chunk.
     pub fn link_program(
         gl: &GL,
+        shader_name: ShaderProgramName,
         shader_signature: &ShaderSignature,
         vert_source: &str,
         frag_source: &str,
-    ) -> Result<WebGlProgram, String> {
+        defines: &[(String, String)],
+    ) -> Result<WebGlProgram, RendererError> {
         let vert_shader = compile_shader(
             &gl,
+            shader_name,
             GL::VERTEX_SHADER,
             vert_source,
-        ).expect(e(E::R11530));
-        if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error(); // @TODO see it work
-            if e != 0 { panic!("{}: compile_shader(&gl, VERTEX_SHADER, vert_source)", e) } }
+            defines,
+            E::R11530,
+        )?;
+        if SLOWLY_GET_ERROR_COOL_PATH { // @TODO see it work
+            RendererError::check(gl, E::R11245, "compile_shader(&gl, VERTEX_SHADER, vert_source)")? }
 
         let frag_shader = compile_shader(
             &gl,
+            shader_name,
             GL::FRAGMENT_SHADER,
             frag_source,
-        ).expect(e(E::R11572));
-        if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error(); // @TODO see it work
-            if e != 0 { panic!("{}: compile_shader(&gl, FRAGMENT_SHADER, frag_source)", e) } }
+            defines,
+            E::R11572,
+        )?;
+        if SLOWLY_GET_ERROR_COOL_PATH { // @TODO see it work
+            RendererError::check(gl, E::R11245, "compile_shader(&gl, FRAGMENT_SHADER, frag_source)")? }
 
-        let program = gl.create_program()
-            .expect(e(E::R11331)); // @TODO check that R11331 can get thrown
-        if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error(); // @TODO see it work
-            if e != 0 { panic!("{}: create_program()", e) } }
+        let program = gl.create_program() // @TODO check that R11331 can get thrown
+            .ok_or_else(|| RendererError::new(E::R11331, "create_program()".into()))?;
+        if SLOWLY_GET_ERROR_COOL_PATH { // @TODO see it work
+            RendererError::check(gl, E::R11245, "create_program()")? }
 
         // crate::info(&format!("shader_signature.name: {:?}", &shader_signature.name));
         for sig in &shader_signature.attribute_signatures {
@@ -52,27 +63,27 @@ impl SkCool {
                 sig.location,
                 sig.name_glsl,
             );
-            // Panic if sig.location is invalid. WebGL’s warning is:
+            // Fail if sig.location is invalid. WebGL’s warning is:
             //   bindAttribLocation: `location` must be less than MAX_VERTEX_ATTRIBS.
             // @TODO pick up other errors, eg a nonexistant attribute name
-            if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error();
-                if e != 0 { panic!("{}: bind_attrib_location(&program, {}, \"{}\")",
-                    e, sig.location, sig.name_glsl) } }
+            if SLOWLY_GET_ERROR_COOL_PATH {
+                RendererError::check(gl, E::R11245, &format!("bind_attrib_location(&program, {}, \"{}\")",
+                    sig.location, sig.name_glsl))? }
             // crate::info(&format!("name_glsl: {:?}", attribute_signature.name_glsl));
             // crate::info(&format!("location: {:?}", attribute_signature.location));
         }
 
         gl.attach_shader(&program, &vert_shader);
-        if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error(); // @TODO see it work
-            if e != 0 { panic!("{}: attach_shader(&program, &vert_shader)", e) } }
+        if SLOWLY_GET_ERROR_COOL_PATH { // @TODO see it work
+            RendererError::check(gl, E::R11245, "attach_shader(&program, &vert_shader)")? }
 
         gl.attach_shader(&program, &frag_shader);
-        if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error(); // @TODO see it work
-            if e != 0 { panic!("{}: attach_shader(&program, &frag_shader)", e) } }
+        if SLOWLY_GET_ERROR_COOL_PATH { // @TODO see it work
+            RendererError::check(gl, E::R11245, "attach_shader(&program, &frag_shader)")? }
 
         gl.link_program(&program);
-        if SLOWLY_GET_ERROR_COOL_PATH { let e = gl.get_error(); // @TODO see it work
-            if e != 0 { panic!("{}: link_program(&program)", e) } }
+        if SLOWLY_GET_ERROR_COOL_PATH { // @TODO see it work
+            RendererError::check(gl, E::R11245, "link_program(&program)")? }
 
         // Probably (?) free up resources.
         gl.detach_shader(&program, &vert_shader);
@@ -83,47 +94,78 @@ impl SkCool {
         if gl.get_program_parameter(&program, GL::LINK_STATUS).as_bool().unwrap_or(false) {
             Ok(program)
         } else {
-            Err(gl.get_program_info_log(&program).expect(e(E::R11982))) // @TODO check that R11982 can get thrown
+            let info_log = gl.get_program_info_log(&program) // @TODO check that R11982 can get thrown
+                .ok_or_else(|| RendererError::new(E::R11982, "get_program_info_log()".into()))?;
+            Err(RendererError::new(E::R11418, info_log))
         }
     }
 
 
+    /// Replaces each `#include "quat.glsl"`-style line with that chunk’s GLSL,
+    /// and adds a `#define NAME VALUE` line for each of `defines`. The defines
+    /// go just after `#version`, which must stay the first line.  
+    /// Each chunk is only included once, so chunks can include each other.  
+    /// `#line` directives undo the shift in line numbers, so that a driver’s
+    /// diagnostics point at the original line. A chunk’s lines are reported
+    /// under its own source string number, from `glsl_chunk()`.
+    pub fn preprocess(
+        source: &str,
+        defines: &[(String, String)],
+    ) -> Result<String, RendererError> {
+        let mut included: Vec<u32> = vec![];
+        let body = resolve_includes(source, 0, &mut included)?;
+
+        let (version, body, first_line) = match body.find('\n') {
+            Some(newline) if body.trim_start().starts_with("#version") =>
+                (&body[..=newline], &body[newline + 1..], 2),
+            _ => ("", &body[..], 1),
+        };
+        let mut preprocessed = String::with_capacity(source.len());
+        preprocessed.push_str(version);
+        for (name, value) in defines.iter() {
+            preprocessed.push_str(&format!("#define {} {}\n", name, value));
+        }
+        preprocessed.push_str(&format!("#line {} 0\n", first_line));
+        preprocessed.push_str(body);
+        Ok(preprocessed)
+    }
+
+
     /// Returns the ‘location’ of a WebGL attribute with the given name.  
-    /// Panics if no attribute with that name exists in the given program.
+    /// Fails if no attribute with that name exists in the given program.
     pub fn _locate_attribute(
         gl: &GL,
         program: &WebGlProgram,
         name: &'static str,
-    ) -> u32 {
+    ) -> Result<u32, RendererError> {
         let location = gl.get_attrib_location(&program, name);
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("get_attrib_location() {} {}", name, e) }
+            RendererError::check(gl, E::R11245, &format!("get_attrib_location(..., \"{}\")", name))?;
         }
-        if location < 0 { panic!("attribute name '{}' not recognised", name) }
+        if location < 0 { return Err(RendererError::new(E::R11691, name.into())) }
         let msg = format!("{} {}", name, location);
         crate::info(&msg);
-        location as u32
+        Ok(location as u32)
     }
 
 
     /// Returns the ‘location’ of a WebGL uniform with the given name.  
-    /// Panics if no uniform with that name exists in the given program.
+    /// Fails if no uniform with that name exists in the given program.
     pub fn locate_uniform(
         gl: &GL,
         program: &WebGlProgram,
         signature: &ShaderSignature,
         name: UniformName,
-    ) -> WebGlUniformLocation {
-        let name_glsl = signature.get_uniform_name_glsl(name);
+    ) -> Result<WebGlUniformLocation, RendererError> {
+        let name_glsl = signature.get_uniform_name_glsl(name)?;
         // gl.bind_attrib_location(&program, 123, name_glsl);
         let location = gl.get_uniform_location(&program, name_glsl)
-            .expect(&format!("{:?}' get_uniform_location(..., \"{}\")", e(E::R11006), name_glsl));
+            .ok_or_else(|| RendererError::new(E::R11006,
+                format!("get_uniform_location(..., \"{}\")", name_glsl)))?;
         if SLOWLY_GET_ERROR_COOL_PATH {
-            let e = gl.get_error(); // @TODO does this actually pick up errors?
-            if e != 0 { panic!("{}: get_uniform_location(..., \"{}\")", e, name_glsl) }
+            RendererError::check(gl, E::R11245, &format!("get_uniform_location(..., \"{}\")", name_glsl))?;
         }
-        location
+        Ok(location)
     }
 }
 
@@ -132,19 +174,105 @@ impl SkCool {
 
 // HELPERS
 
+/// Recursively replaces `#include "name"` lines with chunks from `glsl_chunk()`.
+/// `number` is the source string number of `source`. `included` lists the
+/// numbers of the chunks already included, which are skipped.
+fn resolve_includes(
+    source: &str,
+    number: u32,
+    included: &mut Vec<u32>,
+) -> Result<String, RendererError> {
+    let mut resolved = String::with_capacity(source.len());
+    for (index, line) in source.lines().enumerate() {
+        let name = match line.trim().strip_prefix("#include") {
+            Some(name) => name.trim().trim_matches('"'),
+            None => {
+                resolved.push_str(line);
+                resolved.push('\n');
+                continue;
+            },
+        };
+        let (chunk_number, chunk) = glsl_chunk(name)
+            .ok_or_else(|| RendererError::new(E::R11447, format!("#include \"{}\"", name)))?;
+        // A blank line in place of a repeat `#include` keeps the numbering.
+        if included.contains(&chunk_number) { resolved.push('\n'); continue }
+        included.push(chunk_number);
+        resolved.push_str(&format!("#line 1 {}\n", chunk_number));
+        resolved.push_str(&resolve_includes(chunk, chunk_number, included)?);
+        // Carry on from the line after the `#include`.
+        resolved.push_str(&format!("#line {} {}\n", index + 2, number));
+    }
+    Ok(resolved)
+}
+
+/// `code` is the error to return if compilation fails, eg `ERROR::R11530`.
+/// Diagnostics are tied back to `source`, not to the preprocessed GLSL.
 fn compile_shader(
     gl: &GL,
+    shader_name: ShaderProgramName,
     shader_type: u32,
     source: &str,
-) -> Result<WebGlShader, String> {
+    defines: &[(String, String)],
+    code: E,
+) -> Result<WebGlShader, RendererError> {
+    let preprocessed = SkCool::preprocess(source, defines)?;
     let shader = gl.create_shader(shader_type)
-        .expect(e(E::R11872));
-    gl.shader_source(&shader, source);
+        .ok_or_else(|| RendererError::new(E::R11872, "create_shader()".into()))?;
+    gl.shader_source(&shader, &preprocessed);
     gl.compile_shader(&shader);
 
     if gl.get_shader_parameter(&shader, GL::COMPILE_STATUS).as_bool().unwrap_or(false) {
         Ok(shader)
     } else {
-        Err(gl.get_shader_info_log(&shader).expect(e(E::R11982))) // @TODO check that R11982 can get thrown
+        let info_log = gl.get_shader_info_log(&shader) // @TODO check that R11982 can get thrown
+            .ok_or_else(|| RendererError::new(E::R11982, "get_shader_info_log()".into()))?;
+        gl.delete_shader(Some(&shader));
+        let stage = if shader_type == GL::VERTEX_SHADER { "vertex" } else { "fragment" };
+        let diagnostics = ShaderDiagnostic::parse_info_log(shader_name, stage, &info_log, source);
+        let context = if diagnostics.is_empty() {
+            format!("{:?} {} shader: {}", shader_name, stage, info_log.trim())
+        } else {
+            format!("{:?} {} shader", shader_name, stage)
+        };
+        Err(RendererError::new(code, context).with_diagnostics(diagnostics))
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_defines_after_version() {
+        let defines = vec![("INSTANCE_COLOR".to_string(), "1".to_string())];
+        let preprocessed = SkCool::preprocess("#version 100\nvoid main() {}\n", &defines).unwrap();
+        assert_eq!(preprocessed, "#version 100\n#define INSTANCE_COLOR 1\n#line 2 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn places_defines_first_without_version() {
+        let defines = vec![("INSTANCE_COLOR".to_string(), "1".to_string())];
+        let preprocessed = SkCool::preprocess("void main() {}\n", &defines).unwrap();
+        assert_eq!(preprocessed, "#define INSTANCE_COLOR 1\n#line 1 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn includes_each_chunk_once() {
+        let (number, chunk) = glsl_chunk("quat.glsl").unwrap();
+        let source = "#version 100\n#include \"quat.glsl\"\n#include \"quat.glsl\"\nvoid main() {}\n";
+        let preprocessed = SkCool::preprocess(source, &[]).unwrap();
+        assert_eq!(preprocessed.matches(chunk).count(), 1);
+        assert!(preprocessed.contains(&format!("#line 1 {}\n{}", number, chunk)));
+        // The repeat `#include` becomes a blank line, so main() is still line 4.
+        assert!(preprocessed.ends_with(&format!("{}#line 3 0\n\nvoid main() {{}}\n", chunk)));
+    }
+
+    #[test]
+    fn rejects_unknown_chunk() {
+        let error = SkCool::preprocess("#include \"nope.glsl\"\n", &[]).unwrap_err();
+        assert_eq!(error.code, E::R11447);
     }
 }
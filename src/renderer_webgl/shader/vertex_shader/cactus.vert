@@ -12,16 +12,7 @@ uniform vec4 u_quaternion;
 attribute vec3 color; // the color of the point
 varying vec3 vColor; 
 
-// Create a quaternion from an axis and angle.
-vec4 quatFromAxisAngle(vec3 axis, float angle) {
-    float halfAngle = angle * 0.5;
-    return vec4(axis.xyz * sin(halfAngle), cos(halfAngle));
-}
-
-// Apply the quaternion (q) to a vector (v).
-vec3 rotateVector(vec4 q, vec3 v) {
-    return v + 2.0 * cross(q.xyz, cross(q.xyz, v) + q.w * v);
-}
+#include "quat.glsl"
 
 void main(void) {
     vec3 pt = position;
@@ -4,6 +4,13 @@ precision highp float;
 attribute vec3 color; // the color of the point
 attribute vec3 position;
 attribute vec4 ia_curves;
+attribute vec3 normal; // the direction the surface faces, before the Furl moves it
+attribute vec2 uv; // where the Prim samples the petal and seed textures
+attribute float ia_index; // the instance’s position in the instance buffers, from 0.0
+attribute float spike; // 1.0 for the tip of a normal spike, otherwise 0.0
+#ifdef INSTANCE_COLOR
+attribute float ia_spot; // from 0.0 to 1.0, random for each instance
+#endif
 
 uniform mat4 iu_angle;
 uniform mat4 iu_bulge;
@@ -13,46 +20,37 @@ uniform mat4 iu_scale;
 uniform mat4 iu_tilt;
 uniform mat4 u_projection_matrix;
 uniform mat4 u_view_matrix;
+uniform vec3 u_light_direction; // towards the light, in world space
 uniform vec3 u_placement;
 uniform vec4 u_quaternion_x;
 uniform vec4 u_quaternion_y;
 uniform vec4 u_slidermix;
 uniform vec4 u_timermix;
+uniform vec4 u_debug; // debug view, nearest depth, farthest depth, normal spike length
+uniform float u_wire; // 1.0 while edges are drawn over solid shading, else 0.0
+#ifdef INSTANCE_COLOR
+uniform vec4 u_gradient; // start hue, end hue, saturation, mix
+uniform vec4 u_gradient_curves; // how far each of the four curves moves along the gradient
+uniform float u_primcount; // the number of instances being drawn
+uniform vec4 u_spots; // density, hue, brightness, mix
+uniform vec4 u_stripes; // every n instances, width, hue, mix
+#endif
 
 varying vec3 vColor; 
+varying vec4 vDebug; // unshaded, depth view, overdraw view, depth from 0.0 to 1.0
+varying vec3 vLightDirection; // in view space, like vNormal and vViewPosition
+varying vec3 vNormal;
+varying float vSeed; // 1.0 for Prims at the heart of the Furl, 0.0 for its petals
+varying vec2 vUv;
+varying vec3 vViewPosition;
+varying float vWire;
 
-// Create a quaternion from an axis and angle.
-vec4 quatFromAxisAngle(vec3 axis, float angle) {
-    float halfAngle = angle * 0.5;
-    return vec4(axis.xyz * sin(halfAngle), cos(halfAngle));
-}
-
-// Apply the quaternion (q) to a vector (v).
-vec3 rotateVector(vec4 q, vec3 v) {
-    return v + 2.0 * cross(q.xyz, cross(q.xyz, v) + q.w * v);
-}
-// Rotates a point about the x-axis.
-vec3 rotateAboutXAxis(vec3 pt, float angle) {
-    float ha = angle * 0.5; // half angle
-    vec3 quatXAxis = vec3(sin(ha), 0.0, 0.0); // xyz part of the quaternion
-    return pt + 2.0 * cross(
-        quatXAxis,
-        cross(quatXAxis, pt) + cos(ha) * pt // cos(ha) is the w part of the quat
-    );
-}
-
-// Rotates a point about the y-axis.
-vec3 rotateAboutYAxis(vec3 pt, float angle) {
-    float ha = angle * 0.5; // half angle
-    vec3 quatYAxis = vec3(0.0, sin(ha), 0.0); // xyz part of the quaternion
-    return pt + 2.0 * cross(
-        quatYAxis,
-        cross(quatYAxis, pt) + cos(ha) * pt // cos(ha) is the w part of the quat
-    );
-}
+#include "palette.glsl"
+#include "quat.glsl"
 
 void main(void) {
     vec3 pt = position;
+    vec3 n = normal;
 
     // Dereference the four curves.
     float linear = ia_curves[0];
@@ -99,6 +97,7 @@ void main(void) {
     //                 +  u_timermix[0]*iu_scale_tm[0] +  u_timermix[1]*iu_scale_tm[1] +  u_timermix[2]*iu_scale_tm[2] +  u_timermix[3]*iu_scale_tm[3];
     float scale = (1.0-scale_mix) * scale_a + scale_mix * scale_b;
     pt *= scale;
+    n *= sign(scale); // a negative scale turns the Prim inside out
 
     // Tilt the Prim using the linear curve, flutter, sine-hump, and an invariant.
     float tilt_a = linear*iu_tilt_a[0] + flutter*iu_tilt_a[1] + hump*iu_tilt_a[2] + iu_tilt_a[3];
@@ -106,6 +105,7 @@ void main(void) {
     float tilt_mix = dot(u_slidermix, iu_tilt_sm) + dot(u_timermix, iu_tilt_tm);
     float tilt = (1.0-tilt_mix) * tilt_a + tilt_mix * tilt_b;
     pt = rotateAboutXAxis(pt, tilt);
+    n = rotateAboutXAxis(n, tilt);
 
     // Apply bulge to the Prim using the linear curve, flutter, sine-hump and an invariant.
     // This actually just translates the Prim in the Z direction.
@@ -142,23 +142,78 @@ void main(void) {
     float angle_mix = dot(u_slidermix, iu_angle_sm) + dot(u_timermix, iu_angle_tm);
     float angle = (1.0-angle_mix) * angle_a + angle_mix * angle_b;
     pt = rotateAboutYAxis(pt, angle + lean_orientation);
+    n = rotateAboutYAxis(n, angle + lean_orientation);
 
     // Finish making the Furl lean to one side.
     pt = rotateAboutXAxis(pt, lean_inclination);
     pt = rotateAboutYAxis(pt, -lean_orientation); // undoes `+ lean_orientation`
+    n = rotateAboutXAxis(n, lean_inclination);
+    n = rotateAboutYAxis(n, -lean_orientation);
 
     // Apply quaternions to vector to rotate it.
     vec4 quat_x = quatFromAxisAngle(u_quaternion_x.xyz, u_quaternion_x.w);
     pt = rotateVector(quat_x, pt);
+    n = rotateVector(quat_x, n);
     vec4 quat_y = quatFromAxisAngle(u_quaternion_y.xyz, u_quaternion_y.w);
     pt = rotateVector(quat_y, pt);
+    n = rotateVector(quat_y, n);
 
     // Move the entire Furl to the correct position in world space.
     pt += u_placement;
 
-    gl_Position = u_projection_matrix * u_view_matrix * vec4(pt, 1.0);
+    // The tip of a normal spike sticks out along the vertex normal.
+    pt += n * spike * u_debug.w;
+
+    vec4 view_position = u_view_matrix * vec4(pt, 1.0);
+    gl_Position = u_projection_matrix * view_position;
 
+    // Pull edges slightly towards the camera, so they don’t z-fight with the
+    // solid triangles they’re drawn over.
+    gl_Position.z -= u_wire * 0.0005 * gl_Position.w;
+    vWire = u_wire;
+
+    // Light in view space, where the camera is at the origin. The view matrix
+    // only rotates and translates, so it can transform normals too.
     vColor = color;
+#ifdef INSTANCE_COLOR
+    // Colour the whole Prim, rather than each vertex. Spots are laid over
+    // stripes, which are laid over the gradient.
+    float gradient = clamp(dot(ia_curves, u_gradient_curves), 0.0, 1.0);
+    vec3 gradient_color = hsv2rgb(vec3(mix(u_gradient.x, u_gradient.y, gradient), u_gradient.z, 1.0));
+    vColor = mix(vColor, gradient_color, u_gradient.w);
+
+    // The angle sweeps the Prims round in order of the linear curve, so this
+    // is each Prim’s position along the spiral. Stripes every 8, 13, 21 or 34
+    // Prims follow the Furl’s parastichies, when its angle is golden.
+    float spiral_index = floor(linear * u_primcount + 0.5);
+    float every = max(u_stripes.x, 1.0);
+    float stripe = 1.0 - step(u_stripes.y * every, mod(spiral_index, every));
+    vColor = mix(vColor, hsv2rgb(vec3(u_stripes.z, 0.8, 1.0)), stripe * u_stripes.w);
+
+    float spot = 1.0 - step(u_spots.x, ia_spot);
+    vColor = mix(vColor, hsv2rgb(vec3(u_spots.y, 0.8, u_spots.z)), spot * u_spots.w);
+#endif
+
+    // Debug views, where `u_debug.x` is 1.0 for normals, 2.0 for depth, 3.0
+    // for overdraw and 4.0 for instance IDs. Golden-ratio hues keep
+    // neighbouring instances far apart in colour.
+    float debug_view = u_debug.x;
+    if (debug_view == 1.0) vColor = n * 0.5 + 0.5;
+    if (debug_view == 4.0) vColor = hsv2rgb(vec3(fract(ia_index * 0.618034), 0.75, 1.0));
+    vDebug = vec4(
+        debug_view == 1.0 ? 1.0 : 0.0,
+        debug_view == 2.0 ? 1.0 : 0.0,
+        debug_view == 3.0 ? 1.0 : 0.0,
+        clamp((-view_position.z - u_debug.y) / (u_debug.z - u_debug.y), 0.0, 1.0)
+    );
+    vLightDirection = (u_view_matrix * vec4(u_light_direction, 0.0)).xyz;
+    vNormal = (u_view_matrix * vec4(n, 0.0)).xyz;
+    vViewPosition = view_position.xyz;
+
+    // Like a sunflower, the Prims nearest the start of the linear curve are
+    // seeds, and the rest are petals.
+    vSeed = 1.0 - smoothstep(0.3, 0.45, linear);
+    vUv = uv;
 
     // Define point size for WireframePreset::Dots.
     gl_PointSize = pt.z * 3.0 + 2.0;
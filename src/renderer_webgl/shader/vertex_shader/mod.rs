@@ -17,3 +17,7 @@ pub fn guides_vert() -> &'static str {
 pub fn furl_basic_vert() -> &'static str {
     include_str!("furl_basic.vert")
 }
+
+pub fn post_vert() -> &'static str {
+    include_str!("post.vert")
+}
@@ -0,0 +1,8 @@
+#version 100
+precision highp float;
+
+attribute vec2 a_corner; // -1.0 or 1.0 in each direction, so the quad covers the screen
+
+void main(void) {
+    gl_Position = vec4(a_corner, 0.0, 1.0);
+}
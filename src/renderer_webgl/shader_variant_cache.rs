@@ -0,0 +1,84 @@
+//! Remembers which shader variants have been compiled, and how long each took.
+
+use super::ShaderProgramName;
+use super::shader::ShaderFeatures;
+
+
+/// One compiled combination of a ShaderProgramName and its ShaderFeatures.
+pub struct ShaderVariant {
+    pub name: ShaderProgramName,
+    pub features: ShaderFeatures,
+    /// Index of the compiled program in `RendererWebGl::shaders`.
+    pub shader_index: usize,
+    /// How long compiling and linking took, in milliseconds.
+    pub compile_ms: f64,
+    /// How many times `RkCool::add_shader()` has asked for this variant.
+    pub requests: u32,
+}
+
+
+/// #### Remembers which shader variants have been compiled.
+///
+/// Compiling every combination of features up front would be slow, so each
+/// variant is compiled the first time `RkCool::add_shader()` asks for it, and
+/// then reused — eg by the next Scene which needs it.
+pub struct ShaderVariantCache {
+    variants: Vec<ShaderVariant>,
+}
+
+impl ShaderVariantCache {
+
+    pub fn new() -> Self {
+        Self {
+            variants: vec![],
+        }
+    }
+
+    /// Returns the index in `RendererWebGl::shaders` of a compiled variant,
+    /// and counts the request. Returns None if it has not been compiled yet.
+    pub fn request(
+        &mut self,
+        name: ShaderProgramName,
+        features: ShaderFeatures,
+    ) -> Option<usize> {
+        let variant = self.variants.iter_mut()
+            .find(|variant| variant.name == name && variant.features == features)?;
+        variant.requests += 1;
+        Some(variant.shader_index)
+    }
+
+    pub fn insert(
+        &mut self,
+        name: ShaderProgramName,
+        features: ShaderFeatures,
+        shader_index: usize,
+        compile_ms: f64,
+    ) {
+        self.variants.push(ShaderVariant {
+            name,
+            features,
+            shader_index,
+            compile_ms,
+            requests: 1,
+        });
+    }
+
+    /// Eg `[{"name":"FurlBasic","features":["INSTANCE_COLOR"],"compile_ms":12.5,"requests":1}]`
+    pub fn to_json(
+        &self,
+    ) -> String {
+        let variants: Vec<String> = self.variants.iter()
+            .map(|variant| format!(
+                r#"{{"name":"{:?}","features":[{}],"compile_ms":{},"requests":{}}}"#,
+                variant.name,
+                variant.features.names().iter()
+                    .map(|name| format!(r#""{}""#, name))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                variant.compile_ms,
+                variant.requests,
+            ))
+            .collect();
+        format!("[{}]", variants.join(","))
+    }
+}
@@ -1,8 +1,64 @@
+use web_sys::WebGlRenderingContext as GL;
+
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum AttributeKind {
     /// A 32 bit floating point number.
     F32,
+    /// Vector of two 32 bit floating point numbers.
+    Vec2,
     /// Vector of three 32 bit floating point numbers.
     Vec3,
     /// Vector of four 32 bit floating point numbers.
     Vec4,
+    /// Vector of three 16 bit signed integers, eg a position in millimetres.
+    /// The vertex shader sees whole-number floats, so declares it as `vec3`.
+    #[allow(dead_code)] // not used by any Scene yet
+    I16Vec3,
+    /// Vector of three 16 bit signed integers, which the vertex shader sees as
+    /// floats from -1.0 to 1.0, eg a normal. Declared as `vec3` in GLSL.
+    I16Vec3Norm,
+    /// Vector of three 8 bit unsigned integers, which the vertex shader sees as
+    /// floats from 0.0 to 1.0, eg an RGB colour. Declared as `vec3` in GLSL.
+    U8Vec3Norm,
+    /// Like U8Vec3Norm, but with four components, eg an RGBA colour.
+    #[allow(dead_code)] // not used by any Scene yet
+    U8Vec4Norm,
+}
+
+impl AttributeKind {
+
+    /// The number of values per vertex, eg 3 for Vec3. Must be 1, 2, 3 or 4.
+    pub fn components(&self) -> i32 {
+        match self {
+            AttributeKind::F32 => 1,
+            AttributeKind::Vec2 => 2,
+            AttributeKind::Vec3 | AttributeKind::I16Vec3 | AttributeKind::I16Vec3Norm
+                | AttributeKind::U8Vec3Norm => 3,
+            AttributeKind::Vec4 | AttributeKind::U8Vec4Norm => 4,
+        }
+    }
+
+    /// The `type` to pass to `vertexAttribPointer()`, eg `GL::FLOAT`.
+    pub fn component_type(&self) -> u32 {
+        match self {
+            AttributeKind::F32 | AttributeKind::Vec2 | AttributeKind::Vec3
+                | AttributeKind::Vec4 => GL::FLOAT,
+            AttributeKind::I16Vec3 | AttributeKind::I16Vec3Norm => GL::SHORT,
+            AttributeKind::U8Vec3Norm | AttributeKind::U8Vec4Norm => GL::UNSIGNED_BYTE,
+        }
+    }
+
+    /// The byte length of one value, eg 4 for a FLOAT.
+    pub fn component_bytes(&self) -> i32 {
+        match self.component_type() {
+            GL::FLOAT => 4,
+            GL::SHORT => 2,
+            _ => 1,
+        }
+    }
+
+    /// Whether WebGL should map integers to 0.0..1.0 (or -1.0..1.0 if signed).
+    pub fn normalized(&self) -> bool {
+        matches!(self, AttributeKind::I16Vec3Norm | AttributeKind::U8Vec3Norm | AttributeKind::U8Vec4Norm)
+    }
 }
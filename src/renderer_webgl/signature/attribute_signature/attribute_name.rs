@@ -1,4 +1,4 @@
-#[derive(Debug,PartialEq)]
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum AttributeName {
     /// @todo describe
     Curves,
@@ -10,7 +10,19 @@ pub enum AttributeName {
     PositionY,
 
     Color,
+    /// A corner of the full-screen quad which post-processing passes draw.
+    Corner,
+    /// Each instance’s position in the instance buffers, from 0.0.
+    InstanceIndex,
     InstanceLog,
     InstanceLogRev,
+    /// A random number for each instance, which decides whether it’s a spot.
+    InstanceSpot,
+    /// The direction a surface faces, for lighting.
+    Normal,
     Position,
+    /// 1.0 for the tip of a normal spike, otherwise 0.0.
+    Spike,
+    /// Where a vertex samples textures, from (0, 0) to (1, 1).
+    TexCoord,
 }
@@ -1,5 +1,6 @@
 use super::{AttributeKind,AttributeName};
 
+#[derive(Clone)]
 pub struct AttributeSignature {
     /// The WebGL data type, eg AttributeKind::Vec2 for two f32s in a vector.
     pub kind: AttributeKind,
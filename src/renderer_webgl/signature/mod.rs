@@ -13,4 +13,6 @@ pub use uniform_signature::{UniformKind,UniformName,UniformSignature};
 mod shader_signature_blue_red_box;
 mod shader_signature_furl_basic;
 mod shader_signature_guides;
+mod shader_signature_post;
+mod shader_signature_post_vignette;
 mod shader_signature_rainbow_cactus;
@@ -1,9 +1,15 @@
+use crate::error::ERROR as E;
+use super::super::super::{RendererError,ShaderFeatures};
 use super::super::{AttributeSignature,UniformName,UniformSignature};
 use super::ShaderSignatureName;
 
+#[derive(Clone)]
 pub struct ShaderSignature {
     /// @TODO describe
     pub attribute_signatures: Vec<AttributeSignature>,
+    /// Each attribute or uniform which the GLSL only declares inside an
+    /// `#ifdef`, by its `name_glsl`, and the feature which defines it.
+    pub feature_gated: Vec<(&'static str, ShaderFeatures)>,
     /// Lets Rust code refer to this signature, eg ShaderSignatureName::BlueRedBox.
     pub name: ShaderSignatureName,
     /// @TODO describe
@@ -16,19 +22,37 @@ impl ShaderSignature {
             ShaderSignatureName::BlueRedBox => Self::blue_red_box(),
             ShaderSignatureName::FurlBasic => Self::furl_basic(),
             ShaderSignatureName::Guides => Self::guides(),
+            ShaderSignatureName::Post => Self::post(),
+            ShaderSignatureName::PostVignette => Self::post_vignette(),
             ShaderSignatureName::RainbowCactus => Self::rainbow_cactus(),
         }
     }
 
+    /// Drops each attribute and uniform which needs a feature that is not in
+    /// `features`, so that a variant is only checked for what it declares.
+    pub fn for_features(
+        mut self,
+        features: ShaderFeatures,
+    ) -> Self {
+        let missing: Vec<&'static str> = self.feature_gated.iter()
+            .filter(|(_, feature)| ! features.contains(*feature))
+            .map(|(name_glsl, _)| *name_glsl)
+            .collect();
+        self.attribute_signatures.retain(|signature| ! missing.contains(&signature.name_glsl));
+        self.uniform_signatures.retain(|signature| ! missing.contains(&signature.name_glsl));
+        self
+    }
+
     pub fn get_uniform_name_glsl(
         &self,
         name: UniformName,
-    ) -> &'static str {
+    ) -> Result<&'static str, RendererError> {
         for uniform_signature in self.uniform_signatures.iter() {
             if uniform_signature.name == name {
-                return uniform_signature.name_glsl;
+                return Ok(uniform_signature.name_glsl);
             }
         }
-        panic!("get_uniform_name_glsl() cannot find \"{:?}\"", name);
+        Err(RendererError::new(E::R11714,
+            format!("get_uniform_name_glsl() cannot find \"{:?}\"", name)))
     }
 }
@@ -1,4 +1,4 @@
-#[derive(Debug,PartialEq)]
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum ShaderSignatureName {
     /// An experiment in switching between shader programs during a render().
     BlueRedBox,
@@ -6,6 +6,11 @@ pub enum ShaderSignatureName {
     FurlBasic,
     /// Used to draw guide axes and grids during development.
     Guides,
+    /// Full-screen post-processing passes, eg FXAA or bloom.
+    Post,
+    /// The vignette pass, which also needs to know where the framebuffer is
+    /// in the whole image, eg one tile of a still.
+    PostVignette,
     /// An experiment with WebGL instanced elements.
     RainbowCactus,
 }
@@ -33,6 +33,7 @@ impl ShaderSignature {
                     name_glsl: "a_instance_step",
                 },
             ],
+            feature_gated: vec![],
             name: ShaderSignatureName::BlueRedBox,
             uniform_signatures: vec![
                 UniformSignature {
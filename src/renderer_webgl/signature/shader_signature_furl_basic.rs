@@ -8,6 +8,7 @@ use super::{
     UniformName,
     UniformSignature,
 };
+use super::super::ShaderFeatures;
 
 
 impl ShaderSignature {
@@ -21,7 +22,7 @@ impl ShaderSignature {
                     name_glsl: "position",
                 },
                 AttributeSignature {
-                    kind: AttributeKind::Vec3,
+                    kind: AttributeKind::U8Vec3Norm,
                     location: 7,
                     name: AttributeName::Color,
                     name_glsl: "color",
@@ -32,6 +33,44 @@ impl ShaderSignature {
                     name: AttributeName::Curves,
                     name_glsl: "ia_curves",
                 },
+                AttributeSignature {
+                    kind: AttributeKind::I16Vec3Norm,
+                    location: 9,
+                    name: AttributeName::Normal,
+                    name_glsl: "normal",
+                },
+                AttributeSignature {
+                    kind: AttributeKind::Vec2,
+                    location: 11,
+                    name: AttributeName::TexCoord,
+                    name_glsl: "uv",
+                },
+                AttributeSignature {
+                    kind: AttributeKind::F32,
+                    location: 12,
+                    name: AttributeName::InstanceSpot,
+                    name_glsl: "ia_spot",
+                },
+                AttributeSignature {
+                    kind: AttributeKind::F32,
+                    location: 13,
+                    name: AttributeName::InstanceIndex,
+                    name_glsl: "ia_index",
+                },
+                AttributeSignature {
+                    kind: AttributeKind::F32,
+                    location: 14,
+                    name: AttributeName::Spike,
+                    name_glsl: "spike",
+                },
+            ],
+            feature_gated: vec![
+                ("ia_spot", ShaderFeatures::INSTANCE_COLOR),
+                ("u_gradient", ShaderFeatures::INSTANCE_COLOR),
+                ("u_gradient_curves", ShaderFeatures::INSTANCE_COLOR),
+                ("u_primcount", ShaderFeatures::INSTANCE_COLOR),
+                ("u_spots", ShaderFeatures::INSTANCE_COLOR),
+                ("u_stripes", ShaderFeatures::INSTANCE_COLOR),
             ],
             name: ShaderSignatureName::FurlBasic,
             uniform_signatures: vec![
@@ -100,6 +139,71 @@ impl ShaderSignature {
                     name: UniformName::QuaternionY,
                     name_glsl: "u_quaternion_y",
                 },
+                UniformSignature {
+                    kind: UniformKind::Vec3,
+                    name: UniformName::Ambient,
+                    name_glsl: "u_ambient",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec3,
+                    name: UniformName::LightColor,
+                    name_glsl: "u_light_color",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec3,
+                    name: UniformName::LightDirection,
+                    name_glsl: "u_light_direction",
+                },
+                UniformSignature {
+                    kind: UniformKind::F32,
+                    name: UniformName::Lighting,
+                    name_glsl: "u_lighting",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::Gradient,
+                    name_glsl: "u_gradient",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::GradientCurves,
+                    name_glsl: "u_gradient_curves",
+                },
+                UniformSignature {
+                    kind: UniformKind::F32,
+                    name: UniformName::Primcount,
+                    name_glsl: "u_primcount",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::Spots,
+                    name_glsl: "u_spots",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::Stripes,
+                    name_glsl: "u_stripes",
+                },
+                UniformSignature {
+                    kind: UniformKind::F32,
+                    name: UniformName::Wire,
+                    name_glsl: "u_wire",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::Debug,
+                    name_glsl: "u_debug",
+                },
+                UniformSignature {
+                    kind: UniformKind::Sampler2D,
+                    name: UniformName::PetalTexture,
+                    name_glsl: "u_petal_texture",
+                },
+                UniformSignature {
+                    kind: UniformKind::Sampler2D,
+                    name: UniformName::SeedTexture,
+                    name_glsl: "u_seed_texture",
+                },
             ],
         }
     }
@@ -21,12 +21,13 @@ impl ShaderSignature {
                     name_glsl: "position",
                 },
                 AttributeSignature {
-                    kind: AttributeKind::Vec3,
+                    kind: AttributeKind::U8Vec3Norm,
                     location: 7,
                     name: AttributeName::Color,
                     name_glsl: "color",
                 },
             ],
+            feature_gated: vec![],
             name: ShaderSignatureName::Guides,
             uniform_signatures: vec![
                 UniformSignature {
@@ -39,6 +40,11 @@ impl ShaderSignature {
                     name: UniformName::ViewMatrix,
                     name_glsl: "u_view_matrix",
                 },
+                UniformSignature {
+                    kind: UniformKind::Mat4,
+                    name: UniformName::ModelMatrix,
+                    name_glsl: "u_model_matrix",
+                },
             ],
         }
     }
@@ -0,0 +1,50 @@
+use super::{
+    AttributeKind,
+    AttributeName,
+    AttributeSignature,
+    ShaderSignature,
+    ShaderSignatureName,
+    UniformKind,
+    UniformName,
+    UniformSignature,
+};
+
+
+impl ShaderSignature {
+    pub fn post_vignette() -> Self {
+        Self {
+            attribute_signatures: vec![
+                AttributeSignature {
+                    kind: AttributeKind::Vec2,
+                    location: 10,
+                    name: AttributeName::Corner,
+                    name_glsl: "a_corner",
+                },
+            ],
+            feature_gated: vec![],
+            name: ShaderSignatureName::PostVignette,
+            uniform_signatures: vec![
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::PostFrame,
+                    name_glsl: "u_frame",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec4,
+                    name: UniformName::PostParams,
+                    name_glsl: "u_params",
+                },
+                UniformSignature {
+                    kind: UniformKind::Vec2,
+                    name: UniformName::Resolution,
+                    name_glsl: "u_resolution",
+                },
+                UniformSignature {
+                    kind: UniformKind::Sampler2D,
+                    name: UniformName::Source,
+                    name_glsl: "u_source",
+                },
+            ],
+        }
+    }
+}
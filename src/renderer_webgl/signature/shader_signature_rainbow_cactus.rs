@@ -45,6 +45,7 @@ impl ShaderSignature {
                     name_glsl: "a_instance_log_rev",
                 },
             ],
+            feature_gated: vec![],
             name: ShaderSignatureName::RainbowCactus,
             uniform_signatures: vec![
                 UniformSignature {
@@ -1,8 +1,13 @@
+#[derive(Clone,Copy,Debug,PartialEq)]
 pub enum UniformKind {
     /// A 32 bit floating point number.
     F32,
     /// Four by four matrix of sixteen 32 bit floating point numbers.
     Mat4,
+    /// A texture unit, which the shader samples a 2D texture through.
+    Sampler2D,
+    /// Vector of two 32 bit floating point numbers.
+    Vec2,
     /// Vector of three 32 bit floating point numbers.
     Vec3,
     /// Vector of four 32 bit floating point numbers.
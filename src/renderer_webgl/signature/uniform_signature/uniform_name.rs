@@ -19,4 +19,33 @@ pub enum UniformName {
     Quaternion,
     QuaternionX,
     QuaternionY,
+
+    // Lighting Uniforms.
+    Ambient,
+    LightColor,
+    LightDirection,
+    Lighting,
+
+    // Colour Scheme Uniforms.
+    Gradient,
+    GradientCurves,
+    Primcount,
+    Spots,
+    Stripes,
+
+    // Wireframe Uniforms.
+    Wire,
+
+    // Debug Uniforms.
+    Debug,
+
+    // Texture Uniforms.
+    PetalTexture,
+    SeedTexture,
+
+    // Post-processing Uniforms.
+    PostFrame,
+    PostParams,
+    Resolution,
+    Source,
 }
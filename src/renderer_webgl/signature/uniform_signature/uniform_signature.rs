@@ -5,7 +5,8 @@ use super::{UniformKind,UniformName};
 /// there is no ‘global’ location for a uniform which all shader programs can
 /// share. Therefore, UniformSignature does not have a `location` field (unlike
 /// AttributeSignature). Instead, uniform locations are stored in a shader’s
-/// `locations_uniform` field.
+/// `uniform_locations` field.
+#[derive(Clone)]
 pub struct UniformSignature {
     /// The WebGL data type, eg UniformKind::Vec2 for two f32s in a vector.
     pub kind: UniformKind,
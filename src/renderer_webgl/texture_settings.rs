@@ -0,0 +1,100 @@
+//! How a texture is sampled: its filtering, wrapping and mipmaps.
+
+use web_sys::WebGlRenderingContext as GL;
+
+
+/// How texels are blended when a texture is drawn bigger or smaller than it is.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum TextureFilter {
+    /// The nearest texel, for crisp pixel art.
+    #[allow(dead_code)] // not used by any Scene yet
+    Nearest,
+    /// A weighted average of the four nearest texels.
+    Linear,
+}
+
+
+/// What happens to texture coordinates outside the 0.0 to 1.0 range.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum TextureWrap {
+    /// Repeats the edge texels.
+    ClampToEdge,
+    /// Tiles the texture.
+    #[allow(dead_code)] // not used by any Scene yet
+    Repeat,
+    /// Tiles the texture, flipping every other tile.
+    #[allow(dead_code)] // not used by any Scene yet
+    MirroredRepeat,
+}
+
+impl TextureWrap {
+    /// The `TEXTURE_WRAP_S` and `TEXTURE_WRAP_T` value.
+    pub fn to_gl(
+        self,
+    ) -> u32 {
+        match self {
+            TextureWrap::ClampToEdge => GL::CLAMP_TO_EDGE,
+            TextureWrap::Repeat => GL::REPEAT,
+            TextureWrap::MirroredRepeat => GL::MIRRORED_REPEAT,
+        }
+    }
+}
+
+
+/// #### How a texture created by `RkCool::create_texture()` is sampled.
+///
+/// WebGL1 can only mipmap or repeat textures whose sides are powers of two, eg
+/// 256 by 64. Other sizes need `TextureSettings::default()`, or any settings
+/// where `needs_power_of_two()` is false.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct TextureSettings {
+    pub filter: TextureFilter,
+    pub wrap: TextureWrap,
+    /// Generates smaller copies of the texture, so it doesn’t shimmer when
+    /// drawn small. Costs a third more VRAM.
+    pub mipmaps: bool,
+}
+
+impl TextureSettings {
+
+    /// Whether these settings only work for power-of-two sizes, in WebGL1.
+    pub fn needs_power_of_two(
+        &self,
+    ) -> bool {
+        self.mipmaps || self.wrap != TextureWrap::ClampToEdge
+    }
+
+    /// The `TEXTURE_MIN_FILTER` value. Mipmaps are blended between, as well
+    /// as within, when filtering is linear.
+    pub fn min_filter_to_gl(
+        &self,
+    ) -> u32 {
+        match (self.filter, self.mipmaps) {
+            (TextureFilter::Nearest, false) => GL::NEAREST,
+            (TextureFilter::Nearest, true) => GL::NEAREST_MIPMAP_NEAREST,
+            (TextureFilter::Linear, false) => GL::LINEAR,
+            (TextureFilter::Linear, true) => GL::LINEAR_MIPMAP_LINEAR,
+        }
+    }
+
+    /// The `TEXTURE_MAG_FILTER` value. Mipmaps are never used when magnifying.
+    pub fn mag_filter_to_gl(
+        &self,
+    ) -> u32 {
+        match self.filter {
+            TextureFilter::Nearest => GL::NEAREST,
+            TextureFilter::Linear => GL::LINEAR,
+        }
+    }
+}
+
+/// Linear filtering, clamped to its edges, with no mipmaps — works for any size.
+impl Default for TextureSettings {
+    fn default() -> Self {
+        Self {
+            filter: TextureFilter::Linear,
+            wrap: TextureWrap::ClampToEdge,
+            mipmaps: false,
+        }
+    }
+}
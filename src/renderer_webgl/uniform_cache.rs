@@ -0,0 +1,96 @@
+//! Remembers the uniform values most recently sent to a shader program.
+
+use super::UniformName;
+
+
+/// #### Remembers the uniform values most recently sent to one shader program.
+///
+/// WebGL keeps uniform values per program, and they persist until changed. So
+/// if a value has not changed since the last `uniform*()` call, there’s no
+/// need to send it again.
+pub struct UniformCache {
+    values: Vec<(UniformName, Vec<f32>)>,
+}
+
+impl UniformCache {
+
+    pub fn new() -> Self {
+        Self {
+            values: vec![],
+        }
+    }
+
+    /// Returns `true` if `value` is the cached value, so WebGL already has it.
+    pub fn contains(
+        &self,
+        uniform_name: UniformName,
+        value: &[f32],
+    ) -> bool {
+        self.values.iter().any(|(name, cached)| *name == uniform_name && cached[..] == value[..])
+    }
+
+    /// Stores `value`, and returns `true` if it differs from the cached value.
+    /// Returns `false` if WebGL already has this value, so the call can be skipped.
+    pub fn update(
+        &mut self,
+        uniform_name: UniformName,
+        value: &[f32],
+    ) -> bool {
+        for (name, cached) in self.values.iter_mut() {
+            if *name == uniform_name {
+                if cached[..] == value[..] { return false }
+                cached.clear();
+                cached.extend_from_slice(value);
+                return true;
+            }
+        }
+        self.values.push((uniform_name, value.to_vec()));
+        true
+    }
+
+    /// Iterates over every cached uniform name and value, in the order that
+    /// they were first sent.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = &(UniformName, Vec<f32>)> {
+        self.values.iter()
+    }
+
+}
+
+
+/// Counts WebGL calls which were skipped, because they would have changed nothing.
+#[derive(Clone,Copy,Default)]
+pub struct SkippedCalls {
+    /// `gl.bindTexture()` calls skipped, because that texture was already bound to that unit.
+    pub bind_texture: u32,
+    /// `RkWarm::set_pipeline_state()` calls skipped, because WebGL was already in that state.
+    pub pipeline_state: u32,
+    /// `gl.useProgram()` calls skipped, because that program was already active.
+    pub use_program: u32,
+    /// `gl.uniform*()` calls skipped, because the uniform already had that value.
+    pub uniform: u32,
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_only_changed_values() {
+        let mut uniform_cache = UniformCache::new();
+        assert!(! uniform_cache.contains(UniformName::ViewMatrix, &[1., 2.]));
+        assert!(uniform_cache.update(UniformName::ViewMatrix, &[1., 2.]));
+        assert!(uniform_cache.contains(UniformName::ViewMatrix, &[1., 2.]));
+        assert!(! uniform_cache.update(UniformName::ViewMatrix, &[1., 2.]));
+        assert!(uniform_cache.update(UniformName::ViewMatrix, &[1., 3.]));
+        assert!(! uniform_cache.contains(UniformName::ViewMatrix, &[1., 2.]));
+        // Each uniform is cached separately.
+        assert!(! uniform_cache.contains(UniformName::ProjectionMatrix, &[1., 3.]));
+        assert!(uniform_cache.update(UniformName::ProjectionMatrix, &[1., 3.]));
+        assert_eq!(uniform_cache.iter().count(), 2);
+    }
+}
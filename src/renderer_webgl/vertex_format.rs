@@ -0,0 +1,191 @@
+//! Describes how several attributes are interleaved in one vertex buffer.
+
+use crate::error::ERROR as E;
+use super::{AttributeKind,AttributeName,RendererError,ShaderSignature};
+
+
+/// One attribute’s place in an interleaved vertex.
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct VertexFormatEntry {
+    pub kind: AttributeKind,
+    pub name: AttributeName,
+    /// Bytes from the start of the vertex, always a multiple of `kind.component_bytes()`.
+    pub offset: i32,
+}
+
+
+/// One attribute’s values, for `VertexFormat::interleave()`. The element type
+/// must match the AttributeKind, eg `U8` for `AttributeKind::U8Vec3Norm`.
+pub enum VertexColumn<'a> {
+    F32(&'a [f32]),
+    I16(&'a [i16]),
+    U8(&'a [u8]),
+}
+
+
+/// #### Describes how several attributes are interleaved in one vertex buffer.
+///
+/// Eg position (Vec3) then colour (U8Vec3Norm) packs into 16 bytes per vertex:
+/// twelve for the position, three for the colour and one of padding. Keeping
+/// each vertex’s attributes together means one buffer and one `bind_buffer()`
+/// call, instead of one per attribute.
+#[derive(Clone,Debug,PartialEq)]
+pub struct VertexFormat {
+    pub attributes: Vec<VertexFormatEntry>,
+    /// Bytes from the start of one vertex to the start of the next.
+    pub stride: i32,
+}
+
+impl VertexFormat {
+
+    /// Lays out the named attributes in order, taking each one’s AttributeKind
+    /// from `signature`. Each offset is padded to suit its component type, and
+    /// the stride is padded so that every vertex starts suitably aligned.
+    pub fn new(
+        signature: &ShaderSignature,
+        names: &[AttributeName],
+    ) -> Result<Self, RendererError> {
+        let mut attributes = vec![];
+        let mut offset = 0;
+        let mut alignment = 1;
+        for name in names.iter() {
+            let kind = signature.attribute_signatures.iter()
+                .find(|attribute_signature| attribute_signature.name == *name)
+                .map(|attribute_signature| attribute_signature.kind)
+                .ok_or_else(|| RendererError::new(E::R33512,
+                    format!("{:?} does not use AttributeName::{:?}", signature.name, name)))?;
+            let component_bytes = kind.component_bytes();
+            offset = round_up(offset, component_bytes);
+            alignment = alignment.max(component_bytes);
+            attributes.push(VertexFormatEntry { kind, name: *name, offset });
+            offset += kind.components() * component_bytes;
+        }
+        Ok(Self {
+            attributes,
+            stride: round_up(offset, alignment),
+        })
+    }
+
+    /// Packs one column of values per attribute, in the same order as `names`
+    /// was given to `new()`, into bytes ready for `RkCool::create_buffer()`.
+    pub fn interleave(
+        &self,
+        columns: &[VertexColumn],
+    ) -> Result<Vec<u8>, RendererError> {
+        if columns.len() != self.attributes.len() {
+            return Err(RendererError::new(E::R33593, format!(
+                "{} columns for {} attributes", columns.len(), self.attributes.len())));
+        }
+
+        // Every column must describe the same number of vertices.
+        let mut vertex_count = None;
+        for (entry, column) in self.attributes.iter().zip(columns.iter()) {
+            let (len, component_type_ok) = match column {
+                VertexColumn::F32(values) => (values.len(), entry.kind.component_bytes() == 4),
+                VertexColumn::I16(values) => (values.len(), entry.kind.component_bytes() == 2),
+                VertexColumn::U8(values) => (values.len(), entry.kind.component_bytes() == 1),
+            };
+            let components = entry.kind.components() as usize;
+            if ! component_type_ok || len % components != 0
+                || vertex_count.is_some_and(|count| count != len / components) {
+                return Err(RendererError::new(E::R33593, format!(
+                    "{:?} has {} values, of the wrong type or count for {:?}", entry.name, len, entry.kind)));
+            }
+            vertex_count = Some(len / components);
+        }
+
+        let vertex_count = vertex_count.unwrap_or(0);
+        let mut bytes = vec![0; vertex_count * self.stride as usize];
+        for (entry, column) in self.attributes.iter().zip(columns.iter()) {
+            let components = entry.kind.components() as usize;
+            for vertex in 0..vertex_count {
+                let mut at = vertex * self.stride as usize + entry.offset as usize;
+                for component in 0..components {
+                    let i = vertex * components + component;
+                    match column {
+                        VertexColumn::F32(values) => write(&mut bytes, &mut at, &values[i].to_le_bytes()),
+                        VertexColumn::I16(values) => write(&mut bytes, &mut at, &values[i].to_le_bytes()),
+                        VertexColumn::U8(values) => write(&mut bytes, &mut at, &[values[i]]),
+                    }
+                }
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+
+
+
+// HELPERS
+
+/// Eg `round_up(13, 4)` is 16.
+fn round_up(
+    value: i32,
+    multiple: i32,
+) -> i32 {
+    (value + multiple - 1) / multiple * multiple
+}
+
+/// Copies `source` into `bytes` at `*at`, and moves `*at` past it.
+fn write(
+    bytes: &mut [u8],
+    at: &mut usize,
+    source: &[u8],
+) {
+    bytes[*at..*at + source.len()].copy_from_slice(source);
+    *at += source.len();
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ShaderSignatureName;
+
+    #[test]
+    fn pads_offsets_and_stride() {
+        let signature = ShaderSignature::new(ShaderSignatureName::FurlBasic);
+        let vertex_format = VertexFormat::new(&signature, &[AttributeName::Position,
+            AttributeName::Color, AttributeName::Normal, AttributeName::TexCoord, AttributeName::Spike]).unwrap();
+        let offsets: Vec<i32> = vertex_format.attributes.iter().map(|entry| entry.offset).collect();
+        assert_eq!(offsets, vec![0, 12, 16, 24, 32]);
+        assert_eq!(vertex_format.stride, 36);
+    }
+
+    #[test]
+    fn interleaves_columns() {
+        let signature = ShaderSignature::new(ShaderSignatureName::Guides);
+        let vertex_format = VertexFormat::new(&signature, &[AttributeName::Position, AttributeName::Color]).unwrap();
+        assert_eq!(vertex_format.stride, 16);
+        let bytes = vertex_format.interleave(&[
+            VertexColumn::F32(&[1., 2., 3., 4., 5., 6.]),
+            VertexColumn::U8(&[10, 20, 30, 40, 50, 60]),
+        ]).unwrap();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[0..4], 1f32.to_le_bytes());
+        assert_eq!(bytes[12..16], [10, 20, 30, 0]);
+        assert_eq!(bytes[16..20], 4f32.to_le_bytes());
+        assert_eq!(bytes[28..32], [40, 50, 60, 0]);
+    }
+
+    #[test]
+    fn rejects_mismatched_columns() {
+        let signature = ShaderSignature::new(ShaderSignatureName::Guides);
+        let vertex_format = VertexFormat::new(&signature, &[AttributeName::Position, AttributeName::Color]).unwrap();
+        let wrong_count = vertex_format.interleave(&[
+            VertexColumn::F32(&[1., 2., 3., 4., 5., 6.]),
+            VertexColumn::U8(&[10, 20, 30]),
+        ]);
+        assert_eq!(wrong_count.unwrap_err().code, E::R33593);
+        let wrong_type = vertex_format.interleave(&[
+            VertexColumn::F32(&[1., 2., 3.]),
+            VertexColumn::F32(&[0.1, 0.2, 0.3]),
+        ]);
+        assert_eq!(wrong_type.unwrap_err().code, E::R33593);
+        let unused = VertexFormat::new(&signature, &[AttributeName::Spike]);
+        assert_eq!(unused.unwrap_err().code, E::R33512);
+    }
+}
@@ -9,6 +9,9 @@ pub use projection::{ortho,orthographic,perspective};
 mod rotate;
 pub use rotate::{rotate_x,rotate_y};
 
+mod scale;
+pub use scale::scale;
+
 mod translate;
 pub use translate::translate;
 
@@ -0,0 +1,15 @@
+//! Utilities for scaling matrices.
+
+pub fn scale(
+    m: [f32; 16], // 4x4 matrix
+    sx: f32,
+    sy: f32,
+    sz: f32,
+) -> [f32; 16] {
+    [
+        m[0]*sx,  m[1]*sx,  m[2]*sx,  m[3]*sx,
+        m[4]*sy,  m[5]*sy,  m[6]*sy,  m[7]*sy,
+        m[8]*sz,  m[9]*sz,  m[10]*sz, m[11]*sz,
+        m[12],    m[13],    m[14],    m[15],
+    ]
+}
@@ -5,6 +5,11 @@ pub mod matrix;
 mod pnrg;
 pub use pnrg::SimplePrng;
 
+mod quad_view;
+pub use quad_view::{quad_view_labels_json,render_quad_view};
+
+pub mod quat;
+
 mod wow;
 pub use wow::wow;
 
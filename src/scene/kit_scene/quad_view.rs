@@ -0,0 +1,81 @@
+//! Four views of a Scene at once, each in its own quarter of the framebuffer.
+
+use crate::develop::CameraPreset;
+use crate::renderer_webgl::{PipelineState,RendererError,RendererWebGl,RkWarm};
+
+/// The colour of the lines between the panes, and around them.
+const BORDER_COLOR: [f32; 4] = [0.56, 0.76, 0.7, 1.0];
+
+/// How far each pane is inset, so the lines between panes are twice as wide.
+const BORDER_INSET_PX: i32 = 1;
+
+/// Which camera each pane shows, and its label: the top view above the front
+/// view, and the left view beside it, as in a technical drawing.
+const PANE_CAMERAS: [(CameraPreset, &str); 4] = [
+    (CameraPreset::OrthographicTop, "Top"),          // top left
+    (CameraPreset::ChosenByScene, "Scene’s camera"), // top right
+    (CameraPreset::OrthographicFront, "Front"),      // bottom left
+    (CameraPreset::OrthographicLeft, "Left"),        // bottom right
+];
+
+
+/// Returns a JSON array of the pane labels, in reading order: top left, top
+/// right, bottom left, bottom right. The labels are not drawn by WebGL, so
+/// index.html overlays them on the canvas. They are on-screen only, and never
+/// appear in frames from `frw_capture()` or images from `frw_still()`.
+pub fn quad_view_labels_json() -> String {
+    let labels: Vec<String> = PANE_CAMERAS.iter()
+        .map(|(_, label)| format!(r#""{}""#, label))
+        .collect();
+    format!("[{}]", labels.join(","))
+}
+
+/// Clears the bound framebuffer to a border colour, then for each pane,
+/// scissors and clears it, points the viewport at it, and calls `render_pane()`
+/// with its camera. Each pane has about the same aspect ratio as the whole
+/// framebuffer, so the Scene’s projections need no changes. Afterwards, the
+/// viewport and the Scene’s PipelineState are restored.
+pub fn render_quad_view<F>(
+    r: &RendererWebGl,
+    mut render_pane: F,
+) -> Result<(), RendererError>
+where
+    F: FnMut(CameraPreset) -> Result<(), RendererError>,
+{
+    let scene_pipeline_state = r.pipeline_state.get();
+    let [width, height] = r.framebuffer_size.get();
+    let (half_width, half_height) = (width / 2, height / 2);
+
+    RkWarm::set_pipeline_state(r, &PipelineState {
+        clear_color: BORDER_COLOR,
+        scissor: None,
+        ..scene_pipeline_state
+    })?;
+    RkWarm::clear(r)?;
+
+    let viewports = [
+        [0, half_height, half_width, height - half_height],
+        [half_width, half_height, width - half_width, height - half_height],
+        [0, 0, half_width, half_height],
+        [half_width, 0, width - half_width, half_height],
+    ];
+    for ((camera_preset, _), viewport) in PANE_CAMERAS.iter().zip(viewports.iter()) {
+        let [x, y, pane_width, pane_height] = *viewport;
+        RkWarm::set_pipeline_state(r, &PipelineState {
+            // WebGL rejects a negative size, eg for a pane under 2px wide.
+            scissor: Some([
+                x + BORDER_INSET_PX,
+                y + BORDER_INSET_PX,
+                (pane_width - BORDER_INSET_PX * 2).max(0),
+                (pane_height - BORDER_INSET_PX * 2).max(0),
+            ]),
+            ..scene_pipeline_state
+        })?;
+        RkWarm::clear(r)?;
+        RkWarm::set_viewport(r, *viewport)?;
+        render_pane(*camera_preset)?;
+    }
+
+    RkWarm::set_pipeline_state(r, &scene_pipeline_state)?;
+    RkWarm::set_viewport(r, [0, 0, width, height])
+}
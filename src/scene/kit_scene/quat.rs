@@ -0,0 +1,55 @@
+//! Quaternion helpers, which match `quat.glsl`. They let a Scene work out on
+//! the CPU where a shader will move a point.
+
+/// Creates a quaternion from a normalised axis and an angle, in radians.
+pub fn quat_from_axis_angle(
+    axis: [f32; 3],
+    angle: f32,
+) -> [f32; 4] {
+    let half_angle = angle * 0.5;
+    let sin = half_angle.sin();
+    [axis[0] * sin, axis[1] * sin, axis[2] * sin, half_angle.cos()]
+}
+
+/// Applies the quaternion `q` to the vector `v`.
+pub fn rotate_vector(
+    q: [f32; 4],
+    v: [f32; 3],
+) -> [f32; 3] {
+    let q_xyz = [q[0], q[1], q[2]];
+    let c = cross(q_xyz, v);
+    let t = cross(q_xyz, [c[0] + q[3] * v[0], c[1] + q[3] * v[1], c[2] + q[3] * v[2]]);
+    [v[0] + 2. * t[0], v[1] + 2. * t[1], v[2] + 2. * t[2]]
+}
+
+/// Rotates a point about the x-axis.
+pub fn rotate_about_x_axis(
+    pt: [f32; 3],
+    angle: f32,
+) -> [f32; 3] {
+    rotate_vector(quat_from_axis_angle([1., 0., 0.], angle), pt)
+}
+
+/// Rotates a point about the y-axis.
+pub fn rotate_about_y_axis(
+    pt: [f32; 3],
+    angle: f32,
+) -> [f32; 3] {
+    rotate_vector(quat_from_axis_angle([0., 1., 0.], angle), pt)
+}
+
+
+
+
+// HELPERS
+
+fn cross(
+    a: [f32; 3],
+    b: [f32; 3],
+) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
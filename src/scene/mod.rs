@@ -1,6 +1,7 @@
 //! Switching between complex Scenes may be slow — do this rarely!
 
 mod kit_scene;
+pub use kit_scene::quad_view_labels_json;
 
 mod scene_container;
 pub use scene_container::{SceneContainer,SceneContainerName};
@@ -19,3 +20,6 @@ pub use scene_rainbow_cactus::SceneRainbowCactus;
 
 mod scene;
 pub use scene::Scene;
+
+mod texture_name;
+pub use texture_name::TextureName;
@@ -1,6 +1,8 @@
+use web_sys::HtmlImageElement;
 use crate::app::Timer;
 use crate::develop::Develop;
-use crate::renderer_webgl::RendererWebGl;
+use crate::renderer_webgl::{PipelineState,RendererError,RendererWebGl};
+use super::TextureName;
 
 pub trait Scene {
     fn render(
@@ -8,7 +10,35 @@ pub trait Scene {
         _develop: &Develop,
         _renderer: &RendererWebGl,
         _timer: &Timer,
-    ) {}
+    ) -> Result<(), RendererError> {
+        Ok(()) // renders nothing by default
+    }
+
+    /// Rebuilds the Scene’s Shaders and buffers from its retained CPU-side
+    /// data, after the browser has dropped and then restored the WebGL context.
+    fn restore(
+        &mut self,
+        _renderer: &mut RendererWebGl,
+    ) -> Result<(), RendererError> {
+        Ok(()) // nothing to rebuild by default
+    }
+
+    /// Replaces one of the Scene’s textures with an image which JavaScript has
+    /// loaded. The Scene decides how it’s filtered, wrapped and mipmapped.
+    fn set_texture(
+        &mut self,
+        _renderer: &RendererWebGl,
+        _texture_name: TextureName,
+        _image: &HtmlImageElement,
+    ) -> Result<(), RendererError> {
+        Ok(()) // no textures by default
+    }
+
+    /// The fixed-function state the Scene renders with, eg its clear colour.
+    /// The App applies it before clearing, on every render.
+    fn get_pipeline_state(&self) -> PipelineState {
+        PipelineState::default()
+    }
 
     fn get_fieldsets(&self) -> String {
         "[]".into() // empty array by default
@@ -1,38 +1,58 @@
-//! Demonstrates a single Furl, with no ‘stripes’ or ‘spots’.
+//! Demonstrates a single Furl, coloured with a gradient, ‘stripes’ and ‘spots’.
 
-use web_sys::WebGlRenderingContext as GL;
+use web_sys::{HtmlImageElement,WebGlRenderingContext as GL};
 use crate::app::Timer;
 use crate::develop::{
     CameraPreset,
+    DebugPreset,
     Develop,
     GuidesPreset,
+    LightingPreset,
     LodPreset,
     WireframePreset,
 };
+use crate::error::ERROR as E;
 use crate::renderer_webgl::{
     AttributeName,
+    BlendMode,
+    BufferHandle,
+    DepthTest,
+    IndexBuffer,
+    PipelineState,
+    RendererError,
     RkCool,
+    RkTepid,
     RkWarm,
     RendererWebGl,
+    ShaderFeatures,
     ShaderProgramName,
     ShaderSignatureName,
-    UniformName
+    TextureHandle,
+    TextureSettings,
+    UniformName,
+    VertexColumn,
+    VertexFormat,
 };
-use crate::shape::{Point3,ShapeAxes,ShapeFurl,ShapeGrids};
-use super::kit_scene::{PI,SimplePrng,wow};
-use super::kit_scene::matrix::{dot,IDENTITY,ortho,orthographic,perspective,rotate_x,rotate_y,translate};
-use super::Scene;
+use crate::shape::{box_corners,Point3,ShapeAxes,ShapeBox,ShapeFurl,ShapeGrids};
+use super::kit_scene::{PI,render_quad_view,SimplePrng,wow};
+use super::kit_scene::matrix::{dot,IDENTITY,ortho,orthographic,perspective,rotate_x,rotate_y,scale,translate};
+use super::kit_scene::quat::{quat_from_axis_angle,rotate_about_x_axis,rotate_about_y_axis,rotate_vector};
+use super::{Scene,TextureName};
 
 // Six fieldsets, each containing four pairs (val_a and val_b) plus four Shadermix, plus four Timermix.
 const NUM_AB: usize = 6 * 16;
 
-// Each fieldset contains various numbers of sliders, but there are 7 val_s sliders in total.
-const NUM_SINGLE: usize = 7;
+// Each fieldset contains various numbers of sliders, but there are 24 val_s sliders in total.
+const NUM_SINGLE: usize = 24;
 
 const MAX_INSTANCES: usize = 1024;
 
+// How far the tips of DebugPreset::Normals’ spikes stick out, in metres.
+const NORMAL_SPIKE_LENGTH: f32 = 0.03;
+
 struct ShaderIndices {
-    furl_basic: usize,
+    furl_basic: usize, // with ShaderFeatures::INSTANCE_COLOR
+    furl_plain: usize, // FurlBasic without it, for when no instance colours are mixed in
     guides: usize,
 }
 
@@ -56,6 +76,8 @@ pub struct Quaternions {
 
 pub struct Guides {
     axes: ShapeAxes,
+    /// Stretched round the whole Furl by DebugPreset::BoundingBoxes.
+    bounds: ShapeBox,
     grids: ShapeGrids,
 }
 pub struct Furls {
@@ -66,7 +88,35 @@ pub struct Shapes {
     furls: Furls,
 }
 
+/// Bound to texture units 0 and 1, which the FurlBasic samplers read.
+struct Textures {
+    petal: TextureHandle,
+    seed: TextureHandle,
+}
+
+/// CPU-side data, kept so that the buffers can be rebuilt after context loss.
+struct Retained {
+    cnnx: Vec<u16>,
+    colors: Vec<f32>,
+    curves: Vec<f32>,
+    normals: Vec<i16>,
+    spikes: Vec<f32>,
+    /// One random number per instance. Instances below the spot density are spots.
+    spots: Vec<f32>,
+    /// The images JavaScript has sent, if any. Otherwise the textures are white.
+    petal_image: Option<HtmlImageElement>,
+    seed_image: Option<HtmlImageElement>,
+    uvs: Vec<f32>,
+    vertices: Vec<f32>,
+}
+
 pub struct SceneAloneFurl {
+    buffer_spots: BufferHandle,
+    /// Holds the cnnx of every Shape, so each Shape’s render() needs it.
+    cnnx: IndexBuffer,
+    /// The FurlBasic variant which render() last used, `shader_indices.furl_basic`
+    /// or `.furl_plain`.
+    furl_shader: usize,
     iu_angle: [f32;16],
     iu_bulge: [f32;16],
     iu_lean: [f32;16],
@@ -77,9 +127,13 @@ pub struct SceneAloneFurl {
     parameter_values: [f32;NUM_AB + NUM_SINGLE],
     projection: Projection,
     quaternions: Quaternions,
+    retained: Retained,
     shader_indices: ShaderIndices,
     shapes: Shapes,
     slidermix: [f32;4],
+    /// The seed which `retained.spots` was generated from.
+    spot_seed: u32,
+    textures: Textures,
     timermix: [f32;4],
     view: View,
 }
@@ -87,40 +141,7 @@ pub struct SceneAloneFurl {
 impl SceneAloneFurl {
     pub fn new (
         r: &mut RendererWebGl, // the app’s singleton Renderer instance
-    ) -> Self {
-
-
-        // SHADERS
-
-        // Init the shaders, and store them in the `renderer.shaders` vector.
-        let shader_indices = ShaderIndices {
-            furl_basic: RkCool::add_shader(r, ShaderProgramName::FurlBasic),
-            guides: RkCool::add_shader(r, ShaderProgramName::Guides),
-        };
-
-        // Make sure all Shaders have compatible ShaderSignatures. @TODO
-        // This restriction means that we can switch between shaders in render()
-        // without having to rebind buffers to attributes — faster, less faff.
-        let name = ShaderSignatureName::FurlBasic;
-        // if r.shaders[shader_indices.guides].get_signature_name() != &name {
-        //     panic!("Guides does not use the Furl ShaderSignature");
-        // }
-        if r.shaders[shader_indices.furl_basic].get_signature_name() != &name {
-            panic!("FurlBasic does not use the Furl ShaderSignature");
-        }
-
-        // We can’t predict which locations WebGL has assigned to each of the
-        // attributes. It seems that any given browser will alway assigns them
-        // the same way... but there’s no guarantee. In any case, each browser
-        // has its own strategy for assigning attribute locations.
-        // 
-        // Attributes, and their locations, are global. So we should be able to
-        // use any of the shaders to find the attribute locations. Once we have
-        // them, we can store them in Rust fields for the lifetime of the Scene,
-        // they _shouldn’t_ change.
-        // RkCool::store_signature_locations(r, shader_indices.furl_basic);
-
-
+    ) -> Result<Self, RendererError> {
 
 
         // PRECALCULATE
@@ -128,6 +149,7 @@ impl SceneAloneFurl {
         // This Scene supports guides.
         let mut axes = ShapeAxes::new();
         let mut grids = ShapeGrids::new();
+        let mut bounds = ShapeBox::new();
 
         // Create the Furls.
         let mut furl1 = ShapeFurl::new(
@@ -216,78 +238,69 @@ impl SceneAloneFurl {
 
 
 
-        // INSTANCE BUFFERS
-
-        let buffer_curves = RkCool::create_buffer_f32(r, curves);
-
-
-
-
-        // VERTEX BUFFERS
+        // VERTICES
 
         // Aggregate colors and vertices from all shapes, and all LoDs.
         let mut colors: Vec<f32> = vec![];
         colors.append(&mut axes.get_colors(0));
         colors.append(&mut grids.get_colors(colors.len() / 3)); // `/ 3` because each vertex is three numbers
+        colors.append(&mut bounds.get_colors(colors.len() / 3));
         colors.append(&mut furl1.get_colors(colors.len() / 3));
 
         let mut vertices: Vec<f32> = vec![];
         vertices.append(&mut axes.get_vertices(0));
         vertices.append(&mut grids.get_vertices(vertices.len() / 3)); // `/ 3` because each vertex is three numbers
+        vertices.append(&mut bounds.get_vertices(vertices.len() / 3));
+        let guides_vertices_len = vertices.len();
         vertices.append(&mut furl1.get_vertices(vertices.len() / 3));
 
-        let buffer_colors = RkCool::create_buffer_f32(r, colors);
-        let buffer_vertices = RkCool::create_buffer_f32(r, vertices);
+        // ShapeAxes and ShapeGrids are never lit, so their normals are zero.
+        let mut normals: Vec<i16> = vec![0; guides_vertices_len];
+        normals.append(&mut furl1.get_normals(normals.len() / 3));
+
+        // The guides are never textured either, so their uvs are zero.
+        let mut uvs: Vec<f32> = vec![0.; guides_vertices_len / 3 * 2];
+        uvs.append(&mut furl1.get_uvs(uvs.len() / 2)); // `/ 2` because each uv is two numbers
+
+        // Nor do the guides have normal spikes.
+        let mut spikes: Vec<f32> = vec![0.; guides_vertices_len / 3];
+        spikes.append(&mut furl1.get_spikes(spikes.len()));
 
         // @TODO describe
         // Note that ShapeGrids does not need to connect together any vertices.
         let mut cnnx: Vec<u16> = vec![];
         cnnx.append(&mut axes.get_cnnx(0));
+        cnnx.append(&mut bounds.get_cnnx(cnnx.len()));
         cnnx.append(&mut furl1.get_cnnx(cnnx.len()));
-        RkCool::create_index_buffer_u16(r, cnnx);
-
-
-
-
-        // INDEX ATTRIBUTES
-
-        // From WebGL’s point of view, these are global variables which can be
-        // read by all shader programs. Each shader program maintains its own
-        // set of ‘locations’ (indices) for the attributes that it uses, 
-        // __BUT WE HAVE ARRANGED IT SO THAT LOCATIONS MATCH IN ALL SHADERS__.  
-        //
-        // - Attributes can be read by vertex shaders, but not fragment shaders
-        // - Attributes are disabled by default — so, enable_attribute()
-
-        RkCool::enable_attribute(r, AttributeName::Curves);
-
-        // Point the Shader attributes to the correct buffers.
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&buffer_curves));
-        RkCool::use_attribute(r, AttributeName::Curves, 4);
-
-        // @TODO describe
-        RkCool::set_repeat_gap(r, AttributeName::Curves, 1);
-
 
+        // Keep the CPU-side data, in case the WebGL context is lost.
+        let retained = Retained {
+            cnnx,
+            colors,
+            curves,
+            normals,
+            spikes,
+            spots: random_spots(0),
+            petal_image: None,
+            seed_image: None,
+            uvs,
+            vertices,
+        };
 
 
-        // VERTEX ATTRIBUTES
 
-        RkCool::enable_attribute(r, AttributeName::Color);
-        RkCool::enable_attribute(r, AttributeName::Position);
 
-        // Point the Shader attributes to the correct buffers.
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&buffer_colors));
-        RkCool::use_attribute(r, AttributeName::Color, 3);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&buffer_vertices));
-        RkCool::use_attribute(r, AttributeName::Position, 3);
+        // GPU
 
-        // Switch to ShaderFurlBasic, ready for the first render() call.
-        RkCool::use_shader(r, shader_indices.furl_basic);
+        let (shader_indices, cnnx, textures, buffer_spots) = Self::init_gpu(r, &retained)?;
 
         let ortho_zoom = 2.8; // smaller is more zoomed in
 
-        Self {
+        Ok(Self {
+            buffer_spots,
+            cnnx,
+            furl_shader: shader_indices.furl_basic,
+
             // Set initial values for the instance uniforms. These will be 
             // filled using parameters b_2, b_3, s_2, etc.
             iu_angle: [0.;16],
@@ -341,10 +354,12 @@ impl SceneAloneFurl {
                     0.0
                 ],
             },
+            retained,
             shader_indices,
             shapes: Shapes {
                 guides: Guides {
                     axes,
+                    bounds,
                     grids,
                 },
                 furls: Furls {
@@ -352,6 +367,8 @@ impl SceneAloneFurl {
                 },
             },
             slidermix: [0., 0., 0., 0.],
+            spot_seed: 0,
+            textures,
             timermix: [0., 0., 0., 0.],
             view: View {
                 choice: dot(
@@ -370,17 +387,201 @@ impl SceneAloneFurl {
                 ),
 
             },
+        })
+    }
+
+    /// Creates the Shaders, buffers and textures, from the retained CPU-side
+    /// data. Called again by restore(), if the WebGL context is lost and then
+    /// restored.
+    fn init_gpu (
+        r: &mut RendererWebGl, // the app’s singleton Renderer instance
+        retained: &Retained,
+    ) -> Result<(ShaderIndices, IndexBuffer, Textures, BufferHandle), RendererError> {
+
+
+        // SHADERS
+
+        // Init the shaders, and store them in the `renderer.shaders` vector.
+        let shader_indices = ShaderIndices {
+            furl_basic: RkCool::add_shader(r, ShaderProgramName::FurlBasic, ShaderFeatures::INSTANCE_COLOR)?,
+            furl_plain: RkCool::add_shader(r, ShaderProgramName::FurlBasic, ShaderFeatures::NONE)?,
+            guides: RkCool::add_shader(r, ShaderProgramName::Guides, ShaderFeatures::NONE)?,
+        };
+
+        // Make sure all Shaders have compatible ShaderSignatures. @TODO
+        // This restriction means that we can switch between shaders in render()
+        // without having to rebind buffers to attributes — faster, less faff.
+        let name = ShaderSignatureName::FurlBasic;
+        // if r.shaders[shader_indices.guides].get_signature_name() != &name {
+        //     panic!("Guides does not use the Furl ShaderSignature");
+        // }
+        if r.shaders[shader_indices.furl_basic].get_signature_name() != &name {
+            return Err(RendererError::new(E::R33617,
+                "FurlBasic does not use the Furl ShaderSignature".into()));
+        }
+
+        // We can’t predict which locations WebGL has assigned to each of the
+        // attributes. It seems that any given browser will alway assigns them
+        // the same way... but there’s no guarantee. In any case, each browser
+        // has its own strategy for assigning attribute locations.
+        // 
+        // Attributes, and their locations, are global. So we should be able to
+        // use any of the shaders to find the attribute locations. Once we have
+        // them, we can store them in Rust fields for the lifetime of the Scene,
+        // they _shouldn’t_ change.
+        // RkCool::store_signature_locations(r, shader_indices.furl_basic);
+
+        // FurlBasic and Guides share the `position` and `color` attributes.
+        // Make sure that WebGL has given them the same locations in both.
+        RkCool::confirm_signature_locations(r, &[
+            shader_indices.furl_basic,
+            shader_indices.furl_plain,
+            shader_indices.guides,
+        ])?;
+
+
+
+
+        // INSTANCE BUFFERS
+
+        let buffer_curves = RkCool::create_buffer_f32(r, &retained.curves)?;
+
+        // Updated whenever the spots’ seed slider moves.
+        let buffer_spots = RkTepid::create_dynamic_buffer_f32(r, &retained.spots)?;
+
+        // Each instance’s index, for DebugPreset::InstanceIds.
+        let indices: Vec<f32> = (0..MAX_INSTANCES).map(|index| index as f32).collect();
+        let buffer_indices = RkCool::create_buffer_f32(r, &indices)?;
+
+
+
+
+        // VERTEX BUFFERS
+
+        // Interleave each vertex’s position, colour, normal, uv and spike in a
+        // single buffer. The colours become normalised u8s and the normals
+        // normalised i16s, so each vertex is 36 bytes, not 48.
+        let vertex_format = VertexFormat::new(
+            r.shaders[shader_indices.furl_basic].get_signature(),
+            &[AttributeName::Position, AttributeName::Color, AttributeName::Normal, AttributeName::TexCoord, AttributeName::Spike],
+        )?;
+        let colors: Vec<u8> = retained.colors.iter()
+            .map(|color| (color.clamp(0., 1.) * 255.).round() as u8)
+            .collect();
+        let buffer_vertices = RkCool::create_buffer(r, GL::ARRAY_BUFFER, &vertex_format.interleave(&[
+            VertexColumn::F32(&retained.vertices),
+            VertexColumn::U8(&colors),
+            VertexColumn::I16(&retained.normals),
+            VertexColumn::F32(&retained.uvs),
+            VertexColumn::F32(&retained.spikes),
+        ])?)?;
+        let cnnx = RkCool::create_index_buffer_u16(r, &retained.cnnx)?;
+
+
+
+
+        // INDEX ATTRIBUTES
+
+        // From WebGL’s point of view, these are global variables which can be
+        // read by all shader programs. Each shader program maintains its own
+        // set of ‘locations’ (indices) for the attributes that it uses, 
+        // __BUT WE HAVE ARRANGED IT SO THAT LOCATIONS MATCH IN ALL SHADERS__.  
+        //
+        // - Attributes can be read by vertex shaders, but not fragment shaders
+        // - Attributes are disabled by default — so, enable_attribute()
+
+        RkCool::enable_attribute(r, AttributeName::Curves)?;
+        RkCool::enable_attribute(r, AttributeName::InstanceSpot)?;
+        RkCool::enable_attribute(r, AttributeName::InstanceIndex)?;
+
+        // Point the Shader attributes to the correct buffers.
+        RkCool::bind_buffer(r, buffer_curves)?;
+        RkCool::use_attribute(r, AttributeName::Curves, 4)?;
+        RkCool::bind_buffer(r, buffer_spots)?;
+        RkCool::use_attribute(r, AttributeName::InstanceSpot, 1)?;
+        RkCool::bind_buffer(r, buffer_indices)?;
+        RkCool::use_attribute(r, AttributeName::InstanceIndex, 1)?;
+
+        // @TODO describe
+        RkCool::set_repeat_gap(r, AttributeName::Curves, 1)?;
+        RkCool::set_repeat_gap(r, AttributeName::InstanceSpot, 1)?;
+        RkCool::set_repeat_gap(r, AttributeName::InstanceIndex, 1)?;
+
+
+
+
+        // VERTEX ATTRIBUTES
+
+        RkCool::enable_attribute(r, AttributeName::Color)?;
+        RkCool::enable_attribute(r, AttributeName::Normal)?;
+        RkCool::enable_attribute(r, AttributeName::Position)?;
+        RkCool::enable_attribute(r, AttributeName::Spike)?;
+        RkCool::enable_attribute(r, AttributeName::TexCoord)?;
+
+        // Point the Shader attributes to their parts of the interleaved buffer.
+        RkCool::use_vertex_format(r, buffer_vertices, &vertex_format)?;
+
+        // Switch to the FurlBasic shader, ready for the first render() call.
+        RkCool::use_shader(r, shader_indices.furl_basic)?;
+
+
+
+
+        // LIGHTS
+
+        // A warm key light from above, front and to the right, in world space.
+        // Both FurlBasic variants keep their own uniform values.
+        let furl_shaders = [shader_indices.furl_basic, shader_indices.furl_plain];
+        for furl_shader in furl_shaders {
+            RkTepid::set_uniform_point3_f32(r, furl_shader, UniformName::Ambient, Point3::new(0.25, 0.25, 0.3))?;
+            RkTepid::set_uniform_point3_f32(r, furl_shader, UniformName::LightColor, Point3::new(1., 0.95, 0.85))?;
+            RkTepid::set_uniform_point3_f32(r, furl_shader, UniformName::LightDirection, Point3::new(0.4, 0.8, 0.45))?;
+        }
+
+
+
+
+        // TEXTURES
+
+        let textures = Textures {
+            petal: Self::create_texture(r, retained.petal_image.as_ref())?,
+            seed: Self::create_texture(r, retained.seed_image.as_ref())?,
+        };
+        for furl_shader in furl_shaders {
+            RkTepid::set_uniform_sampler2d(r, furl_shader, UniformName::PetalTexture, 0)?;
+            RkTepid::set_uniform_sampler2d(r, furl_shader, UniformName::SeedTexture, 1)?;
+        }
+
+        Ok((shader_indices, cnnx, textures, buffer_spots))
+    }
+
+    /// Creates a texture from an image sent by JavaScript, or a single white
+    /// texel if there’s none yet. Images whose sides are powers of two are
+    /// mipmapped, so that distant Furls don’t shimmer.
+    fn create_texture(
+        r: &RendererWebGl,
+        image: Option<&HtmlImageElement>,
+    ) -> Result<TextureHandle, RendererError> {
+        match image {
+            Some(image) => {
+                let mipmaps = image.natural_width().is_power_of_two()
+                    && image.natural_height().is_power_of_two();
+                RkCool::create_texture_from_image(r, image, TextureSettings { mipmaps, ..TextureSettings::default() })
+            },
+            None => RkCool::create_texture(r, 1, 1, &[255, 255, 255, 255], TextureSettings::default()),
         }
     }
-}
 
-impl Scene for SceneAloneFurl {
-    fn render(
-        &mut self,
-        develop: &Develop, // the app’s `develop` instance
-        r: &RendererWebGl, // the app’s singleton Renderer instance
-        timer: &Timer, // the app’s `timer` instance
-    ) {
+    /// Renders the furls, and maybe the guides, as seen by one camera. Called
+    /// once per render(), or four times for a quad view.
+    fn render_view(
+        &self,
+        develop: &Develop,
+        r: &RendererWebGl,
+        camera_preset: CameraPreset,
+        primcount: u32,
+        furl_bounds: Option<(Point3, Point3)>, // measured for DebugPreset::BoundingBoxes and ::Depth
+    ) -> Result<(), RendererError> {
         // Get presets from the app’s `develop` instance.
         let lod: u8 = match develop.lod_preset {
             LodPreset::All0 => 0,
@@ -388,10 +589,210 @@ impl Scene for SceneAloneFurl {
         };
         let wireframe_mode = match develop.wireframe_preset {
             WireframePreset::Dots => GL::POINTS,
-            WireframePreset::Lines => GL::LINE_STRIP,
+            WireframePreset::Lines => GL::LINES,
             _ => GL::TRIANGLES,
         };
 
+        let projection = match camera_preset {
+            CameraPreset::OrthographicFront => self.projection.orthographic_front,
+            CameraPreset::OrthographicLeft => self.projection.orthographic_left,
+            CameraPreset::OrthographicTop => self.projection.orthographic_top,
+            _ => self.projection.choice,
+        };
+        let view = match camera_preset {
+            CameraPreset::OrthographicFront => self.view.orthographic_front,
+            CameraPreset::OrthographicLeft => self.view.orthographic_left,
+            CameraPreset::OrthographicTop => self.view.orthographic_top,
+            _ => self.view.choice,
+        };
+        let shader_index = self.furl_shader;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::ProjectionMatrix, projection)?;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::ViewMatrix, view)?;
+
+
+        // Tell FurlBasic which debug view to draw, if any. Depth is shaded from
+        // the nearest to the farthest corner of the Furl’s bounding box, as
+        // this camera sees it.
+        let debug_view = match develop.debug_preset {
+            DebugPreset::Normals => 1.,
+            DebugPreset::Depth => 2.,
+            DebugPreset::Overdraw => 3.,
+            DebugPreset::InstanceIds => 4.,
+            _ => 0.,
+        };
+        let (nearest, farthest) = match furl_bounds {
+            Some(furl_bounds) => depth_range(furl_bounds, view),
+            None => (0., 1.),
+        };
+        RkTepid::set_uniform_vec4_f32(r, shader_index, UniformName::Debug,
+            [debug_view, nearest, farthest, NORMAL_SPIKE_LENGTH])?;
+
+        // Overdraw adds up every fragment, even those hidden behind others.
+        let pipeline_state = r.pipeline_state.get();
+        if develop.debug_preset == DebugPreset::Overdraw {
+            RkWarm::set_pipeline_state(r, &PipelineState {
+                blend_mode: BlendMode::Additive,
+                depth_test: DepthTest::Off,
+                ..pipeline_state
+            })?;
+        }
+
+
+        // Render the furls. ‘Solid + Lines’ draws every edge again, in white,
+        // over the solid triangles. Normal spikes and instance boxes are drawn
+        // over them in the same way.
+        let furl1 = &self.shapes.furls.furl1;
+        furl1.render(r, shader_index, &self.cnnx, lod, wireframe_mode, primcount)?;
+        let show_edges = develop.wireframe_preset == WireframePreset::SolidAndLines;
+        let show_normals = develop.debug_preset == DebugPreset::Normals;
+        let show_bounds = develop.debug_preset == DebugPreset::BoundingBoxes;
+        if show_edges || show_normals || show_bounds {
+            RkTepid::set_uniform_f32(r, shader_index, UniformName::Wire, 1.)?;
+            if show_edges { furl1.render(r, shader_index, &self.cnnx, lod, GL::LINES, primcount)? }
+            if show_normals { furl1.render_normals(r, shader_index, &self.cnnx, lod, primcount)? }
+            if show_bounds { furl1.render_bounds(r, shader_index, &self.cnnx, primcount)? }
+            RkTepid::set_uniform_f32(r, shader_index, UniformName::Wire, 0.)?;
+        }
+        if develop.debug_preset == DebugPreset::Overdraw {
+            RkWarm::set_pipeline_state(r, &pipeline_state)?;
+        }
+
+
+        // Maybe show guides, and the box round the whole Furl.
+        // Without either showing, render() doesn’t have to call use_shader() or
+        // set_uniform_*(), so it may run a bit faster. @TODO benchmark
+        let show_guides = develop.guides_preset != GuidesPreset::ChosenByScene
+            && develop.guides_preset != GuidesPreset::NoGuides;
+        let furl_box = furl_bounds.filter(|_| show_bounds);
+        if show_guides || furl_box.is_some() {
+
+            // Switch to the Guides shader.
+            let guides = self.shader_indices.guides;
+            RkWarm::use_shader(r, guides)?;
+            RkWarm::set_uniform_mat4_f32(r, guides, UniformName::ProjectionMatrix, projection)?;
+            RkWarm::set_uniform_mat4_f32(r, guides, UniformName::ViewMatrix, view)?;
+            RkWarm::set_uniform_mat4_f32(r, guides, UniformName::ModelMatrix, IDENTITY)?;
+
+            // Blend the guides additively, so they glow where they cross.
+            RkWarm::set_pipeline_state(r, &PipelineState { blend_mode: BlendMode::Additive, ..pipeline_state })?;
+
+            // Render the ShapeAxes, or the ShapeGrids, or both.
+            let guides_preset = &develop.guides_preset;
+            match guides_preset {
+                GuidesPreset::All10m | GuidesPreset::All1m => {
+                    self.shapes.guides.axes.render(r, &self.cnnx, guides_preset)?;
+                    self.shapes.guides.grids.render(r, guides_preset)?;
+                },            
+                GuidesPreset::AxesOnly10m | GuidesPreset::AxesOnly1m => {
+                    self.shapes.guides.axes.render(r, &self.cnnx, guides_preset)?;
+                },
+                GuidesPreset::GridsOnly10m | GuidesPreset::GridsOnly1m => {
+                    self.shapes.guides.grids.render(r, guides_preset)?;
+                },
+                _ => (), // ::ChosenByScene or ::NoGuides
+            }
+
+            // Stretch the unit ShapeBox round the whole Furl.
+            if let Some((min, max)) = furl_box {
+                let model = translate(scale(IDENTITY, max.x - min.x, max.y - min.y, max.z - min.z), min.x, min.y, min.z);
+                RkWarm::set_uniform_mat4_f32(r, guides, UniformName::ModelMatrix, model)?;
+                self.shapes.guides.bounds.render(r, &self.cnnx)?;
+            }
+            RkWarm::set_pipeline_state(r, &pipeline_state)?;
+
+            // Switch back to the FurlBasic shader, ready for the next render() call.
+            // Its uniforms were kept by WebGL, so there’s no need to resend them.
+            RkWarm::use_shader(r, self.furl_shader)?;
+        }
+        Ok(())
+    }
+
+    /// The smallest box which holds every instance’s bounding box, after the
+    /// FurlBasic vertex shader has moved it. Each instance is only scaled,
+    /// rotated and moved, so its box corners are all that need transforming.
+    /// This mirrors `furl_basic.vert`, so must be kept in step with it.
+    fn get_furl_bounds(
+        &self,
+        primcount: u32,
+    ) -> (Point3, Point3) {
+        let furl1 = &self.shapes.furls.furl1;
+        let (nubbin_min, nubbin_max) = furl1.get_nubbin_bounds();
+        let corners = box_corners(nubbin_min, nubbin_max);
+        let placement = furl1.get_placement();
+        let [qx0, qx1, qx2, qx3] = self.quaternions.x;
+        let [qy0, qy1, qy2, qy3] = self.quaternions.y;
+        let quat_x = quat_from_axis_angle([qx0, qx1, qx2], qx3);
+        let quat_y = quat_from_axis_angle([qy0, qy1, qy2], qy3);
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for curves in self.retained.curves.chunks(4).take(primcount as usize) {
+            let [linear, wow, hump, flutter] = [curves[0], curves[1], curves[2], curves[3]];
+            let scale = self.interpolate(&self.iu_scale, [linear, wow, hump]);
+            let tilt = self.interpolate(&self.iu_tilt, [linear, flutter, hump]);
+            let bulge = self.interpolate(&self.iu_bulge, [linear, flutter, hump]);
+            let rise = self.interpolate(&self.iu_rise, [linear, wow, hump]);
+            let angle = self.interpolate(&self.iu_angle, [linear, wow, hump]);
+
+            // Lean’s first pair of values set how far, and its second pair
+            // which way.
+            let lean = &self.iu_lean;
+            let lean_mix = self.get_mix(lean);
+            let lean_inclination = (1. - lean_mix) * (linear * lean[0] + lean[1])
+                + lean_mix * (linear * lean[4] + lean[5]);
+            let lean_orientation = (1. - lean_mix) * (hump * lean[2] + lean[3])
+                + lean_mix * (hump * lean[6] + lean[7]);
+
+            for corner in corners.iter() {
+                let mut pt = [corner.x * scale, corner.y * scale, corner.z * scale];
+                pt = rotate_about_x_axis(pt, tilt);
+                pt[2] += bulge;
+                pt[1] -= rise;
+                pt = rotate_about_y_axis(pt, angle + lean_orientation);
+                pt = rotate_about_x_axis(pt, lean_inclination);
+                pt = rotate_about_y_axis(pt, -lean_orientation);
+                pt = rotate_vector(quat_x, pt);
+                pt = rotate_vector(quat_y, pt);
+                let pt = [pt[0] + placement.x, pt[1] + placement.y, pt[2] + placement.z];
+                for axis in 0..3 {
+                    min[axis] = min[axis].min(pt[axis]);
+                    max[axis] = max[axis].max(pt[axis]);
+                }
+            }
+        }
+        (Point3::new(min[0], min[1], min[2]), Point3::new(max[0], max[1], max[2]))
+    }
+
+    /// Like the vertex shader, mixes an iu_* uniform’s `a` and `b` values for
+    /// one instance, from three of its curves plus an invariant.
+    fn interpolate(
+        &self,
+        iu: &[f32; 16],
+        curves: [f32; 3],
+    ) -> f32 {
+        let a = curves[0] * iu[0] + curves[1] * iu[1] + curves[2] * iu[2] + iu[3];
+        let b = curves[0] * iu[4] + curves[1] * iu[5] + curves[2] * iu[6] + iu[7];
+        let mix = self.get_mix(iu);
+        (1. - mix) * a + mix * b
+    }
+
+    /// How far an iu_* uniform is from its `a` to its `b` values, from the
+    /// Slidermix and Timermix.
+    fn get_mix(
+        &self,
+        iu: &[f32; 16],
+    ) -> f32 {
+        (0..4).map(|i| self.slidermix[i] * iu[8 + i] + self.timermix[i] * iu[12 + i]).sum()
+    }
+}
+
+impl Scene for SceneAloneFurl {
+    fn render(
+        &mut self,
+        develop: &Develop, // the app’s `develop` instance
+        r: &RendererWebGl, // the app’s singleton Renderer instance
+        timer: &Timer, // the app’s `timer` instance
+    ) -> Result<(), RendererError> {
         // Convert the "_2p" slider to a `primcount` value, so 4 => 16, 5 => 32.
         let primcount = (2u32).pow(self.get_parameter_value("_2p", 0) as u32);
 
@@ -444,72 +845,123 @@ impl Scene for SceneAloneFurl {
             ((timer.beat4_continuous_normalised - 0.25) * 2.0 * PI).sin() / 2.0 + 0.5,
         ];
 
-        // @TODO only send uniforms to WebGL which have changed since the last render()? Or is that premature optimisation?
-
-        let projection = match develop.camera_preset {
-            CameraPreset::OrthographicFront => self.projection.orthographic_front,
-            CameraPreset::OrthographicLeft => self.projection.orthographic_left,
-            CameraPreset::OrthographicTop => self.projection.orthographic_top,
-            _ => self.projection.choice,
+        // The set_uniform_*() functions skip any uniform which has not changed
+        // since the last render(), so it’s fine to call them every frame.
+
+        // While no instance colours are mixed in, the FurlBasic variant without
+        // INSTANCE_COLOR skips the gradient, stripes and spots for each vertex.
+        let instance_colors = self.get_parameter_value("g_mx", 0) > 0.
+            || self.get_parameter_value("st_mx", 0) > 0.
+            || self.get_parameter_value("sp_mx", 0) > 0.;
+        self.furl_shader = if instance_colors {
+            self.shader_indices.furl_basic
+        } else {
+            self.shader_indices.furl_plain
         };
-        let view = match develop.camera_preset {
-            CameraPreset::OrthographicFront => self.view.orthographic_front,
-            CameraPreset::OrthographicLeft => self.view.orthographic_left,
-            CameraPreset::OrthographicTop => self.view.orthographic_top,
-            _ => self.view.choice,
+        let shader_index = self.furl_shader;
+        RkWarm::use_shader(r, shader_index)?;
+        let lighting = match develop.lighting_preset {
+            LightingPreset::Unlit => 0.,
+            _ => 1.,
         };
-        let shader_index = self.shader_indices.furl_basic;
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::ProjectionMatrix, projection);
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::ViewMatrix, view);
-        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::QuaternionX, self.quaternions.x);
-        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::QuaternionY, self.quaternions.y);
-        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::Slidermix, self.slidermix);
-        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::Timermix, self.timermix);
-
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Angle, self.iu_angle);
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Bulge, self.iu_bulge);
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Lean, self.iu_lean);
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Rise, self.iu_rise);
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Scale, self.iu_scale);
-        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Tilt, self.iu_tilt);
+        RkTepid::set_uniform_f32(r, shader_index, UniformName::Lighting, lighting)?; // only reaches WebGL when the preset changes
+        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::QuaternionX, self.quaternions.x)?;
+        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::QuaternionY, self.quaternions.y)?;
+        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::Slidermix, self.slidermix)?;
+        RkWarm::set_uniform_vec4_f32(r, shader_index, UniformName::Timermix, self.timermix)?;
+
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Angle, self.iu_angle)?;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Bulge, self.iu_bulge)?;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Lean, self.iu_lean)?;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Rise, self.iu_rise)?;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Scale, self.iu_scale)?;
+        RkWarm::set_uniform_mat4_f32(r, shader_index, UniformName::Tilt, self.iu_tilt)?;
+
+        // Colour whole instances with a gradient, stripes and spots. These only
+        // reach WebGL when a slider moves.
+        if instance_colors {
+            RkTepid::set_uniform_f32(r, shader_index, UniformName::Primcount, primcount as f32)?;
+            RkTepid::set_uniform_vec4_f32(r, shader_index, UniformName::Gradient, [
+                self.get_parameter_value("g_hs", 0),
+                self.get_parameter_value("g_he", 0),
+                self.get_parameter_value("g_sa", 0),
+                self.get_parameter_value("g_mx", 0),
+            ])?;
+            RkTepid::set_uniform_vec4_f32(r, shader_index, UniformName::GradientCurves, [
+                self.get_parameter_value("g_l", 0),
+                self.get_parameter_value("g_w", 0),
+                self.get_parameter_value("g_h", 0),
+                self.get_parameter_value("g_f", 0),
+            ])?;
+            RkTepid::set_uniform_vec4_f32(r, shader_index, UniformName::Stripes, [
+                self.get_parameter_value("st_n", 0),
+                self.get_parameter_value("st_w", 0),
+                self.get_parameter_value("st_h", 0),
+                self.get_parameter_value("st_mx", 0),
+            ])?;
+            RkTepid::set_uniform_vec4_f32(r, shader_index, UniformName::Spots, [
+                self.get_parameter_value("sp_d", 0),
+                self.get_parameter_value("sp_h", 0),
+                self.get_parameter_value("sp_v", 0),
+                self.get_parameter_value("sp_mx", 0),
+            ])?;
+        }
 
+        // Pick different instances to be spots, if the seed has changed.
+        let spot_seed = self.get_parameter_value("sp_s", 0) as u32;
+        if spot_seed != self.spot_seed {
+            self.retained.spots = random_spots(spot_seed);
+            RkTepid::update_buffer_f32(r, self.buffer_spots, 0, &self.retained.spots)?;
+            self.spot_seed = spot_seed;
+        }
 
-        // Render the furls.
-        self.shapes.furls.furl1.render(r, shader_index, lod, wireframe_mode, primcount);
+        // A PostChain may have bound its own textures since the last render().
+        RkWarm::bind_texture(r, 0, self.textures.petal)?;
+        RkWarm::bind_texture(r, 1, self.textures.seed)?;
 
 
-        // Maybe show guides.
-        // Without guides showing, render() doesn’t have to call use_shader() or
-        // set_uniform_*(), so it may run a bit faster. @TODO benchmark
-        if develop.guides_preset != GuidesPreset::ChosenByScene
-            && develop.guides_preset != GuidesPreset::NoGuides {
+        // Measure the whole Furl on the CPU, for the debug views which need it.
+        let furl_bounds = match develop.debug_preset {
+            DebugPreset::BoundingBoxes | DebugPreset::Depth => Some(self.get_furl_bounds(primcount)),
+            _ => None,
+        };
 
-            // Switch to ShaderGuides.
-            RkWarm::use_shader(r, self.shader_indices.guides);
-            RkWarm::set_uniform_mat4_f32(r, self.shader_indices.guides, UniformName::ProjectionMatrix, projection);
-            RkWarm::set_uniform_mat4_f32(r, self.shader_indices.guides, UniformName::ViewMatrix, view);
+        // Render the Scene’s camera, or one of the orthographic cameras, or
+        // all four at once.
+        match develop.camera_preset {
+            CameraPreset::QuadView => render_quad_view(r, |camera_preset|
+                self.render_view(develop, r, camera_preset, primcount, furl_bounds)),
+            camera_preset => self.render_view(develop, r, camera_preset, primcount, furl_bounds),
+        }
+    }
 
-            // Render the ShapeAxes, or the ShapeGrids, or both.
-            let guides_preset = &develop.guides_preset;
-            match guides_preset {
-                GuidesPreset::All10m | GuidesPreset::All1m => {
-                    self.shapes.guides.axes.render(r, guides_preset);
-                    self.shapes.guides.grids.render(r, guides_preset);
-                },            
-                GuidesPreset::AxesOnly10m | GuidesPreset::AxesOnly1m => {
-                    self.shapes.guides.axes.render(r, guides_preset);
-                },
-                GuidesPreset::GridsOnly10m | GuidesPreset::GridsOnly1m => {
-                    self.shapes.guides.grids.render(r, guides_preset);
-                },
-                _ => (), // unreachable, because of the `if` conditional
-            }
+    fn restore(
+        &mut self,
+        r: &mut RendererWebGl, // the app’s singleton Renderer instance
+    ) -> Result<(), RendererError> {
+        let (shader_indices, cnnx, textures, buffer_spots) = Self::init_gpu(r, &self.retained)?;
+        self.buffer_spots = buffer_spots;
+        self.furl_shader = shader_indices.furl_basic;
+        self.shader_indices = shader_indices;
+        self.cnnx = cnnx;
+        self.textures = textures;
+        Ok(())
+    }
 
-            // Switch back to ShaderFurlBasic, ready for the next render() call.
-            RkWarm::use_shader(r, self.shader_indices.furl_basic);
-            RkWarm::set_uniform_mat4_f32(r, self.shader_indices.furl_basic, UniformName::ProjectionMatrix, projection);
-            RkWarm::set_uniform_mat4_f32(r, self.shader_indices.furl_basic, UniformName::ViewMatrix, view);
-        }
+    fn set_texture(
+        &mut self,
+        r: &RendererWebGl, // the app’s singleton Renderer instance
+        texture_name: TextureName,
+        image: &HtmlImageElement,
+    ) -> Result<(), RendererError> {
+        let texture = Self::create_texture(r, Some(image))?;
+        let (current, retained_image) = match texture_name {
+            TextureName::Petal => (&mut self.textures.petal, &mut self.retained.petal_image),
+            TextureName::Seed => (&mut self.textures.seed, &mut self.retained.seed_image),
+        };
+        RkTepid::delete_texture(r, std::mem::replace(current, texture));
+        *retained_image = Some(image.clone());
+        Ok(())
     }
 
     fn get_fieldsets(&self) -> String {        
@@ -562,6 +1014,29 @@ impl Scene for SceneAloneFurl {
             { "kind":"single", "id":"rotate-furl", "heading":"Rotate Furl", "parameters":[
               { "name":"qx3", "min":-3.14, "max":3.14, "step":0.01, "title":"X\nquaternions.x[3]" },
               { "name":"qy3", "min":-3.14, "max":3.14, "step":0.01, "title":"Y\nquaternions.y[3]" }
+            ]},
+            { "kind":"single", "id":"gradient", "heading":"Gradient", "parameters":[
+              { "name":"g_hs", "min":0,    "max":1,    "step":0.01, "title":"Start Hue\nu_gradient[0]" },
+              { "name":"g_he", "min":0,    "max":2,    "step":0.01, "title":"End Hue\nu_gradient[1]" },
+              { "name":"g_sa", "min":0,    "max":1,    "step":0.01, "title":"Saturation\nu_gradient[2]" },
+              { "name":"g_mx", "min":0,    "max":1,    "step":0.01, "title":"Mix\nu_gradient[3]" },
+              { "name":"g_l",  "min":-1,   "max":1,    "step":0.01, "title":"Linear\nu_gradient_curves[0]" },
+              { "name":"g_w",  "min":-1,   "max":1,    "step":0.01, "title":"Wow\nu_gradient_curves[1]" },
+              { "name":"g_h",  "min":-1,   "max":1,    "step":0.01, "title":"Sine\nu_gradient_curves[2]" },
+              { "name":"g_f",  "min":-1,   "max":1,    "step":0.01, "title":"Flutter\nu_gradient_curves[3]" }
+            ]},
+            { "kind":"single", "id":"stripes", "heading":"Stripes", "parameters":[
+              { "name":"st_n", "min":1,    "max":55,   "step":1,    "title":"Every N (try 8, 13, 21 or 34)\nu_stripes[0]" },
+              { "name":"st_w", "min":0,    "max":1,    "step":0.01, "title":"Width\nu_stripes[1]" },
+              { "name":"st_h", "min":0,    "max":1,    "step":0.01, "title":"Hue\nu_stripes[2]" },
+              { "name":"st_mx","min":0,    "max":1,    "step":0.01, "title":"Mix\nu_stripes[3]" }
+            ]},
+            { "kind":"single", "id":"spots", "heading":"Spots", "parameters":[
+              { "name":"sp_d", "min":0,    "max":1,    "step":0.01, "title":"Density\nu_spots[0]" },
+              { "name":"sp_s", "min":0,    "max":99,   "step":1,    "title":"Seed\nSimplePrng" },
+              { "name":"sp_h", "min":0,    "max":1,    "step":0.01, "title":"Hue\nu_spots[1]" },
+              { "name":"sp_v", "min":0,    "max":1,    "step":0.01, "title":"Brightness\nu_spots[2]" },
+              { "name":"sp_mx","min":0,    "max":1,    "step":0.01, "title":"Mix\nu_spots[3]" }
             ]}
         ]"#.into()
     }
@@ -574,7 +1049,17 @@ impl Scene for SceneAloneFurl {
               0,0,0,0,1,0,0,0,0,0,0,0,1,0,0,0,1,0,0,0,1,0,0,0,
               0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,
               0,0,0,0,5,0,0,
-              0,0,1,1,1,1,1,1,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,1,1,1,1,1,1,0,1,1,1
+              ]}
+            ,{ "title":"Sunflower", "notes":"The Default Furl, with a golden gradient, rust-red stripes every 13 instances, and dark seed-like spots.", "values": [
+              2.4,0,0.2,-3.14,0,0,0.2,0,0,0,0,0,0.5,0,0,0,0,0,1.5,0.5,1.6,0,-0.04,-1.61,
+              2.4,0,0.2,3.14,0,0,0.29,0,0,0,0,0,1.18,0,0,-0.29,0.46,0,2.41,0.81,2.1,0,-0.04,-1.61,
+              0,0,0,0,1,0,0,0,0,0,0,0,1,0,0,0,1,0,0,0,1,0,0,0,
+              0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,
+              0,0,0,0,5,0,0,
+              0.02,0.16,0.9,1,1,0,0,0,13,0.35,0.01,0.7,0.12,7,0.08,0.25,1,
+              0,0,1,1,1,1,1,1,0,0,0,0
               ]}
             ,{ "title":"Zero", "notes":"A blank slate, useful for starting new Furl designs. All values are set to zero, apart from Angle Invariant (set to slowly rotate) and Scale Invariant (set to 4).", "values": [
               0,0,0,-3.14, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,4, 0,0,-0.04,-0.01,
@@ -582,7 +1067,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,1,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0,0,0,0,
-              1,1,0,1,1,1,0,1,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              1,1,0,1,1,1,0,1,0,1,1,1
               ]}
             ,{ "title":"Column", "values": [
               0,0,0,0,0,0,0,0,0,0,0,0,2,0,0,-1.2,0,0,0,0.5,0,0,-0.04,-0.01,
@@ -590,7 +1076,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0,4,0,0,
-              0,0,0,0,0,0,0,0,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,0,0,0,0,0,0,0,1,1,1
               ]}
             ,{ "title":"Dancing Pinecone", "values": [
               2.4,0,0.2,-1.67,0,0,0.39,0,0.21,0,0,-3.14,1.33,0,-0.27,-0.55,0,0,1.5,0.5,6.2,0,-0.04,-1.61,
@@ -598,7 +1085,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 
               0,0,0,0, 0,0,0,0, 0,1,1,0, 0,1,0,0, 0,0,0,0, 0,0,1,0,
               0,0,0,0,9,0.34,0.13,
-              0,0,0,0,0,0,0,0,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,0,0,0,0,0,0,0,1,1,1
               ]}
             ,{ "title":"Spiral Claw", "values": [
               1.26,0,0.33,-3.14,0.16,0,0.63,-0.01,0.2,1.69,0.51,-0.31,-0.97,0,0.3,-0.02,0.05,0,1.95,-0.1,4.4,0,-0.74,-4.51,
@@ -606,7 +1094,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,1,0, 0,1,0,0, 0,0,0,0, 0,1,0,0, 0,0,0,0, 0,1,0,0,
               0,0,0,0,10,1.02,-1.02,
-              0,0,0,0,0,0,0,0,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,0,0,0,0,0,0,0,1,1,1
               ]}
             ,{ "title":"Armadillo", "notes":"This abstract armadillo was found by Bertie Young", "values": [
               2.4,0,0.2,0,0,-0.079,1,0,3.14,3.14,3.14,3.14,2,-0.1,-2,2,3.03,0,1.5,0.5,1.5,0.1,2.06,-1.51,
@@ -614,7 +1103,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0,10,0,-2.55,
-              0,0,0,0,0,0,0,0,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,0,0,0,0,0,0,0,1,1,1
               ]}
             ,{ "title":"Horn", "values": [
               5.88,0,0.2,1.99,-0.51,0,0.2,0,-3.14,0,0,0,1.57,0,0,-0.37,-2.02,0,1.5,0.5,1.6,0,-0.04,-1.61,
@@ -622,7 +1112,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0,10,0.4,-1.83,
-              0,0,0,0,0,0,0,0,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,0,0,0,0,0,0,0,1,1,1
               ]}
             ,{ "title":"Cup", "values": [
               0.13,0.04,-1,0.6,-0.88,0,0.2,0.52,0,0,0,0,-1.24,-0.025,0,0.26,0,0,1.5,0.5,0,0.47,-1.04,-1.71,
@@ -630,7 +1121,8 @@ impl Scene for SceneAloneFurl {
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0,
               0,0,0,0,10,0.35,-0.01,
-              0,0,0,0,0,0,0,0,0
+              0.1,0.6,0.7,0,1,0,0,0,13,0.5,0.15,0,0,0,0,0.2,0,
+              0,0,0,0,0,0,0,0,0,1,1,1
               ]}
         ]"#.into()
     }
@@ -689,6 +1181,23 @@ impl Scene for SceneAloneFurl {
             "_2p" => self.parameter_values[NUM_AB + 4],
             "qx3" => self.parameter_values[NUM_AB + 5],
             "qy3" => self.parameter_values[NUM_AB + 6],
+            "g_hs" => self.parameter_values[NUM_AB + 7],
+            "g_he" => self.parameter_values[NUM_AB + 8],
+            "g_sa" => self.parameter_values[NUM_AB + 9],
+            "g_mx" => self.parameter_values[NUM_AB + 10],
+            "g_l" => self.parameter_values[NUM_AB + 11],
+            "g_w" => self.parameter_values[NUM_AB + 12],
+            "g_h" => self.parameter_values[NUM_AB + 13],
+            "g_f" => self.parameter_values[NUM_AB + 14],
+            "st_n" => self.parameter_values[NUM_AB + 15],
+            "st_w" => self.parameter_values[NUM_AB + 16],
+            "st_h" => self.parameter_values[NUM_AB + 17],
+            "st_mx" => self.parameter_values[NUM_AB + 18],
+            "sp_d" => self.parameter_values[NUM_AB + 19],
+            "sp_s" => self.parameter_values[NUM_AB + 20],
+            "sp_h" => self.parameter_values[NUM_AB + 21],
+            "sp_v" => self.parameter_values[NUM_AB + 22],
+            "sp_mx" => self.parameter_values[NUM_AB + 23],
             _ => panic!("Parameter name {} does not exist", name),
         }
     }
@@ -710,3 +1219,29 @@ impl Scene for SceneAloneFurl {
 
 }
 
+
+
+
+// HELPERS
+
+/// One pseudo-random number from 0.0 to 1.0 per instance. The same seed always
+/// gives the same numbers, so a preset’s spots always land on the same Prims.
+fn random_spots(
+    seed: u32,
+) -> Vec<f32> {
+    let mut prng = SimplePrng::new(seed);
+    (0..MAX_INSTANCES).map(|_| prng.next_float()).collect()
+}
+
+/// How far in front of a camera the nearest and farthest corners of a box are.
+/// The camera looks down the view matrix’s negative z-axis. The two are never
+/// equal, so the shader never divides by zero.
+fn depth_range(
+    bounds: (Point3, Point3),
+    view: [f32; 16],
+) -> (f32, f32) {
+    let (nearest, farthest) = box_corners(bounds.0, bounds.1).iter()
+        .map(|p| -(view[2] * p.x + view[6] * p.y + view[10] * p.z + view[14]))
+        .fold((f32::MAX, f32::MIN), |(nearest, farthest), depth| (nearest.min(depth), farthest.max(depth)));
+    (nearest, farthest.max(nearest + 0.001))
+}
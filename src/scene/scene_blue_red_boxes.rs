@@ -1,13 +1,17 @@
 //! Demonstrates how to switch between two Shaders on each `render()` call.
 
-use web_sys::{WebGlBuffer,WebGlRenderingContext as GL};
+use web_sys::WebGlRenderingContext as GL;
 use crate::app::Timer;
 use crate::develop::Develop;
+use crate::error::ERROR as E;
 use crate::renderer_webgl::{
     AttributeName,
+    BufferHandle,
+    RendererError,
     RkCool,
     RkWarm,
     RendererWebGl,
+    ShaderFeatures,
     ShaderProgramName,
     ShaderSignatureName,
     UniformName
@@ -15,9 +19,9 @@ use crate::renderer_webgl::{
 use super::Scene;
 
 struct RefsBuffer {
-    instance_steps: WebGlBuffer,
-    a_position_x: WebGlBuffer,
-    a_position_y: WebGlBuffer,
+    instance_steps: BufferHandle,
+    a_position_x: BufferHandle,
+    a_position_y: BufferHandle,
 }
 
 struct IndicesShader {
@@ -33,15 +37,27 @@ pub struct SceneBlueRedBoxes {
 impl SceneBlueRedBoxes {
     pub fn new (
         r: &mut RendererWebGl, // the app’s singleton Renderer instance
-    ) -> Self {
+    ) -> Result<Self, RendererError> {
+        let (indices_shader, refs_buffer) = Self::init_gpu(r)?;
+        Ok(Self {
+            indices_shader,
+            _refs_buffer: refs_buffer,
+        })
+    }
+
+    /// Creates the Shaders and buffers. This Scene’s data is so small that
+    /// it’s just written inline, so there’s no CPU-side data to retain.
+    fn init_gpu (
+        r: &mut RendererWebGl, // the app’s singleton Renderer instance
+    ) -> Result<(IndicesShader, RefsBuffer), RendererError> {
 
 
         // SHADERS
 
         // Init the Shaders, and store them in the `renderer.shaders` vector.
         let indices_shader = IndicesShader {
-            blue_box: RkCool::add_shader(r, ShaderProgramName::BlueBox),
-            red_box: RkCool::add_shader(r, ShaderProgramName::RedBox),
+            blue_box: RkCool::add_shader(r, ShaderProgramName::BlueBox, ShaderFeatures::NONE)?,
+            red_box: RkCool::add_shader(r, ShaderProgramName::RedBox, ShaderFeatures::NONE)?,
         };
 
         // Make sure all Shaders use the ‘BlueRedBox’ ShaderSignature.
@@ -49,10 +65,12 @@ impl SceneBlueRedBoxes {
         // without having to rebind buffers to attributes — faster, less faff.
         let name = ShaderSignatureName::BlueRedBox;
         if r.shaders[indices_shader.blue_box].get_signature_name() != &name {
-            panic!("BlueBox does not use the BlueRedBox ShaderSignature");
+            return Err(RendererError::new(E::R33617,
+                "BlueBox does not use the BlueRedBox ShaderSignature".into()));
         }
         if r.shaders[indices_shader.red_box].get_signature_name() != &name {
-            panic!("RedBox does not use the BlueRedBox ShaderSignature");
+            return Err(RendererError::new(E::R33617,
+                "RedBox does not use the BlueRedBox ShaderSignature".into()));
         }
 
         // We can’t predict which locations WebGL has assigned to each of the
@@ -64,17 +82,17 @@ impl SceneBlueRedBoxes {
         // use any of the shaders to find the attribute locations. Once we have
         // them, we can store them in Rust fields for the lifetime of the Scene,
         // they _shouldn’t_ change.
-        RkCool::store_signature_locations(r, indices_shader.blue_box);
-        RkCool::store_signature_locations(r, indices_shader.red_box);
+        RkCool::store_signature_locations(r, indices_shader.blue_box)?;
+        RkCool::store_signature_locations(r, indices_shader.red_box)?;
 
-        // Make sure that WebGPU has assigned the locations we expected to each
+        // Make sure that WebGL has assigned the locations we expected to each
         // attribute, in all Shaders. It’s possible that some browser or 
-        // graphics card out there in the wild may not play nice!
-        //
-        // @TODO implement confirm_signature_locations(), so that it checks that
-        // all Shaders have identical attribute locations.
-        // RkCool::confirm_signature_locations(r, indices_shader.blue_box);
-        // RkCool::confirm_signature_locations(r, indices_shader.red_box);
+        // graphics card out there in the wild may not play nice! If so, the
+        // Shaders are rebound and relinked.
+        RkCool::confirm_signature_locations(r, &[
+            indices_shader.blue_box,
+            indices_shader.red_box,
+        ])?;
 
 
 
@@ -84,21 +102,21 @@ impl SceneBlueRedBoxes {
         let refs_buffer = RefsBuffer {
 
             // Set the offset attribute, used for the x _and_ the y positions.
-            instance_steps: RkCool::create_buffer_f32(r, vec![
+            instance_steps: RkCool::create_buffer_f32(r, &[
                 0.0, 0.15, 0.3, // xy offset of the three blue boxes, widedly spaced
                 0.0, -0.05, -0.1, // xy offset of the three red boxes, overlapping
-            ]),
+            ])?,
 
             // Set the vertex x and y coordinate attribute.
             // No projection, so they’re in GL-space, -1.0 to 1.0 in both directions.
-            a_position_x: RkCool::create_buffer_f32(r, vec![
+            a_position_x: RkCool::create_buffer_f32(r, &[
                 0.1, 0.0, 0.0, // x positions of the three blue boxes, right-angle
                 0.2, 0.15, 0.25, // x positions of the three red boxes, equilateralish
-            ]),
-            a_position_y: RkCool::create_buffer_f32(r, vec![
+            ])?,
+            a_position_y: RkCool::create_buffer_f32(r, &[
                 0.1, 0.1, 0.0, // y positions of the three blue boxes, right-angle
                 0.4, 0.3, 0.3,  // y positions of the three red boxes, equilateralish
-            ]),
+            ])?,
         };
 
 
@@ -114,20 +132,20 @@ impl SceneBlueRedBoxes {
         // - Attributes can be read by vertex shaders, but not fragment shaders
         // - Attributes are disabled by default — so, enable_attribute()
 
-        RkCool::enable_attribute(r, AttributeName::InstanceStep);
-        RkCool::enable_attribute(r, AttributeName::PositionX);
-        RkCool::enable_attribute(r, AttributeName::PositionY);
+        RkCool::enable_attribute(r, AttributeName::InstanceStep)?;
+        RkCool::enable_attribute(r, AttributeName::PositionX)?;
+        RkCool::enable_attribute(r, AttributeName::PositionY)?;
 
         // Point the Shader attributes to the correct buffers.
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.instance_steps));
-        RkCool::use_attribute(r, AttributeName::InstanceStep, 1);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.a_position_x));
-        RkCool::use_attribute(r, AttributeName::PositionX, 1);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.a_position_y));
-        RkCool::use_attribute(r, AttributeName::PositionY, 1);
+        RkCool::bind_buffer(r, refs_buffer.instance_steps)?;
+        RkCool::use_attribute(r, AttributeName::InstanceStep, 1)?;
+        RkCool::bind_buffer(r, refs_buffer.a_position_x)?;
+        RkCool::use_attribute(r, AttributeName::PositionX, 1)?;
+        RkCool::bind_buffer(r, refs_buffer.a_position_y)?;
+        RkCool::use_attribute(r, AttributeName::PositionY, 1)?;
 
         // @TODO describe
-        RkCool::set_repeat_gap(r, AttributeName::InstanceStep, 1);
+        RkCool::set_repeat_gap(r, AttributeName::InstanceStep, 1)?;
 
 
 
@@ -142,21 +160,18 @@ impl SceneBlueRedBoxes {
     
         // Set the sizes of the blue and red boxes, using a uniform.
         let index = indices_shader.blue_box;
-        RkCool::use_shader(r, index);
-        RkCool::set_uniform_f32(r, index, UniformName::Pointsize, 5.0);
+        RkCool::use_shader(r, index)?;
+        RkCool::set_uniform_f32(r, index, UniformName::Pointsize, 5.0)?;
 
         let index = indices_shader.red_box;
-        RkCool::use_shader(r, index);
-        RkCool::set_uniform_f32(r, index, UniformName::Pointsize, 10.0);
+        RkCool::use_shader(r, index)?;
+        RkCool::set_uniform_f32(r, index, UniformName::Pointsize, 10.0)?;
 
 
 
 
 
-        Self {
-            indices_shader,
-            _refs_buffer: refs_buffer,
-        }
+        Ok((indices_shader, refs_buffer))
     }
 }
 
@@ -166,14 +181,14 @@ impl Scene for SceneBlueRedBoxes {
         _develop: &Develop, // the app’s `develop` instance
         r: &RendererWebGl, // the app’s `renderer` instance
         _timer: &Timer, // the app’s `timer` instance
-    ) {
+    ) -> Result<(), RendererError> {
 
 
         // Switch to the ‘blue box’ Shader.
-        RkWarm::use_shader(r, self.indices_shader.blue_box);
+        RkWarm::use_shader(r, self.indices_shader.blue_box)?;
 
         // Draw the three blue boxes.
-        RkWarm::draw(r, GL::POINTS, 0, 3);
+        RkWarm::draw(r, GL::POINTS, 0, 3)?;
 
         // Draw three blue triangles.
         RkWarm::repeat(r, 
@@ -181,14 +196,14 @@ impl Scene for SceneBlueRedBoxes {
             0, // first — starting index in the array of vector points
             3, // count — number of vertices per instance
             3, // primcount — number of instances
-        );
+        )?;
 
 
         // Switch to the ‘red box’ Shader.
-        RkWarm::use_shader(r, self.indices_shader.red_box);
+        RkWarm::use_shader(r, self.indices_shader.red_box)?;
 
         // Draw the three red boxes.
-        RkWarm::draw(r, GL::POINTS, 3, 3);
+        RkWarm::draw(r, GL::POINTS, 3, 3)?;
 
         // Draw three red triangles.
         RkWarm::repeat(r, 
@@ -196,7 +211,18 @@ impl Scene for SceneBlueRedBoxes {
             3, // first — starting index in the array of vector points
             3, // count — number of vertices per instance
             3, // primcount — number of instances
-        );
+        )?;
+
+        Ok(())
+    }
 
+    fn restore(
+        &mut self,
+        r: &mut RendererWebGl, // the app’s singleton Renderer instance
+    ) -> Result<(), RendererError> {
+        let (indices_shader, refs_buffer) = Self::init_gpu(r)?;
+        self.indices_shader = indices_shader;
+        self._refs_buffer = refs_buffer;
+        Ok(())
     }
 }
@@ -1,4 +1,4 @@
-use crate::renderer_webgl::RendererWebGl;
+use crate::renderer_webgl::{RendererError,RendererWebGl};
 use super::super::{
     Scene,
     SceneBlueRedBoxes,
@@ -20,12 +20,12 @@ impl SceneContainer {
     pub fn new(
         renderer: &mut RendererWebGl,
         scene_container_name: SceneContainerName,
-    ) -> Self {
-        match scene_container_name {
+    ) -> Result<Self, RendererError> {
+        Ok(match scene_container_name {
             SceneContainerName::BlueRedBoxes => Self {
                 name: SceneContainerName::BlueRedBoxes,
                 scene: Box::new(
-                    SceneBlueRedBoxes::new(renderer)
+                    SceneBlueRedBoxes::new(renderer)?
                 ),
             },
             SceneContainerName::Empty => Self {
@@ -37,15 +37,27 @@ impl SceneContainer {
             SceneContainerName::AloneFurl => Self {
                 name: SceneContainerName::AloneFurl,
                 scene: Box::new(
-                    SceneAloneFurl::new(renderer)
+                    SceneAloneFurl::new(renderer)?
                 ),
             },
             SceneContainerName::RainbowCactus => Self {
                 name: SceneContainerName::RainbowCactus,
                 scene: Box::new(
-                    SceneRainbowCactus::new(renderer)
+                    SceneRainbowCactus::new(renderer)?
                 ),
             },
+        })
+    }
+
+    /// A SceneContainer which can’t fail, eg if the requested Scene did.
+    pub fn empty(
+        renderer: &mut RendererWebGl,
+    ) -> Self {
+        Self {
+            name: SceneContainerName::Empty,
+            scene: Box::new(
+                SceneEmpty::new(renderer)
+            ),
         }
     }
 }
@@ -1,13 +1,18 @@
 //! Demonstrates WebGL instanced elements.
 
-use web_sys::{WebGlBuffer,WebGlRenderingContext as GL};
+use web_sys::WebGlRenderingContext as GL;
 use crate::app::Timer;
 use crate::develop::Develop;
+use crate::error::ERROR as E;
 use crate::renderer_webgl::{
     AttributeName,
+    BufferHandle,
+    IndexBuffer,
+    RendererError,
     RkCool,
     RkWarm,
     RendererWebGl,
+    ShaderFeatures,
     ShaderProgramName,
     ShaderSignatureName,
     UniformName
@@ -19,60 +24,43 @@ use super::kit_scene::matrix::{perspective,rotate_x};
 const INSTANCE_TALLY: usize = 500;
 
 struct RefsBuffer {
-    a_instance_log: WebGlBuffer,
-    a_instance_log_rev: WebGlBuffer,
-    a_instance_step: WebGlBuffer,
-    colors: WebGlBuffer,
-    vertices: WebGlBuffer,
+    a_instance_log: BufferHandle,
+    a_instance_log_rev: BufferHandle,
+    a_instance_step: BufferHandle,
+    /// Stays bound as the ELEMENT_ARRAY_BUFFER, so render() needn’t rebind it.
+    cnnx: IndexBuffer,
+    colors: BufferHandle,
+    vertices: BufferHandle,
 }
 
 struct IndicesShader {
     main: usize,
 }
 
+/// CPU-side data, kept so that the buffers can be rebuilt after context loss.
+struct Retained {
+    a_instance_log: Vec<f32>,
+    a_instance_log_rev: Vec<f32>,
+    a_instance_step: Vec<f32>,
+    cnnx: Vec<u16>,
+    colors: Vec<f32>,
+    vertices: Vec<f32>,
+}
+
 pub struct SceneRainbowCactus {
     indices_shader: IndicesShader,
     _refs_buffer: RefsBuffer,
     mov_matrix: [f32; 16],
     proj_matrix: [f32; 16],
     quaternion: [f32; 4],
+    retained: Retained,
     view_matrix: [f32; 16],
 }
 
 impl SceneRainbowCactus {
     pub fn new (
         r: &mut RendererWebGl, // the app’s singleton Renderer instance
-    ) -> Self {
-
-
-        // SHADERS
-
-        // Init the shader, and store it in the `renderer.shaders` vector.
-        let indices_shader = IndicesShader {
-            main: RkCool::add_shader(r, ShaderProgramName::RainbowCactus),
-        };
-
-        // Make sure the Shader uses the ‘RainbowCactus’
-        // ShaderSignature. This restriction means that we could switch between
-        // shaders in render(), if we had more than one, without having to
-        // rebind buffers to attributes — faster, less faff.
-        let name = ShaderSignatureName::RainbowCactus;
-        if r.shaders[indices_shader.main].get_signature_name() != &name {
-            panic!("main does not use the RainbowCactus ShaderSignature");
-        }
-
-        // We can’t predict which locations WebGL has assigned to each of the
-        // attributes. It seems that any given browser will alway assigns them
-        // the same way... but there’s no guarantee. In any case, each browser
-        // has its own strategy for assigning attribute locations.
-        // 
-        // Attributes, and their locations, are global. So we should be able to
-        // use any of the shaders to find the attribute locations. Once we have
-        // them, we can store them in Rust fields for the lifetime of the Scene,
-        // they _shouldn’t_ change.
-        RkCool::store_signature_locations(r, indices_shader.main);
-
-
+    ) -> Result<Self, RendererError> {
 
 
         // PRECALCULATE
@@ -91,41 +79,108 @@ impl SceneRainbowCactus {
             |&raw| f32::log(raw, 2.5)
         ).rev().collect();
 
+        let retained = Retained {
+            a_instance_log,
+            a_instance_log_rev,
+            a_instance_step,
 
-
-
-        // BUFFERS
-
-        let refs_buffer = RefsBuffer {
-            a_instance_log: RkCool::create_buffer_f32(r, a_instance_log),
-            a_instance_log_rev: RkCool::create_buffer_f32(r, a_instance_log_rev),
-            a_instance_step: RkCool::create_buffer_f32(r, a_instance_step),
+            cnnx: vec![
+                0, 1, 2,  0, 2, 3,  4, 5, 6,  4, 6, 7,
+                8, 9,10,  8,10,11, 12,13,14, 12,14,15,
+               16,17,18, 16,18,19, 20,21,22, 20,22,23,
+            ],
 
             // from https://www.tutorialspoint.com/webgl/webgl_cube_rotation.htm
-            colors: RkCool::create_buffer_f32(r, vec![
+            colors: vec![
                 0.9,0.0,0.1, 0.8,0.1,0.2, 0.8,0.0,0.1, 0.9,0.2,0.0, // reds
                 0.9,0.5,0.0, 0.8,0.4,0.0, 0.9,0.4,0.0, 0.9,0.4,0.1, // oranges
                 0.8,0.9,0.3, 0.9,1.0,0.2, 0.7,0.6,0.1, 0.6,0.8,0.2, // yellows
                 0.3,0.9,0.3, 0.0,1.0,0.2, 0.2,0.9,0.1, 0.1,0.8,0.2, // greens
                 0.1,0.3,0.8, 0.0,0.2,0.9, 0.2,0.1,0.7, 0.0,0.3,0.6, // blues
                 0.6,0.0,0.8, 0.3,0.1,0.5, 0.7,0.0,0.5, 0.8,0.1,0.6, // purples
-            ]),
+            ],
 
-            vertices: RkCool::create_buffer_f32(r, vec![
+            vertices: vec![
                 -1.0,-1.0,-1.0,  1.0,-1.0,-1.0,  1.0, 1.0,-1.0, -1.0, 1.0,-1.0,
                 -1.0,-1.0, 1.0,  1.0,-1.0, 1.0,  1.0, 1.0, 1.0, -1.0, 1.0, 1.0,
                 -1.0,-1.0,-1.0, -1.0, 1.0,-1.0, -1.0, 1.0, 1.0, -1.0,-1.0, 1.0,
                  1.0,-1.0,-1.0,  1.0, 1.0,-1.0,  1.0, 1.0, 1.0,  1.0,-1.0, 1.0,
                 -1.0,-1.0,-1.0, -1.0,-1.0, 1.0,  1.0,-1.0, 1.0,  1.0,-1.0,-1.0,
                 -1.0, 1.0,-1.0, -1.0, 1.0, 1.0,  1.0, 1.0, 1.0,  1.0, 1.0,-1.0,     
-            ]),
+            ],
+        };
+
+
+
+
+        // GPU
+
+        let (indices_shader, refs_buffer) = Self::init_gpu(r, &retained)?;
+
+        Ok(Self {
+            indices_shader,
+            _refs_buffer: refs_buffer,
+            mov_matrix: [1.,0.,0.,0., 0.,1.,0.,0., 0.,0.,1.,0., 0.,0.,0.,1.],
+            view_matrix: [1.,0.,0.,0., 0.,1.,0.,0., 0.,0.,1.,0., 0.,0.,3.0,1.], // 3 m[14] to push the camera forward
+            proj_matrix: perspective(40., r.aspect_ratio, 1., 100.0),
+            quaternion: [
+                0.0,0.0,1.0, // normalised axis
+                -1.0 // initial angle in radians (updated using the timer)
+            ],
+            retained,
+        })
+    }
+
+    /// Creates the Shader and buffers, from the retained CPU-side data. Called
+    /// again by restore(), if the WebGL context is lost and then restored.
+    fn init_gpu (
+        r: &mut RendererWebGl, // the app’s singleton Renderer instance
+        retained: &Retained,
+    ) -> Result<(IndicesShader, RefsBuffer), RendererError> {
+
+
+        // SHADERS
+
+        // Init the shader, and store it in the `renderer.shaders` vector.
+        let indices_shader = IndicesShader {
+            main: RkCool::add_shader(r, ShaderProgramName::RainbowCactus, ShaderFeatures::NONE)?,
         };
 
-        RkCool::create_index_buffer_u16(r, vec![
-            0, 1, 2,  0, 2, 3,  4, 5, 6,  4, 6, 7,
-            8, 9,10,  8,10,11, 12,13,14, 12,14,15,
-           16,17,18, 16,18,19, 20,21,22, 20,22,23,
-        ]);
+        // Make sure the Shader uses the ‘RainbowCactus’
+        // ShaderSignature. This restriction means that we could switch between
+        // shaders in render(), if we had more than one, without having to
+        // rebind buffers to attributes — faster, less faff.
+        let name = ShaderSignatureName::RainbowCactus;
+        if r.shaders[indices_shader.main].get_signature_name() != &name {
+            return Err(RendererError::new(E::R33617,
+                "main does not use the RainbowCactus ShaderSignature".into()));
+        }
+
+        // We can’t predict which locations WebGL has assigned to each of the
+        // attributes. It seems that any given browser will alway assigns them
+        // the same way... but there’s no guarantee. In any case, each browser
+        // has its own strategy for assigning attribute locations.
+        // 
+        // Attributes, and their locations, are global. So we should be able to
+        // use any of the shaders to find the attribute locations. Once we have
+        // them, we can store them in Rust fields for the lifetime of the Scene,
+        // they _shouldn’t_ change.
+        RkCool::store_signature_locations(r, indices_shader.main)?;
+        RkCool::confirm_signature_locations(r, &[indices_shader.main])?;
+
+
+
+
+        // BUFFERS
+
+        let refs_buffer = RefsBuffer {
+            a_instance_log: RkCool::create_buffer_f32(r, &retained.a_instance_log)?,
+            a_instance_log_rev: RkCool::create_buffer_f32(r, &retained.a_instance_log_rev)?,
+            a_instance_step: RkCool::create_buffer_f32(r, &retained.a_instance_step)?,
+            cnnx: RkCool::create_index_buffer_u16(r, &retained.cnnx)?,
+            colors: RkCool::create_buffer_f32(r, &retained.colors)?,
+            vertices: RkCool::create_buffer_f32(r, &retained.vertices)?,
+        };
 
 
 
@@ -140,41 +195,30 @@ impl SceneRainbowCactus {
         // - Attributes can be read by vertex shaders, but not fragment shaders
         // - Attributes are disabled by default — so, enable_attribute()
 
-        RkCool::enable_attribute(r, AttributeName::Color);
-        RkCool::enable_attribute(r, AttributeName::InstanceLog);
-        RkCool::enable_attribute(r, AttributeName::InstanceLogRev);
-        RkCool::enable_attribute(r, AttributeName::InstanceStep);
-        RkCool::enable_attribute(r, AttributeName::Position);
+        RkCool::enable_attribute(r, AttributeName::Color)?;
+        RkCool::enable_attribute(r, AttributeName::InstanceLog)?;
+        RkCool::enable_attribute(r, AttributeName::InstanceLogRev)?;
+        RkCool::enable_attribute(r, AttributeName::InstanceStep)?;
+        RkCool::enable_attribute(r, AttributeName::Position)?;
 
         // Point the Shader attributes to the correct buffers.
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.colors));
-        RkCool::use_attribute(r, AttributeName::Color, 3);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.vertices));
-        RkCool::use_attribute(r, AttributeName::Position, 3);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.a_instance_log));
-        RkCool::use_attribute(r, AttributeName::InstanceLog, 1);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.a_instance_log_rev));
-        RkCool::use_attribute(r, AttributeName::InstanceLogRev, 1);
-        r.gl.bind_buffer(GL::ARRAY_BUFFER, Some(&refs_buffer.a_instance_step));
-        RkCool::use_attribute(r, AttributeName::InstanceStep, 1);
+        RkCool::bind_buffer(r, refs_buffer.colors)?;
+        RkCool::use_attribute(r, AttributeName::Color, 3)?;
+        RkCool::bind_buffer(r, refs_buffer.vertices)?;
+        RkCool::use_attribute(r, AttributeName::Position, 3)?;
+        RkCool::bind_buffer(r, refs_buffer.a_instance_log)?;
+        RkCool::use_attribute(r, AttributeName::InstanceLog, 1)?;
+        RkCool::bind_buffer(r, refs_buffer.a_instance_log_rev)?;
+        RkCool::use_attribute(r, AttributeName::InstanceLogRev, 1)?;
+        RkCool::bind_buffer(r, refs_buffer.a_instance_step)?;
+        RkCool::use_attribute(r, AttributeName::InstanceStep, 1)?;
 
         // @TODO describe
-        RkCool::set_repeat_gap(r, AttributeName::InstanceLog, 1);
-        RkCool::set_repeat_gap(r, AttributeName::InstanceLogRev, 1);
-        RkCool::set_repeat_gap(r, AttributeName::InstanceStep, 1);
+        RkCool::set_repeat_gap(r, AttributeName::InstanceLog, 1)?;
+        RkCool::set_repeat_gap(r, AttributeName::InstanceLogRev, 1)?;
+        RkCool::set_repeat_gap(r, AttributeName::InstanceStep, 1)?;
 
-
-        Self {
-            indices_shader,
-            _refs_buffer: refs_buffer,
-            mov_matrix: [1.,0.,0.,0., 0.,1.,0.,0., 0.,0.,1.,0., 0.,0.,0.,1.],
-            view_matrix: [1.,0.,0.,0., 0.,1.,0.,0., 0.,0.,1.,0., 0.,0.,3.0,1.], // 3 m[14] to push the camera forward
-            proj_matrix: perspective(40., r.aspect_ratio, 1., 100.0),
-            quaternion: [
-                0.0,0.0,1.0, // normalised axis
-                -1.0 // initial angle in radians (updated using the timer)
-            ],
-        }
+        Ok((indices_shader, refs_buffer))
     }
 }
 
@@ -184,7 +228,7 @@ impl Scene for SceneRainbowCactus {
         _develop: &Develop, // the app’s `develop` instance
         r: &RendererWebGl, // the app’s singleton Renderer instance
         timer: &Timer, // the app’s `timer` instance
-    ) {
+    ) -> Result<(), RendererError> {
         let main = self.indices_shader.main;
 
         // Rotate on the y-axis according to delta time.
@@ -198,18 +242,30 @@ impl Scene for SceneRainbowCactus {
         self.view_matrix = rotate_x(self.view_matrix, timer.time_delta * - 0.1);
         self.view_matrix[14] -= timer.time_delta * 0.2;
 
-        RkWarm::set_uniform_mat4_f32(r, main, UniformName::ProjectionMatrix, self.proj_matrix);
-        RkWarm::set_uniform_mat4_f32(r, main, UniformName::ViewMatrix, self.view_matrix);
-        RkWarm::set_uniform_mat4_f32(r, main, UniformName::ModelMatrix, self.mov_matrix);
-        RkWarm::set_uniform_vec4_f32(r, main, UniformName::Quaternion, self.quaternion);
+        RkWarm::set_uniform_mat4_f32(r, main, UniformName::ProjectionMatrix, self.proj_matrix)?;
+        RkWarm::set_uniform_mat4_f32(r, main, UniformName::ViewMatrix, self.view_matrix)?;
+        RkWarm::set_uniform_mat4_f32(r, main, UniformName::ModelMatrix, self.mov_matrix)?;
+        RkWarm::set_uniform_vec4_f32(r, main, UniformName::Quaternion, self.quaternion)?;
 
         // Draw the rainbow cactus cubes.
         RkWarm::draw_instances(r, 
             GL::TRIANGLES, // mode — eg WebGLRenderingContext::POINTS or ::TRIANGLES
-            0, // offset — in bytes, so must be a multiple of the size of the given type
+            &self._refs_buffer.cnnx, // indices — knows whether they’re u16 or u32
+            0, // first — index of the first index to draw
             36, // count — number of vertices per instance
             INSTANCE_TALLY as u32, // primcount — number of instances
-        );
+        )?;
+
+        Ok(())
+    }
 
+    fn restore(
+        &mut self,
+        r: &mut RendererWebGl, // the app’s singleton Renderer instance
+    ) -> Result<(), RendererError> {
+        let (indices_shader, refs_buffer) = Self::init_gpu(r, &self.retained)?;
+        self.indices_shader = indices_shader;
+        self._refs_buffer = refs_buffer;
+        Ok(())
     }
 }
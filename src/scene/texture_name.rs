@@ -0,0 +1,12 @@
+//! Lets JavaScript say which of a Scene’s textures an image is for.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub enum TextureName {
+    /// Drawn on the outer Prims of a Furl.
+    Petal,
+    /// Drawn on the Prims at the heart of a Furl.
+    Seed,
+}
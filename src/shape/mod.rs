@@ -9,6 +9,9 @@ pub use point_3::Point3;
 mod shape_axes;
 pub use shape_axes::{ShapeAxes,ShapeAxesCnnxMap};
 
+mod shape_box;
+pub use shape_box::{box_corners,ShapeBox};
+
 // mod shape_cube;
 // pub use shape_cube::ShapeCube;
 
@@ -1,6 +1,6 @@
 use web_sys::WebGlRenderingContext as GL;
 use crate::develop::GuidesPreset;
-use crate::renderer_webgl::{RkWarm,RendererWebGl};
+use crate::renderer_webgl::{IndexBuffer,RendererError,RkWarm,RendererWebGl};
 use super::point_3::Point3;
 
 const M1_POINTS: usize = 6;
@@ -18,7 +18,7 @@ pub struct StartIndices {
 }
 
 pub struct ShapeAxesCnnxMap {
-    pub m1: (i32,i32), // (offset,count)
+    pub m1: (i32,i32), // (first,count) — in indices, not bytes
     pub m10: (i32,i32),
 }
 
@@ -102,8 +102,8 @@ impl ShapeAxes {
             m10+4, m10+5, // back - front
         ];
 
-        self.start_indices.cnnx.m1 = ((start_index + 0) * 2) as u16;
-        self.start_indices.cnnx.m10 = ((start_index + M1_POINTS) * 2) as u16;
+        self.start_indices.cnnx.m1 = (start_index + 0) as u16;
+        self.start_indices.cnnx.m10 = (start_index + M1_POINTS) as u16;
 
         self.cnnx_map.m1 = (self.start_indices.cnnx.m1 as i32,M1_POINTS as i32);
         self.cnnx_map.m10 = (self.start_indices.cnnx.m10 as i32,M10_POINTS as i32);
@@ -174,18 +174,19 @@ impl ShapeAxes {
     pub fn render(
         &self,
         r: &RendererWebGl, // the app’s singleton Renderer instance
+        indices: &IndexBuffer, // the Scene’s index buffer, which holds `get_cnnx()`
         guides_preset: &GuidesPreset,
-    ) {
+    ) -> Result<(), RendererError> {
         let cnnx = match guides_preset {
             GuidesPreset::AxesOnly1m | GuidesPreset::All1m => self.cnnx_map.m1,
             _ => self.cnnx_map.m10, // must be ::AxesOnly10m or ::All10m
         };
         RkWarm::draw_elements(r,
             GL::LINES, // mode — GL::LINES connects pairs of vertices
-            cnnx.0, // offset — in bytes, so must be a multiple of the size of the given type
+            indices,
+            cnnx.0, // first — index of the first index to draw
             cnnx.1, // count — number of vertices per instance
-            GL::UNSIGNED_SHORT, // type_ eg WebGLRenderingContext::UNSIGNED_SHORT
-        );
+        )
     }
 
 }
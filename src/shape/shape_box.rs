@@ -0,0 +1,116 @@
+use web_sys::WebGlRenderingContext as GL;
+use crate::renderer_webgl::{IndexBuffer,RendererError,RkWarm,RendererWebGl};
+use super::point_3::Point3;
+
+pub const BOX_POINTS: usize = 8;
+
+// Each edge, as indices into the corners from `box_corners()`.
+/*      .2 ------.6
+      .' |     .' |
+    3'------ 7'   |
+    |    |   |    |
+    |   .0 - | --.4
+    | .'     | .'
+    1'------ 5'        */
+pub const BOX_EDGES: [[u16; 2]; 12] = [
+    [0, 1], [2, 3], [4, 5], [6, 7], // along z
+    [0, 2], [1, 3], [4, 6], [5, 7], // along y
+    [0, 4], [1, 5], [2, 6], [3, 7], // along x
+];
+
+pub struct StartIndices {
+    pub colors: u16,
+    pub cnnx: u16,
+    pub vertices: u16,
+}
+
+/// The outline of a box from (0, 0, 0) to (1, 1, 1). A model matrix stretches
+/// it round something, eg a whole Furl.
+pub struct ShapeBox {
+    points: [Point3; BOX_POINTS],
+    pub start_indices: StartIndices,
+}
+
+impl ShapeBox {
+
+    pub fn new(
+    ) -> Self {
+        Self {
+            points: box_corners(Point3::new(0., 0., 0.), Point3::new(1., 1., 1.)),
+            start_indices: StartIndices { // values in here will be updated
+                colors: 0,
+                cnnx: 0,
+                vertices: 0,
+            },
+        }
+    }
+
+    pub fn get_cnnx(
+        &mut self,
+        start_index: usize,
+    ) -> Vec<u16> {
+        let b = self.start_indices.colors;
+        assert!(b == self.start_indices.vertices, "box offset mismatch");
+
+        let mut cnnx: Vec<u16> = vec![];
+        for edge in BOX_EDGES.iter() { cnnx.extend(edge.iter().map(|i| b + i)) }
+
+        self.start_indices.cnnx = start_index as u16;
+
+        cnnx
+    }
+
+    /// Yellow, to stand out from the axes and grids.
+    pub fn get_colors(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<f32> {
+        self.start_indices.colors = start_vertex as u16;
+        [0.9, 0.8, 0.1].repeat(BOX_POINTS)
+    }
+
+    pub fn get_vertices(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<f32> {
+        self.start_indices.vertices = start_vertex as u16;
+        self.points.iter().flat_map(|p| vec![p.x, p.y, p.z]).collect()
+    }
+
+    pub fn render(
+        &self,
+        r: &RendererWebGl, // the app’s singleton Renderer instance
+        indices: &IndexBuffer, // the Scene’s index buffer, which holds `get_cnnx()`
+    ) -> Result<(), RendererError> {
+        RkWarm::draw_elements(r,
+            GL::LINES, // mode — GL::LINES connects pairs of vertices
+            indices,
+            self.start_indices.cnnx as i32, // first — index of the first index to draw
+            BOX_EDGES.len() as i32 * 2, // count — number of vertices
+        )
+    }
+
+}
+
+
+
+
+// HELPERS
+
+/// The eight corners of the box between `min` and `max`, in the order which
+/// `BOX_EDGES` expects.
+pub fn box_corners(
+    min: Point3,
+    max: Point3,
+) -> [Point3; BOX_POINTS] {
+    [
+        Point3::new(min.x, min.y, min.z), // 0
+        Point3::new(min.x, min.y, max.z), // 1
+        Point3::new(min.x, max.y, min.z), // 2
+        Point3::new(min.x, max.y, max.z), // 3
+        Point3::new(max.x, min.y, min.z), // 4
+        Point3::new(max.x, min.y, max.z), // 5
+        Point3::new(max.x, max.y, min.z), // 6
+        Point3::new(max.x, max.y, max.z), // 7
+    ]
+}
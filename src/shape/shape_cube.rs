@@ -86,7 +86,7 @@ impl ShapeCube {
             l0+3, l0+5, l0+7, // 3 - 5 - 7    +ve z face (front)
         ];
 
-        self.start_indices.connections.lod0 = ((start_index + 0) * 2) as u16;
+        self.start_indices.connections.lod0 = (start_index + 0) as u16;
 
         connections
     }
@@ -1,4 +1,5 @@
-use crate::renderer_webgl::{RkWarm,RendererWebGl,UniformName};
+use web_sys::WebGlRenderingContext as GL;
+use crate::renderer_webgl::{IndexBuffer,RendererError,RkWarm,RendererWebGl,UniformName};
 use super::point_3::Point3;
 use super::ShapeNubbin;
 
@@ -30,6 +31,20 @@ impl ShapeFurl {
         }
     }
 
+    /// The smallest box which holds one nubbin, before the shader moves it,
+    /// as its lowest and highest corners.
+    pub fn get_nubbin_bounds(
+        &self,
+    ) -> (Point3, Point3) {
+        self.nubbin.get_bounds()
+    }
+
+    pub fn get_placement(
+        &self,
+    ) -> Point3 {
+        self.placement
+    }
+
     pub fn get_cnnx(
         &mut self,
         start_index: usize,
@@ -44,6 +59,27 @@ impl ShapeFurl {
         self.nubbin.get_colors(start_vertex)
     }
 
+    pub fn get_normals(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<i16> {
+        self.nubbin.get_normals(start_vertex)
+    }
+
+    pub fn get_spikes(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<f32> {
+        self.nubbin.get_spikes(start_vertex)
+    }
+
+    pub fn get_uvs(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<f32> {
+        self.nubbin.get_uvs(start_vertex)
+    }
+
     pub fn get_vertices(
         &mut self,
         start_vertex: usize,
@@ -55,21 +91,66 @@ impl ShapeFurl {
         &self,
         r: &RendererWebGl, // the app’s singleton Renderer instance
         shader_index: usize,
+        indices: &IndexBuffer, // the Scene’s index buffer, which holds `get_cnnx()`
         lod: u8,
-        wireframe_mode: u32, // eg WebGLRenderingContext::LINE_STRIP, ::POINTS or ::TRIANGLES
+        wireframe_mode: u32, // eg WebGLRenderingContext::LINES, ::POINTS or ::TRIANGLES
         primcount: u32,
-    ) {
-        let cnnx_map = match lod {
-            0 => self.nubbin.cnnx_map.lod0,
+    ) -> Result<(), RendererError> {
+        // GL::LINES draws each edge once. Other modes use the triangles.
+        let cnnx_map = match (lod, wireframe_mode) {
+            (0, GL::LINES) => self.nubbin.cnnx_map.lod0_edges,
+            (_, GL::LINES) => self.nubbin.cnnx_map.lod1_edges,
+            (0, _) => self.nubbin.cnnx_map.lod0,
             _ => self.nubbin.cnnx_map.lod1,
         };
-        RkWarm::set_uniform_point3_f32(r, shader_index, UniformName::Placement, self.placement);
+        self.draw(r, shader_index, indices, cnnx_map, wireframe_mode, primcount)
+    }
+
+    /// Draws a spike along each vertex normal. FurlBasic’s `u_debug` uniform
+    /// sets how long the spikes are.
+    pub fn render_normals(
+        &self,
+        r: &RendererWebGl, // the app’s singleton Renderer instance
+        shader_index: usize,
+        indices: &IndexBuffer, // the Scene’s index buffer, which holds `get_cnnx()`
+        lod: u8,
+        primcount: u32,
+    ) -> Result<(), RendererError> {
+        let cnnx_map = match lod {
+            0 => self.nubbin.cnnx_map.lod0_normals,
+            _ => self.nubbin.cnnx_map.lod1_normals,
+        };
+        self.draw(r, shader_index, indices, cnnx_map, GL::LINES, primcount)
+    }
+
+    /// Draws a box round each instance, which the shader moves along with it.
+    pub fn render_bounds(
+        &self,
+        r: &RendererWebGl, // the app’s singleton Renderer instance
+        shader_index: usize,
+        indices: &IndexBuffer, // the Scene’s index buffer, which holds `get_cnnx()`
+        primcount: u32,
+    ) -> Result<(), RendererError> {
+        self.draw(r, shader_index, indices, self.nubbin.cnnx_map.bounds, GL::LINES, primcount)
+    }
+
+    fn draw(
+        &self,
+        r: &RendererWebGl,
+        shader_index: usize,
+        indices: &IndexBuffer,
+        cnnx_map: (i32,i32), // (first,count) — in indices, not bytes
+        mode: u32,
+        primcount: u32,
+    ) -> Result<(), RendererError> {
+        RkWarm::set_uniform_point3_f32(r, shader_index, UniformName::Placement, self.placement)?;
         RkWarm::draw_instances(r,
-            wireframe_mode, // mode — eg WebGLRenderingContext::LINE_STRIP, ::POINTS or ::TRIANGLES
-            cnnx_map.0, // offset — in bytes, so must be a multiple of the size of the given type
+            mode, // mode — eg WebGLRenderingContext::LINES, ::POINTS or ::TRIANGLES
+            indices,
+            cnnx_map.0, // first — index of the first index to draw
             cnnx_map.1, // count — number of vertices per instance
             primcount, // primcount — number of instances
-        );
+        )
     }
 
 }
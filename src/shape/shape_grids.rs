@@ -1,6 +1,6 @@
 use web_sys::WebGlRenderingContext as GL;
 use crate::develop::GuidesPreset;
-use crate::renderer_webgl::{RkWarm,RendererWebGl};
+use crate::renderer_webgl::{RendererError,RkWarm,RendererWebGl};
 use super::point_3::Point3;
 
 // The number of points in each size.
@@ -74,12 +74,12 @@ impl ShapeGrids {
         &self,
         r: &RendererWebGl, // the app’s singleton Renderer instance
         guides_preset: &GuidesPreset,
-    ) {
+    ) -> Result<(), RendererError> {
         let vertices = match guides_preset {
             GuidesPreset::GridsOnly1m | GuidesPreset::All1m => self.vertices_map.m1,
             _ => self.vertices_map.m10, // must be ::GridsOnly10m or ::All10m
         };
-        RkWarm::draw(r, GL::POINTS, vertices.0, vertices.1);
+        RkWarm::draw(r, GL::POINTS, vertices.0, vertices.1)
     }
 
 }
@@ -1,10 +1,60 @@
 use super::point_3::Point3;
+use super::shape_box::{BOX_EDGES,BOX_POINTS,box_corners};
 
 const LOD0_POINTS: usize = 5;
 const LOD0_CNNX: i32 = 6;
 const LOD1_POINTS: usize = 9;
 const LOD1_CNNX: i32 = 14;
 
+// After both LoDs come a copy of each of their points, for the tips of the
+// normal spikes, and then the corners of the bounding box.
+const TIP_POINTS: usize = LOD0_POINTS + LOD1_POINTS;
+
+// Each triangle, as indices into `lod0_points`.
+/*  2 ------- 3
+     \'.   .'/
+      \ '*' /  * is where 0 and 4 overlap
+       \ | /
+        \|/
+         1
+*/
+const LOD0_FACES: [[u16; 3]; LOD0_CNNX as usize] = [
+    [0, 1, 2], // upper - nose - tail_left
+    [0, 1, 3], // upper - nose - tail_right
+    [0, 2, 3], // upper - tail_left - tail_right
+    [4, 1, 2], // lower - nose - tail_left
+    [4, 1, 3], // lower - nose - tail_right
+    [4, 2, 3], // lower - tail_left - tail_right
+];
+
+// Each triangle, as indices into `lod1_points`.
+/*
+        .4.
+      .' | '.
+    3'   |   '5
+    | '. | .' |
+    |  .'*'.  |  * is where 0 and 8 overlap
+    6.' / \ '.7
+     \ /   \ /
+      1 --- 2
+*/
+const LOD1_FACES: [[u16; 3]; LOD1_CNNX as usize] = [
+    [0, 1, 2], // upper - nose_left - nose_right
+    [0, 1, 6], // upper - nose_left - mid_left
+    [0, 6, 3], // upper - mid_left - tail_left
+    [0, 3, 4], // upper - tail_left - tail_mid
+    [0, 4, 5], // upper - tail_mid - tail_right
+    [0, 5, 7], // upper - tail_right - mid_right
+    [0, 7, 2], // upper - mid_right - nose_right
+    [8, 1, 2], // lower - nose_left - nose_right
+    [8, 1, 6], // lower - nose_left - mid_left
+    [8, 6, 3], // lower - mid_left - tail_left
+    [8, 3, 4], // lower - tail_left - tail_mid
+    [8, 4, 5], // lower - tail_mid - tail_right
+    [8, 5, 7], // lower - tail_right - mid_right
+    [8, 7, 2], // lower - mid_right - nose_right
+];
+
 pub struct LodOffsets {
     lod0: u16,
     lod1: u16,
@@ -13,12 +63,20 @@ pub struct LodOffsets {
 pub struct StartIndices {
     pub colors: LodOffsets,
     pub cnnx: LodOffsets,
+    pub normals: LodOffsets,
+    pub spikes: LodOffsets,
+    pub uvs: LodOffsets,
     pub vertices: LodOffsets,
 }
 
 pub struct ShapeNubbinCnnxMap {
-    pub lod0: (i32,i32), // (offset,count)
+    pub lod0: (i32,i32), // (first,count) — in indices, not bytes
     pub lod1: (i32,i32),
+    pub lod0_edges: (i32,i32), // pairs of indices, for GL::LINES
+    pub lod1_edges: (i32,i32),
+    pub lod0_normals: (i32,i32), // pairs of indices, from each point to its spike’s tip
+    pub lod1_normals: (i32,i32),
+    pub bounds: (i32,i32), // pairs of indices, round the nubbin’s bounding box
 }
 
 /// A rounded tetrahedron which could look like a seed pod if used in a Furl.
@@ -44,6 +102,11 @@ impl ShapeNubbin {
             cnnx_map: ShapeNubbinCnnxMap {
                 lod0: (0,0),
                 lod1: (0,0),
+                lod0_edges: (0,0),
+                lod1_edges: (0,0),
+                lod0_normals: (0,0),
+                lod1_normals: (0,0),
+                bounds: (0,0),
             },
             /*  2 ------- 3
                  \'.   .'/
@@ -84,11 +147,30 @@ impl ShapeNubbin {
             start_indices: StartIndices { // values in here will be updated
                 colors: LodOffsets { lod0: 0, lod1: 0 },
                 cnnx: LodOffsets { lod0: 0, lod1: 0 },
+                normals: LodOffsets { lod0: 0, lod1: 0 },
+                spikes: LodOffsets { lod0: 0, lod1: 0 },
+                uvs: LodOffsets { lod0: 0, lod1: 0 },
                 vertices: LodOffsets { lod0: 0, lod1: 0 },
             },
         }
     }
 
+    /// The smallest box which holds both LoDs, as its lowest and highest
+    /// corners.
+    pub fn get_bounds(
+        &self,
+    ) -> (Point3, Point3) {
+        let points = self.lod0_points.iter().chain(self.lod1_points.iter());
+        let min = points.clone().fold(Point3::new(f32::MAX, f32::MAX, f32::MAX),
+            |min, p| Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)));
+        let max = points.fold(Point3::new(f32::MIN, f32::MIN, f32::MIN),
+            |max, p| Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)));
+        (min, max)
+    }
+
+    /// The triangles of each LoD, followed by each LoD’s edges. Each edge is
+    /// listed once, so `GL::LINES` draws the nubbin’s true topology. Then come
+    /// the normal spikes of each LoD, and the bounding box, also for LINES.
     // @TODO should all be anticlockwise?
     pub fn get_cnnx(
         &mut self,
@@ -98,56 +180,40 @@ impl ShapeNubbin {
         let l1 = self.start_indices.colors.lod1;
         assert!(l0 == self.start_indices.vertices.lod0, "lod0 offset mismatch");
         assert!(l1 == self.start_indices.vertices.lod1, "lod1 offset mismatch");
+        assert!(l0 == self.start_indices.normals.lod0, "lod0 normals offset mismatch");
+        assert!(l1 == self.start_indices.normals.lod1, "lod1 normals offset mismatch");
+        assert!(l0 == self.start_indices.uvs.lod0, "lod0 uvs offset mismatch");
+        assert!(l1 == self.start_indices.uvs.lod1, "lod1 uvs offset mismatch");
+        assert!(l0 == self.start_indices.spikes.lod0, "lod0 spikes offset mismatch");
+        assert!(l1 == self.start_indices.spikes.lod1, "lod1 spikes offset mismatch");
+        let tips = l0 + TIP_POINTS as u16;
+        let bounds = tips + TIP_POINTS as u16;
 
-        let cnnx: Vec<u16> = vec![
-
-            // lod0
-            /*  2 ------- 3
-                 \'.   .'/
-                  \ '*' /  * is where 0 and 4 overlap
-                   \ | /
-                    \|/
-                     1
-            */
-            l0+0, l0+1, l0+2, // upper - nose - tail_left
-            l0+0, l0+1, l0+3, // upper - nose - tail_right
-            l0+0, l0+2, l0+3, // upper - tail_left - tail_right
-            l0+4, l0+1, l0+2, // lower - nose - tail_left
-            l0+4, l0+1, l0+3, // lower - nose - tail_right
-            l0+4, l0+2, l0+3, // lower - tail_left - tail_right
-
-            // lod1
-            /*
-                    .4.
-                  .' | '.
-                3'   |   '5
-                | '. | .' |
-                |  .'*'.  |  * is where 0 and 8 overlap
-                6.' / \ '.7
-                 \ /   \ /
-                  1 --- 2
-            */
-            l1+0, l1+1, l1+2, // upper - nose_left - nose_right
-            l1+0, l1+1, l1+6, // upper - nose_left - mid_left
-            l1+0, l1+6, l1+3, // upper - mid_left - tail_left
-            l1+0, l1+3, l1+4, // upper - tail_left - tail_mid
-            l1+0, l1+4, l1+5, // upper - tail_mid - tail_right
-            l1+0, l1+5, l1+7, // upper - tail_right - mid_right
-            l1+0, l1+7, l1+2, // upper - mid_right - nose_right
-            l1+8, l1+1, l1+2, // lower - nose_left - nose_right
-            l1+8, l1+1, l1+6, // lower - nose_left - mid_left
-            l1+8, l1+6, l1+3, // lower - mid_left - tail_left
-            l1+8, l1+3, l1+4, // lower - tail_left - tail_mid
-            l1+8, l1+4, l1+5, // lower - tail_mid - tail_right
-            l1+8, l1+5, l1+7, // lower - tail_right - mid_right
-            l1+8, l1+7, l1+2, // lower - mid_right - nose_right
-        ];
+        let mut cnnx: Vec<u16> = vec![];
+        for face in LOD0_FACES.iter() { cnnx.extend(face.iter().map(|i| l0 + i)) }
+        for face in LOD1_FACES.iter() { cnnx.extend(face.iter().map(|i| l1 + i)) }
+        let lod0_edges = unique_edges(&LOD0_FACES);
+        let lod1_edges = unique_edges(&LOD1_FACES);
+        for edge in lod0_edges.iter() { cnnx.extend(edge.iter().map(|i| l0 + i)) }
+        for edge in lod1_edges.iter() { cnnx.extend(edge.iter().map(|i| l1 + i)) }
+        for i in 0..TIP_POINTS as u16 { cnnx.extend([l0 + i, tips + i].iter()) }
+        for edge in BOX_EDGES.iter() { cnnx.extend(edge.iter().map(|i| bounds + i)) }
 
-        self.start_indices.cnnx.lod0 = ((start_index + 0) * 2) as u16;
-        self.start_indices.cnnx.lod1 = ((start_index + 18) * 2) as u16;
+        self.start_indices.cnnx.lod0 = (start_index + 0) as u16;
+        self.start_indices.cnnx.lod1 = (start_index + 18) as u16;
 
         self.cnnx_map.lod0 = (self.start_indices.cnnx.lod0 as i32,LOD0_CNNX * 3);
         self.cnnx_map.lod1 = (self.start_indices.cnnx.lod1 as i32,LOD1_CNNX * 3);
+        let lod0_edges_start = start_index as i32 + (LOD0_CNNX + LOD1_CNNX) * 3;
+        let lod1_edges_start = lod0_edges_start + lod0_edges.len() as i32 * 2;
+        self.cnnx_map.lod0_edges = (lod0_edges_start,lod0_edges.len() as i32 * 2);
+        self.cnnx_map.lod1_edges = (lod1_edges_start,lod1_edges.len() as i32 * 2);
+        let lod0_normals_start = lod1_edges_start + lod1_edges.len() as i32 * 2;
+        let lod1_normals_start = lod0_normals_start + LOD0_POINTS as i32 * 2;
+        let bounds_start = lod1_normals_start + LOD1_POINTS as i32 * 2;
+        self.cnnx_map.lod0_normals = (lod0_normals_start,LOD0_POINTS as i32 * 2);
+        self.cnnx_map.lod1_normals = (lod1_normals_start,LOD1_POINTS as i32 * 2);
+        self.cnnx_map.bounds = (bounds_start,BOX_EDGES.len() as i32 * 2);
 
         cnnx
     }
@@ -156,7 +222,7 @@ impl ShapeNubbin {
         &mut self,
         start_vertex: usize,
     ) -> Vec<f32> {
-        let colors: Vec<f32> = vec![
+        let mut colors: Vec<f32> = vec![
 
             // lod0
             0.3, 0.9, 0.3, // 0 upper       green (positive y direction)
@@ -176,6 +242,8 @@ impl ShapeNubbin {
             0.5, 0.5, 0.5, // 7 mid_right    grey @TODO
             0.6, 0.0, 0.7, // 8 lower       magenta (negative y direction)
         ];
+        colors.append(&mut colors.clone()); // the tips of the normal spikes
+        colors.append(&mut vec![1.; BOX_POINTS * 3]); // the bounding box, in white
 
         self.start_indices.colors.lod0 = (start_vertex + 0) as u16;
         self.start_indices.colors.lod1 = (start_vertex + LOD0_POINTS) as u16;
@@ -183,13 +251,76 @@ impl ShapeNubbin {
         colors
     }
 
+    /// One unit normal per vertex, in the same order as `get_vertices()`. Each
+    /// is the average of the normals of the faces which share that vertex, so
+    /// the nubbin is smoothly shaded, and has no hard edges.
+    pub fn get_normals(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<i16> {
+        let mut normals: Vec<i16> = vec![];
+        normals.append(&mut smooth_normals(&self.lod0_points, &LOD0_FACES));
+        normals.append(&mut smooth_normals(&self.lod1_points, &LOD1_FACES));
+        normals.append(&mut normals.clone()); // the tips of the normal spikes
+
+        // Each corner of the bounding box faces away from its middle.
+        let (min, max) = self.get_bounds();
+        let middle = [(min.x + max.x) / 2., (min.y + max.y) / 2., (min.z + max.z) / 2.];
+        for corner in box_corners(min, max).iter() {
+            let outwards = normalize([corner.x - middle[0], corner.y - middle[1], corner.z - middle[2]]);
+            normals.extend(outwards.iter().map(|component| (component * i16::MAX as f32).round() as i16));
+        }
+
+        self.start_indices.normals.lod0 = start_vertex as u16;
+        self.start_indices.normals.lod1 = (start_vertex + LOD0_POINTS) as u16;
+
+        normals
+    }
+
+    /// One number per vertex, in the same order as `get_vertices()`. The shader
+    /// moves each vertex along its normal by this much times the spike length,
+    /// so only the tips of the normal spikes are 1.0.
+    pub fn get_spikes(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<f32> {
+        let mut spikes: Vec<f32> = vec![0.; TIP_POINTS];
+        spikes.append(&mut vec![1.; TIP_POINTS]);
+        spikes.append(&mut vec![0.; BOX_POINTS]);
+
+        self.start_indices.spikes.lod0 = start_vertex as u16;
+        self.start_indices.spikes.lod1 = (start_vertex + LOD0_POINTS) as u16;
+
+        spikes
+    }
+
+    /// One texture coordinate per vertex, in the same order as `get_vertices()`.
+    /// The texture is laid over the nubbin from above, with its left edge at
+    /// the tail’s left, and `v` running from the tail (0.0) to the nose (1.0).
+    /// So the upper and lower points share the same coordinate.
+    pub fn get_uvs(
+        &mut self,
+        start_vertex: usize,
+    ) -> Vec<f32> {
+        let mut uvs: Vec<f32> = vec![];
+        uvs.append(&mut planar_uvs(&self.lod0_points));
+        uvs.append(&mut planar_uvs(&self.lod1_points));
+        uvs.append(&mut uvs.clone()); // the tips of the normal spikes
+        uvs.append(&mut vec![0.; BOX_POINTS * 2]); // the bounding box is never textured
+
+        self.start_indices.uvs.lod0 = start_vertex as u16;
+        self.start_indices.uvs.lod1 = (start_vertex + LOD0_POINTS) as u16;
+
+        uvs
+    }
+
     pub fn get_vertices(
         &mut self,
         start_vertex: usize,
     ) -> Vec<f32> {
         let p0 = &self.lod0_points;
         let p1 = &self.lod1_points;
-        let vertices: Vec<f32> = vec![
+        let mut vertices: Vec<f32> = vec![
 
             // lod0
             p0[0].x, p0[0].y, p0[0].z, // upper
@@ -209,6 +340,9 @@ impl ShapeNubbin {
             p1[7].x, p1[7].y, p1[7].z, // 7 mid_right
             p1[8].x, p1[8].y, p1[8].z, // 8 lower
         ];
+        vertices.append(&mut vertices.clone()); // the tips of the normal spikes
+        let (min, max) = self.get_bounds();
+        vertices.extend(box_corners(min, max).iter().flat_map(|p| vec![p.x, p.y, p.z]));
 
         self.start_indices.vertices.lod0 = (start_vertex + 0) as u16;
         self.start_indices.vertices.lod1 = (start_vertex + LOD0_POINTS) as u16;
@@ -217,3 +351,110 @@ impl ShapeNubbin {
     }
 
 }
+
+
+
+
+// HELPERS
+
+/// Averages the normals of the faces around each point, and packs the result
+/// as i16s, ready for an `AttributeKind::I16Vec3Norm` attribute.
+fn smooth_normals(
+    points: &[Point3],
+    faces: &[[u16; 3]],
+) -> Vec<i16> {
+    // The faces are not all wound the same way, so each face normal is turned
+    // to point away from the middle of the nubbin.
+    let n = points.len() as f32;
+    let middle = points.iter().fold([0.; 3], |sum, p| [sum[0] + p.x / n, sum[1] + p.y / n, sum[2] + p.z / n]);
+    let mut sums = vec![[0f32; 3]; points.len()];
+    for face in faces.iter() {
+        let [a, b, c] = [points[face[0] as usize], points[face[1] as usize], points[face[2] as usize]];
+        let mut normal = normalize(cross(
+            [b.x - a.x, b.y - a.y, b.z - a.z],
+            [c.x - a.x, c.y - a.y, c.z - a.z],
+        ));
+        let outwards = [
+            (a.x + b.x + c.x) / 3. - middle[0],
+            (a.y + b.y + c.y) / 3. - middle[1],
+            (a.z + b.z + c.z) / 3. - middle[2],
+        ];
+        if normal[0] * outwards[0] + normal[1] * outwards[1] + normal[2] * outwards[2] < 0. {
+            normal = [-normal[0], -normal[1], -normal[2]];
+        }
+        for i in face.iter() {
+            let sum = &mut sums[*i as usize];
+            for axis in 0..3 { sum[axis] += normal[axis] }
+        }
+    }
+    sums.iter()
+        .flat_map(|sum| normalize(*sum))
+        .map(|component| (component * i16::MAX as f32).round() as i16)
+        .collect()
+}
+
+/// Every edge of the faces, once each, with its lower index first. Neighbouring
+/// faces share edges, so outlining each face would draw most edges twice.
+fn unique_edges(
+    faces: &[[u16; 3]],
+) -> Vec<[u16; 2]> {
+    let mut edges: Vec<[u16; 2]> = vec![];
+    for face in faces.iter() {
+        for &(a, b) in [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])].iter() {
+            let edge = if a < b { [a, b] } else { [b, a] };
+            if ! edges.contains(&edge) { edges.push(edge) }
+        }
+    }
+    edges
+}
+
+/// Projects each point straight down onto the nubbin’s footprint, and scales
+/// the footprint to fill the 0.0 to 1.0 texture coordinate range.
+fn planar_uvs(
+    points: &[Point3],
+) -> Vec<f32> {
+    let min_x = points.iter().fold(f32::MAX, |min, p| min.min(p.x));
+    let max_x = points.iter().fold(f32::MIN, |max, p| max.max(p.x));
+    let min_z = points.iter().fold(f32::MAX, |min, p| min.min(p.z));
+    let max_z = points.iter().fold(f32::MIN, |max, p| max.max(p.z));
+    points.iter()
+        .flat_map(|p| vec![
+            (p.x - min_x) / (max_x - min_x),
+            (p.z - min_z) / (max_z - min_z),
+        ])
+        .collect()
+}
+
+fn cross(
+    a: [f32; 3],
+    b: [f32; 3],
+) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Returns the zero vector unchanged, rather than dividing by zero.
+fn normalize(
+    v: [f32; 3],
+) -> [f32; 3] {
+    let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if length == 0. { return v }
+    [v[0] / length, v[1] / length, v[2] / length]
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_shared_edges_once() {
+        let faces = [[0, 1, 2], [2, 1, 3]];
+        assert_eq!(unique_edges(&faces), vec![[0, 1], [1, 2], [0, 2], [1, 3], [2, 3]]);
+    }
+}